@@ -0,0 +1,108 @@
+use super::column_builder::ColumnBuilder;
+use crate::protocol::parts::resultset::ResultSet;
+use crate::{HdbError, HdbResult};
+use arrow_array::RecordBatch;
+use arrow_schema::{Field, Schema, SchemaRef};
+use std::sync::Arc;
+
+/// An iterator over the rows of a `ResultSet`, in form of Arrow `RecordBatch`es.
+///
+/// Is created with
+/// [`ResultSet::into_arrow_batches()`](struct.ResultSet.html#method.into_arrow_batches).
+///
+/// The Arrow schema is derived from the resultset's metadata:
+///
+/// | HANA type | Arrow type |
+/// |-----------|------------|
+/// | TINYINT, SMALLINT, INT, BIGINT | UInt8, Int16, Int32, Int64 |
+/// | REAL, DOUBLE | Float32, Float64 |
+/// | BOOLEAN | Boolean |
+/// | DECIMAL | Decimal128 |
+/// | character types | Utf8 |
+/// | binary and spatial types | Binary |
+/// | LONGDATE, SECONDDATE | Timestamp(Nanosecond), Timestamp(Second) |
+/// | DAYDATE, SECONDTIME | Date32, Time32(Second) |
+/// | CLOB, NCLOB, TEXT | LargeUtf8 |
+/// | BLOB | LargeBinary |
+///
+/// Nanosecond timestamps cannot represent the years before 1677 and after 2262;
+/// LONGDATE values outside this range cause an error.
+///
+/// DECIMAL columns without a fixed precision and scale (floating point decimals)
+/// are mapped to `Decimal128(38, 18)`. Values that do not fit into the Arrow type,
+/// e.g. with more than 18 fractional digits, cause an error rather than being cut off.
+#[derive(Debug)]
+pub struct ArrowBatches {
+    resultset: ResultSet,
+    schema: SchemaRef,
+    batch_size: usize,
+}
+
+impl ArrowBatches {
+    pub(crate) fn try_new(resultset: ResultSet, batch_size: usize) -> HdbResult<ArrowBatches> {
+        if batch_size == 0 {
+            return Err(HdbError::Usage("batch_size must not be 0".to_owned()));
+        }
+        let md = resultset.metadata();
+        let mut fields = Vec::with_capacity(md.number_of_fields());
+        for i in 0..md.number_of_fields() {
+            fields.push(Field::new(
+                md.displayname(i)?.as_str(),
+                ColumnBuilder::data_type(md.type_id(i)?, md.precision(i)?, md.scale(i)?),
+                md.nullable(i)?,
+            ));
+        }
+        Ok(ArrowBatches {
+            resultset,
+            schema: Arc::new(Schema::new(fields)),
+            batch_size,
+        })
+    }
+
+    /// Returns the Arrow schema of the batches.
+    pub fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn next_batch(&mut self) -> HdbResult<Option<RecordBatch>> {
+        let mut builders = self
+            .schema
+            .fields()
+            .iter()
+            .map(|field| ColumnBuilder::new(field.data_type(), self.batch_size))
+            .collect::<HdbResult<Vec<ColumnBuilder>>>()?;
+
+        let mut no_of_rows = 0;
+        while no_of_rows < self.batch_size {
+            match self.resultset.next_row()? {
+                Some(row) => {
+                    for (builder, value) in builders.iter_mut().zip(row) {
+                        builder.append(value)?;
+                    }
+                    no_of_rows += 1;
+                }
+                None => break,
+            }
+        }
+        if no_of_rows == 0 {
+            return Ok(None);
+        }
+
+        let columns = builders.iter_mut().map(ColumnBuilder::finish).collect();
+        Ok(Some(
+            RecordBatch::try_new(Arc::clone(&self.schema), columns)
+                .map_err(|e| HdbError::Impl(format!("cannot create RecordBatch: {}", e), None))?,
+        ))
+    }
+}
+
+impl Iterator for ArrowBatches {
+    type Item = HdbResult<RecordBatch>;
+    fn next(&mut self) -> Option<HdbResult<RecordBatch>> {
+        match self.next_batch() {
+            Ok(Some(batch)) => Some(Ok(batch)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
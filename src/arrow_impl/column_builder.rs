@@ -0,0 +1,395 @@
+use crate::protocol::parts::hdb_value::HdbValue;
+use crate::protocol::parts::type_id::TypeId;
+use crate::{HdbError, HdbResult};
+use arrow_array::builder::{
+    BinaryBuilder, BooleanBuilder, Date32Builder, Decimal128Builder, Float32Builder,
+    Float64Builder, Int16Builder, Int32Builder, Int64Builder, LargeBinaryBuilder,
+    LargeStringBuilder, StringBuilder, Time32SecondBuilder, TimestampNanosecondBuilder,
+    TimestampSecondBuilder, UInt8Builder,
+};
+use arrow_array::types::{Decimal128Type, DecimalType};
+use arrow_array::ArrayRef;
+use arrow_schema::{DataType, TimeUnit};
+use num::ToPrimitive;
+use serde_db::de::ConversionError;
+use std::sync::Arc;
+
+// Days from 0001-01-01 to 1970-01-01
+const DAYS_UNTIL_EPOCH: i64 = 719_162;
+const SECONDS_PER_DAY: i64 = 86_400;
+const LONGDATE_TICKS_PER_SECOND: i64 = 10_000_000;
+const NANOSECONDS_PER_LONGDATE_TICK: i64 = 100;
+
+// Precision and scale for DECIMAL columns without fixed precision and scale
+const FLOATING_DECIMAL_PRECISION: u8 = 38;
+const FLOATING_DECIMAL_SCALE: i8 = 18;
+
+// An Arrow builder for a column, with the conversion from HdbValue.
+#[derive(Debug)]
+pub(crate) enum ColumnBuilder {
+    TinyInt(UInt8Builder),
+    SmallInt(Int16Builder),
+    Int(Int32Builder),
+    BigInt(Int64Builder),
+    Real(Float32Builder),
+    Double(Float64Builder),
+    Boolean(BooleanBuilder),
+    Decimal(Decimal128Builder, u8, i8),
+    String(StringBuilder),
+    Binary(BinaryBuilder),
+    LongDate(TimestampNanosecondBuilder),
+    SecondDate(TimestampSecondBuilder),
+    DayDate(Date32Builder),
+    SecondTime(Time32SecondBuilder),
+    CharLob(LargeStringBuilder),
+    BinaryLob(LargeBinaryBuilder),
+}
+
+impl ColumnBuilder {
+    // The Arrow type for a column of the given type.
+    pub fn data_type(type_id: TypeId, precision: i16, scale: i16) -> DataType {
+        match type_id {
+            TypeId::TINYINT => DataType::UInt8,
+            TypeId::SMALLINT => DataType::Int16,
+            TypeId::INT => DataType::Int32,
+            TypeId::BIGINT => DataType::Int64,
+            TypeId::REAL => DataType::Float32,
+            TypeId::DOUBLE => DataType::Float64,
+            TypeId::BOOLEAN => DataType::Boolean,
+            TypeId::DECIMAL | TypeId::FIXED8 | TypeId::FIXED12 | TypeId::FIXED16 => {
+                if precision > 0
+                    && precision <= i16::from(FLOATING_DECIMAL_PRECISION)
+                    && scale >= 0
+                    && scale <= precision
+                {
+                    DataType::Decimal128(precision as u8, scale as i8)
+                } else {
+                    DataType::Decimal128(FLOATING_DECIMAL_PRECISION, FLOATING_DECIMAL_SCALE)
+                }
+            }
+            TypeId::CHAR
+            | TypeId::VARCHAR
+            | TypeId::NCHAR
+            | TypeId::NVARCHAR
+            | TypeId::STRING
+            | TypeId::NSTRING
+            | TypeId::SHORTTEXT
+            | TypeId::ALPHANUM => DataType::Utf8,
+            TypeId::BINARY
+            | TypeId::VARBINARY
+            | TypeId::BSTRING
+            | TypeId::GEOMETRY
+            | TypeId::POINT => DataType::Binary,
+            TypeId::LONGDATE => DataType::Timestamp(TimeUnit::Nanosecond, None),
+            TypeId::SECONDDATE => DataType::Timestamp(TimeUnit::Second, None),
+            TypeId::DAYDATE => DataType::Date32,
+            TypeId::SECONDTIME => DataType::Time32(TimeUnit::Second),
+            TypeId::CLOB | TypeId::NCLOB | TypeId::TEXT => DataType::LargeUtf8,
+            TypeId::BLOB | TypeId::BINTEXT | TypeId::BLOCATOR => DataType::LargeBinary,
+        }
+    }
+
+    pub fn new(data_type: &DataType, capacity: usize) -> HdbResult<ColumnBuilder> {
+        Ok(match *data_type {
+            DataType::UInt8 => ColumnBuilder::TinyInt(UInt8Builder::with_capacity(capacity)),
+            DataType::Int16 => ColumnBuilder::SmallInt(Int16Builder::with_capacity(capacity)),
+            DataType::Int32 => ColumnBuilder::Int(Int32Builder::with_capacity(capacity)),
+            DataType::Int64 => ColumnBuilder::BigInt(Int64Builder::with_capacity(capacity)),
+            DataType::Float32 => ColumnBuilder::Real(Float32Builder::with_capacity(capacity)),
+            DataType::Float64 => ColumnBuilder::Double(Float64Builder::with_capacity(capacity)),
+            DataType::Boolean => ColumnBuilder::Boolean(BooleanBuilder::with_capacity(capacity)),
+            DataType::Decimal128(precision, scale) => ColumnBuilder::Decimal(
+                Decimal128Builder::with_capacity(capacity)
+                    .with_precision_and_scale(precision, scale)
+                    .map_err(|e| HdbError::Impl(format!("invalid Decimal128 type: {}", e), None))?,
+                precision,
+                scale,
+            ),
+            DataType::Utf8 => ColumnBuilder::String(StringBuilder::new()),
+            DataType::Binary => ColumnBuilder::Binary(BinaryBuilder::new()),
+            DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+                ColumnBuilder::LongDate(TimestampNanosecondBuilder::with_capacity(capacity))
+            }
+            DataType::Timestamp(TimeUnit::Second, _) => {
+                ColumnBuilder::SecondDate(TimestampSecondBuilder::with_capacity(capacity))
+            }
+            DataType::Date32 => ColumnBuilder::DayDate(Date32Builder::with_capacity(capacity)),
+            DataType::Time32(TimeUnit::Second) => {
+                ColumnBuilder::SecondTime(Time32SecondBuilder::with_capacity(capacity))
+            }
+            DataType::LargeUtf8 => ColumnBuilder::CharLob(LargeStringBuilder::new()),
+            DataType::LargeBinary => ColumnBuilder::BinaryLob(LargeBinaryBuilder::new()),
+            ref dt => {
+                return Err(HdbError::Impl(
+                    format!("no builder for Arrow type {:?}", dt),
+                    None,
+                ));
+            }
+        })
+    }
+
+    pub fn append(&mut self, value: HdbValue<'static>) -> HdbResult<()> {
+        if let HdbValue::NULL = value {
+            self.append_null();
+            return Ok(());
+        }
+        match (self, value) {
+            (ColumnBuilder::TinyInt(b), HdbValue::TINYINT(i)) => b.append_value(i),
+            (ColumnBuilder::SmallInt(b), HdbValue::SMALLINT(i)) => b.append_value(i),
+            (ColumnBuilder::Int(b), HdbValue::INT(i)) => b.append_value(i),
+            (ColumnBuilder::BigInt(b), HdbValue::BIGINT(i)) => b.append_value(i),
+            (ColumnBuilder::Real(b), HdbValue::REAL(f)) => b.append_value(f),
+            (ColumnBuilder::Double(b), HdbValue::DOUBLE(f)) => b.append_value(f),
+            (ColumnBuilder::Boolean(b), HdbValue::BOOLEAN(v)) => b.append_value(v),
+            (ColumnBuilder::Decimal(b, precision, scale), HdbValue::DECIMAL(bd)) => {
+                let scaled = bd.with_scale(i64::from(*scale));
+                if scaled != bd {
+                    return Err(HdbError::Conversion(
+                        ConversionError::NumberRange(format!(
+                            "decimal value {} has more than {} fractional digits",
+                            bd, scale
+                        )),
+                        None,
+                    ));
+                }
+                let value = scaled
+                    .as_bigint_and_exponent()
+                    .0
+                    .to_i128()
+                    .filter(|i| Decimal128Type::validate_decimal_precision(*i, *precision).is_ok())
+                    .ok_or_else(|| {
+                        HdbError::Conversion(
+                            ConversionError::NumberRange(format!(
+                                "decimal value {} exceeds Decimal128({}, {})",
+                                bd, precision, scale
+                            )),
+                            None,
+                        )
+                    })?;
+                b.append_value(value)
+            }
+            (ColumnBuilder::String(b), HdbValue::STRING(s)) => b.append_value(s),
+            (ColumnBuilder::Binary(b), HdbValue::BINARY(v))
+            | (ColumnBuilder::Binary(b), HdbValue::GEOMETRY(v))
+            | (ColumnBuilder::Binary(b), HdbValue::POINT(v)) => b.append_value(v),
+            (ColumnBuilder::LongDate(b), HdbValue::LONGDATE(ld)) => {
+                // 100ns-ticks since 0001-01-01, plus 1;
+                // nanoseconds only cover the years 1677 to 2262
+                let ticks = (*ld.ref_raw() - 1).max(0)
+                    - DAYS_UNTIL_EPOCH * SECONDS_PER_DAY * LONGDATE_TICKS_PER_SECOND;
+                b.append_value(
+                    ticks
+                        .checked_mul(NANOSECONDS_PER_LONGDATE_TICK)
+                        .ok_or_else(|| {
+                            HdbError::Conversion(
+                                ConversionError::NumberRange(format!(
+                                    "LONGDATE value {} exceeds the range of Timestamp(ns)",
+                                    ld
+                                )),
+                                None,
+                            )
+                        })?,
+                )
+            }
+            (ColumnBuilder::SecondDate(b), HdbValue::SECONDDATE(sd)) => {
+                // seconds since 0001-01-01, plus 1
+                b.append_value((*sd.ref_raw() - 1).max(0) - DAYS_UNTIL_EPOCH * SECONDS_PER_DAY)
+            }
+            (ColumnBuilder::DayDate(b), HdbValue::DAYDATE(dd)) => {
+                // days since 0001-01-01, plus 1
+                b.append_value((*dd.ref_raw() - 1).max(0) - DAYS_UNTIL_EPOCH as i32)
+            }
+            (ColumnBuilder::SecondTime(b), HdbValue::SECONDTIME(st)) => {
+                // seconds since midnight, plus 1
+                b.append_value((*st.ref_raw()).max(1) as i32 - 1)
+            }
+            (ColumnBuilder::CharLob(b), HdbValue::CLOB(clob)) => {
+                b.append_value(clob.into_string()?)
+            }
+            (ColumnBuilder::CharLob(b), HdbValue::NCLOB(nclob)) => {
+                b.append_value(nclob.into_string()?)
+            }
+            (ColumnBuilder::BinaryLob(b), HdbValue::BLOB(blob)) => {
+                b.append_value(blob.into_bytes()?)
+            }
+            (builder, value) => {
+                return Err(HdbError::Impl(
+                    format!(
+                        "value {:?} does not fit into Arrow column of type {}",
+                        value,
+                        builder.kind()
+                    ),
+                    None,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn append_null(&mut self) {
+        match *self {
+            ColumnBuilder::TinyInt(ref mut b) => b.append_null(),
+            ColumnBuilder::SmallInt(ref mut b) => b.append_null(),
+            ColumnBuilder::Int(ref mut b) => b.append_null(),
+            ColumnBuilder::BigInt(ref mut b) => b.append_null(),
+            ColumnBuilder::Real(ref mut b) => b.append_null(),
+            ColumnBuilder::Double(ref mut b) => b.append_null(),
+            ColumnBuilder::Boolean(ref mut b) => b.append_null(),
+            ColumnBuilder::Decimal(ref mut b, _, _) => b.append_null(),
+            ColumnBuilder::String(ref mut b) => b.append_null(),
+            ColumnBuilder::Binary(ref mut b) => b.append_null(),
+            ColumnBuilder::LongDate(ref mut b) => b.append_null(),
+            ColumnBuilder::SecondDate(ref mut b) => b.append_null(),
+            ColumnBuilder::DayDate(ref mut b) => b.append_null(),
+            ColumnBuilder::SecondTime(ref mut b) => b.append_null(),
+            ColumnBuilder::CharLob(ref mut b) => b.append_null(),
+            ColumnBuilder::BinaryLob(ref mut b) => b.append_null(),
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match *self {
+            ColumnBuilder::TinyInt(_) => "UInt8",
+            ColumnBuilder::SmallInt(_) => "Int16",
+            ColumnBuilder::Int(_) => "Int32",
+            ColumnBuilder::BigInt(_) => "Int64",
+            ColumnBuilder::Real(_) => "Float32",
+            ColumnBuilder::Double(_) => "Float64",
+            ColumnBuilder::Boolean(_) => "Boolean",
+            ColumnBuilder::Decimal(_, _, _) => "Decimal128",
+            ColumnBuilder::String(_) => "Utf8",
+            ColumnBuilder::Binary(_) => "Binary",
+            ColumnBuilder::LongDate(_) => "Timestamp(ns)",
+            ColumnBuilder::SecondDate(_) => "Timestamp(s)",
+            ColumnBuilder::DayDate(_) => "Date32",
+            ColumnBuilder::SecondTime(_) => "Time32(s)",
+            ColumnBuilder::CharLob(_) => "LargeUtf8",
+            ColumnBuilder::BinaryLob(_) => "LargeBinary",
+        }
+    }
+
+    pub fn finish(&mut self) -> ArrayRef {
+        match *self {
+            ColumnBuilder::TinyInt(ref mut b) => Arc::new(b.finish()),
+            ColumnBuilder::SmallInt(ref mut b) => Arc::new(b.finish()),
+            ColumnBuilder::Int(ref mut b) => Arc::new(b.finish()),
+            ColumnBuilder::BigInt(ref mut b) => Arc::new(b.finish()),
+            ColumnBuilder::Real(ref mut b) => Arc::new(b.finish()),
+            ColumnBuilder::Double(ref mut b) => Arc::new(b.finish()),
+            ColumnBuilder::Boolean(ref mut b) => Arc::new(b.finish()),
+            ColumnBuilder::Decimal(ref mut b, _, _) => Arc::new(b.finish()),
+            ColumnBuilder::String(ref mut b) => Arc::new(b.finish()),
+            ColumnBuilder::Binary(ref mut b) => Arc::new(b.finish()),
+            ColumnBuilder::LongDate(ref mut b) => Arc::new(b.finish()),
+            ColumnBuilder::SecondDate(ref mut b) => Arc::new(b.finish()),
+            ColumnBuilder::DayDate(ref mut b) => Arc::new(b.finish()),
+            ColumnBuilder::SecondTime(ref mut b) => Arc::new(b.finish()),
+            ColumnBuilder::CharLob(ref mut b) => Arc::new(b.finish()),
+            ColumnBuilder::BinaryLob(ref mut b) => Arc::new(b.finish()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ColumnBuilder;
+    use crate::protocol::parts::hdb_value::HdbValue;
+    use crate::protocol::parts::type_id::TypeId;
+    use crate::types::{DayDate, LongDate};
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Date32Type, Decimal128Type, TimestampNanosecondType};
+    use arrow_array::Array;
+    use arrow_schema::{DataType, TimeUnit};
+    use bigdecimal::BigDecimal;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_data_types() {
+        assert_eq!(
+            ColumnBuilder::data_type(TypeId::DECIMAL, 10, 2),
+            DataType::Decimal128(10, 2)
+        );
+        assert_eq!(
+            ColumnBuilder::data_type(TypeId::DECIMAL, 34, 32767),
+            DataType::Decimal128(38, 18)
+        );
+        assert_eq!(
+            ColumnBuilder::data_type(TypeId::LONGDATE, 0, 0),
+            DataType::Timestamp(TimeUnit::Nanosecond, None)
+        );
+        assert_eq!(
+            ColumnBuilder::data_type(TypeId::DAYDATE, 0, 0),
+            DataType::Date32
+        );
+        assert_eq!(
+            ColumnBuilder::data_type(TypeId::NCLOB, 0, 0),
+            DataType::LargeUtf8
+        );
+        assert_eq!(
+            ColumnBuilder::data_type(TypeId::BLOB, 0, 0),
+            DataType::LargeBinary
+        );
+    }
+
+    #[test]
+    fn test_append_values() {
+        let mut builder = ColumnBuilder::new(&DataType::Decimal128(10, 2), 2).unwrap();
+        builder
+            .append(HdbValue::DECIMAL(BigDecimal::from_str("12.3").unwrap()))
+            .unwrap();
+        builder.append(HdbValue::NULL).unwrap();
+        let array = builder.finish();
+        let array = array.as_primitive::<Decimal128Type>();
+        assert_eq!(array.value(0), 1230);
+        assert!(array.is_null(1));
+
+        // digits that do not fit are not cut off silently
+        let mut builder = ColumnBuilder::new(&DataType::Decimal128(10, 2), 2).unwrap();
+        assert!(builder
+            .append(HdbValue::DECIMAL(BigDecimal::from_str("12.345").unwrap()))
+            .is_err());
+        assert!(builder
+            .append(HdbValue::DECIMAL(
+                BigDecimal::from_str("123456789").unwrap()
+            ))
+            .is_err());
+        let mut builder = ColumnBuilder::new(&DataType::Decimal128(38, 18), 1).unwrap();
+        assert!(builder
+            .append(HdbValue::DECIMAL(
+                BigDecimal::from_str("0.1234567890123456789").unwrap()
+            ))
+            .is_err());
+
+        // 1970-01-01 is day 719_162 after 0001-01-01, the raw values are shifted by 1
+        let mut builder = ColumnBuilder::new(&DataType::Date32, 1).unwrap();
+        builder
+            .append(HdbValue::DAYDATE(DayDate::new(719_163)))
+            .unwrap();
+        builder
+            .append(HdbValue::DAYDATE(DayDate::new(719_164)))
+            .unwrap();
+        let array = builder.finish();
+        let array = array.as_primitive::<Date32Type>();
+        assert_eq!(array.value(0), 0);
+        assert_eq!(array.value(1), 1);
+
+        let mut builder =
+            ColumnBuilder::new(&DataType::Timestamp(TimeUnit::Nanosecond, None), 1).unwrap();
+        builder
+            .append(HdbValue::LONGDATE(LongDate::new(
+                719_162 * 864_000_000_000 + 1 + 15,
+            )))
+            .unwrap();
+        // 9999-12-31 23:59:59.9999999 is out of the range of nanosecond timestamps
+        assert!(builder
+            .append(HdbValue::LONGDATE(LongDate::new(3_155_378_976_000_000_000)))
+            .is_err());
+        let array = builder.finish();
+        let array = array.as_primitive::<TimestampNanosecondType>();
+        assert_eq!(array.len(), 1);
+        assert_eq!(array.value(0), 1_500);
+
+        let mut builder = ColumnBuilder::new(&DataType::Int32, 1).unwrap();
+        assert!(builder.append(HdbValue::STRING("x".to_owned())).is_err());
+    }
+}
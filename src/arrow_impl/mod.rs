@@ -0,0 +1,9 @@
+//! Export of resultsets into Apache Arrow `RecordBatch`es (requires feature `arrow`).
+//!
+//! The rows are appended to the Arrow builders while they are fetched from the database,
+//! so the memory consumption is bounded by the fetch size and the batch size.
+
+mod arrow_batches;
+mod column_builder;
+
+pub use self::arrow_batches::ArrowBatches;
@@ -0,0 +1,114 @@
+use crate::prepared_statement::PreparedStatement;
+use crate::protocol::parts::hdb_value::HdbValue;
+use crate::protocol::parts::parameter_descriptor::ParameterDescriptor;
+use crate::{BatchResult, HdbResponse, HdbResult};
+use std::time::Duration;
+
+/// Asynchronous variant of [`PreparedStatement`](struct.PreparedStatement.html).
+///
+/// Is created with [`AsyncConnection::prepare()`](struct.AsyncConnection.html#method.prepare).
+///
+/// With feature `alpha_routing`, the executions are not routed to other index servers;
+/// they always run on the connection that prepared the statement.
+#[derive(Debug)]
+pub struct AsyncPreparedStatement(PreparedStatement);
+
+impl<'a> AsyncPreparedStatement {
+    /// Converts the input into a row of parameters,
+    /// if it is consistent with the metadata, and
+    /// executes the statement immediately.
+    ///
+    /// See [`PreparedStatement::execute()`](struct.PreparedStatement.html#method.execute).
+    pub async fn execute<T: serde::ser::Serialize>(&mut self, input: &T) -> HdbResult<HdbResponse> {
+        self.0.execute_async(input).await
+    }
+
+    /// Consumes the given HdbValues as a row of parameters for immediate execution.
+    ///
+    /// The data of `HdbValue::LOBSTREAM` values are read from the given readers
+    /// and transferred asynchronously to the database.
+    ///
+    /// See [`PreparedStatement::execute_row()`](struct.PreparedStatement.html#method.execute_row).
+    pub async fn execute_row(
+        &'a mut self,
+        hdb_values: Vec<HdbValue<'a>>,
+    ) -> HdbResult<HdbResponse> {
+        self.0.execute_row_async(hdb_values).await
+    }
+
+    /// Converts the input into a row of parameters and adds it to the batch of this
+    /// `AsyncPreparedStatement`, if it is consistent with the metadata.
+    pub fn add_batch<T: serde::ser::Serialize>(&mut self, input: &T) -> HdbResult<()> {
+        self.0.add_batch(input)
+    }
+
+    /// Consumes the input as a row of parameters for the batch.
+    pub fn add_row_to_batch(&mut self, hdb_values: Vec<HdbValue<'static>>) -> HdbResult<()> {
+        self.0.add_row_to_batch(hdb_values)
+    }
+
+    /// Executes the statement with the collected batch, and clears the batch.
+    pub async fn execute_batch(&mut self) -> HdbResult<HdbResponse> {
+        self.0.execute_batch_async().await
+    }
+
+    /// Executes the statement with the collected batch, clears the batch,
+    /// and reports the outcome for each row.
+    ///
+    /// See [`PreparedStatement::execute_batch_detailed()`](struct.PreparedStatement.html#method.execute_batch_detailed).
+    pub async fn execute_batch_detailed(&mut self) -> HdbResult<BatchResult> {
+        self.0.execute_batch_detailed_async().await
+    }
+
+    /// Executes the rows of a `BatchResult` again that were not processed
+    /// because an earlier row failed.
+    ///
+    /// See [`PreparedStatement::execute_failed_rows()`](struct.PreparedStatement.html#method.execute_failed_rows).
+    pub async fn execute_failed_rows(
+        &mut self,
+        batch_result: BatchResult,
+    ) -> HdbResult<BatchResult> {
+        self.0.execute_failed_rows_async(batch_result).await
+    }
+
+    /// Overrides the connection's query timeout for the executions of this statement.
+    ///
+    /// See [`PreparedStatement::set_query_timeout()`](struct.PreparedStatement.html#method.set_query_timeout).
+    pub fn set_query_timeout(&mut self, query_timeout: Duration) {
+        self.0.set_query_timeout(query_timeout)
+    }
+
+    /// Overrides the connection's fetch size for the resultsets of this statement.
+    ///
+    /// See [`PreparedStatement::set_fetch_size()`](struct.PreparedStatement.html#method.set_fetch_size).
+    pub fn set_fetch_size(&mut self, fetch_size: u32) {
+        self.0.set_fetch_size(fetch_size)
+    }
+
+    /// Overrides the connection's lob read length for the LOBs in the resultsets
+    /// of this statement.
+    ///
+    /// See [`PreparedStatement::set_lob_read_length()`](struct.PreparedStatement.html#method.set_lob_read_length).
+    pub fn set_lob_read_length(&mut self, lob_read_length: u32) {
+        self.0.set_lob_read_length(lob_read_length)
+    }
+
+    /// Overrides the connection's lob write length for the LOBs that are streamed
+    /// to the database with [`execute_row()`](#method.execute_row).
+    ///
+    /// See [`PreparedStatement::set_lob_write_length()`](struct.PreparedStatement.html#method.set_lob_write_length).
+    pub fn set_lob_write_length(&mut self, lob_write_length: usize) {
+        self.0.set_lob_write_length(lob_write_length)
+    }
+
+    /// Descriptors of all parameters of the prepared statement (in, out, inout).
+    pub fn parameter_descriptors(&self) -> Option<&[ParameterDescriptor]> {
+        self.0.parameter_descriptors()
+    }
+}
+
+impl From<PreparedStatement> for AsyncPreparedStatement {
+    fn from(ps: PreparedStatement) -> AsyncPreparedStatement {
+        AsyncPreparedStatement(ps)
+    }
+}
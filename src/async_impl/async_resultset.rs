@@ -0,0 +1,145 @@
+use crate::protocol::parts::column_batch::ColumnBatch;
+use crate::protocol::parts::resultset::{FetchPosition, ResultSet};
+use crate::protocol::parts::resultset_metadata::ResultSetMetadata;
+use crate::protocol::parts::row::Row;
+use crate::HdbResult;
+
+/// Asynchronous variant of [`ResultSet`](struct.ResultSet.html).
+///
+/// Rows that are not yet transported to the client are fetched asynchronously.
+#[derive(Debug)]
+pub struct AsyncResultSet(ResultSet);
+
+impl AsyncResultSet {
+    /// Translates the resultset into a given rust type that implements serde::Deserialize,
+    /// after having fetched all outstanding rows.
+    ///
+    /// See [`ResultSet::try_into()`](struct.ResultSet.html#method.try_into).
+    pub async fn try_into<'de, T>(mut self) -> HdbResult<T>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        self.fetch_all().await?;
+        self.0.try_into()
+    }
+
+    /// Converts the resultset into a single row.
+    pub async fn into_single_row(mut self) -> HdbResult<Row> {
+        self.fetch_all().await?;
+        self.0.into_single_row()
+    }
+
+    /// Access to metadata.
+    pub fn metadata(&self) -> &ResultSetMetadata {
+        self.0.metadata()
+    }
+
+    /// Returns the total number of rows in the resultset,
+    /// including those that still need to be fetched from the database,
+    /// but excluding those that have already been removed from the resultset.
+    pub async fn total_number_of_rows(&mut self) -> HdbResult<usize> {
+        self.fetch_all().await?;
+        self.0.total_number_of_rows()
+    }
+
+    /// Removes the next row and returns it, or None if the resultset is empty.
+    ///
+    /// Fetches further rows from the database asynchronously, if necessary.
+    pub async fn next_row(&mut self) -> HdbResult<Option<Row>> {
+        if self.0.needs_fetch()? {
+            self.fetch_next().await?;
+        }
+        self.0.next_row()
+    }
+
+    /// Removes the next rows and returns them column by column,
+    /// or None if the resultset is empty.
+    ///
+    /// See [`ResultSet::next_column_batch()`](struct.ResultSet.html#method.next_column_batch).
+    pub async fn next_column_batch(&mut self) -> HdbResult<Option<ColumnBatch>> {
+        loop {
+            if let Some(batch) = self.0.take_column_batch()? {
+                return Ok(Some(batch));
+            }
+            if self.0.is_complete()? {
+                return Ok(None);
+            }
+            self.0.set_decode_into_columns(true);
+            let result = self.fetch_next().await;
+            self.0.set_decode_into_columns(false);
+            result?;
+        }
+    }
+
+    /// Fetches all not yet transported result lines from the server.
+    pub async fn fetch_all(&mut self) -> HdbResult<()> {
+        while !self.0.is_complete()? {
+            self.fetch_next().await?;
+        }
+        Ok(())
+    }
+
+    /// Moves the cursor to the row with the given number and returns this row.
+    ///
+    /// See [`ResultSet::fetch_absolute()`](struct.ResultSet.html#method.fetch_absolute).
+    pub async fn fetch_absolute(&mut self, position: i32) -> HdbResult<Option<Row>> {
+        self.scroll(FetchPosition::Absolute(position)).await
+    }
+
+    /// Moves the cursor by the given number of rows and returns the row at the new position.
+    ///
+    /// See [`ResultSet::fetch_relative()`](struct.ResultSet.html#method.fetch_relative).
+    pub async fn fetch_relative(&mut self, offset: i32) -> HdbResult<Option<Row>> {
+        self.scroll(FetchPosition::Relative(offset)).await
+    }
+
+    /// Moves the cursor to the first row and returns it.
+    ///
+    /// See [`ResultSet::fetch_first()`](struct.ResultSet.html#method.fetch_first).
+    pub async fn fetch_first(&mut self) -> HdbResult<Option<Row>> {
+        self.scroll(FetchPosition::First).await
+    }
+
+    /// Moves the cursor to the last row and returns it.
+    ///
+    /// See [`ResultSet::fetch_last()`](struct.ResultSet.html#method.fetch_last).
+    pub async fn fetch_last(&mut self) -> HdbResult<Option<Row>> {
+        self.scroll(FetchPosition::Last).await
+    }
+
+    /// Converts this into a blocking `ResultSet`, after having fetched all outstanding rows.
+    pub async fn into_resultset(mut self) -> HdbResult<ResultSet> {
+        self.fetch_all().await?;
+        Ok(self.0)
+    }
+
+    async fn fetch_next(&mut self) -> HdbResult<()> {
+        trace!("AsyncResultSet::fetch_next()");
+        let (am_conn_core, request) = self.0.fetch_next_request()?;
+        let reply = am_conn_core
+            .full_send_async(request, None, None, &mut Some(&mut self.0))
+            .await?;
+        self.0.digest_fetch_reply(reply)
+    }
+
+    async fn scroll(&mut self, fetch_position: FetchPosition) -> HdbResult<Option<Row>> {
+        trace!("AsyncResultSet::scroll() to {:?}", fetch_position);
+        let (am_conn_core, request) = self.0.scroll_request(fetch_position)?;
+        let reply = am_conn_core
+            .full_send_async(request, None, None, &mut Some(&mut self.0))
+            .await?;
+        self.0.digest_scroll_reply(reply)
+    }
+}
+
+impl From<ResultSet> for AsyncResultSet {
+    fn from(rs: ResultSet) -> AsyncResultSet {
+        AsyncResultSet(rs)
+    }
+}
+
+impl std::fmt::Display for AsyncResultSet {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.0, fmt)
+    }
+}
@@ -0,0 +1,13 @@
+//! Asynchronous variants of the connection, the prepared statement and the resultset,
+//! based on tokio (requires feature `async`).
+//!
+//! The asynchronous objects use the same protocol implementation as the blocking ones;
+//! only the transport is asynchronous.
+
+mod async_connection;
+mod async_prepared_statement;
+mod async_resultset;
+
+pub use self::async_connection::AsyncConnection;
+pub use self::async_prepared_statement::AsyncPreparedStatement;
+pub use self::async_resultset::AsyncResultSet;
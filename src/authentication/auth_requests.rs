@@ -1,6 +1,7 @@
 use super::authenticator::Authenticator;
 
-use crate::conn_core::AmConnCore;
+use crate::conn_core::connect_params::ConnectParams;
+use crate::conn_core::ConnectionCore;
 use crate::hdb_error::{HdbError, HdbResult};
 use crate::protocol::argument::Argument;
 use crate::protocol::part::Part;
@@ -8,17 +9,16 @@ use crate::protocol::partkind::PartKind;
 use crate::protocol::parts::authfields::AuthFields;
 use crate::protocol::parts::client_context::ClientContext;
 use crate::protocol::parts::connect_options::ConnectOptions;
+use crate::protocol::reply::Reply;
 use crate::protocol::reply_type::ReplyType;
 use crate::protocol::request::Request;
 use crate::protocol::request_type::RequestType;
-use secstr::SecStr;
 use username;
 
 pub(crate) fn first_auth_request(
-    am_conn_core: &mut AmConnCore,
     db_user: &str,
     authenticators: &[Box<dyn Authenticator>],
-) -> HdbResult<(String, Vec<u8>)> {
+) -> Request<'static> {
     let mut request1 = Request::new(RequestType::Authenticate, 0);
     request1.push(Part::new(
         PartKind::ClientContext,
@@ -36,17 +36,24 @@ pub(crate) fn first_auth_request(
         PartKind::Authentication,
         Argument::Auth(auth_fields),
     ));
+    request1
+}
 
-    let mut reply = am_conn_core.send(request1)?;
+// Returns the name of the authenticator that was chosen by the server,
+// and the server challenge.
+pub(crate) fn evaluate_first_auth_reply(mut reply: Reply) -> HdbResult<(String, Vec<u8>)> {
     reply.assert_expected_reply_type(&ReplyType::Nil)?;
 
     match reply.parts.pop_arg_if_kind(PartKind::Authentication) {
         Some(Argument::Auth(mut auth_fields)) => {
             if auth_fields.len() != 2 {
-                Err(HdbError::Impl(format!(
-                    "first_auth_request(): got {} auth_fields, expected 2",
-                    auth_fields.len()
-                )))
+                Err(HdbError::Impl(
+                    format!(
+                        "evaluate_first_auth_reply(): got {} auth_fields, expected 2",
+                        auth_fields.len()
+                    ),
+                    None,
+                ))
             } else {
                 let server_challenge_data: Vec<u8> = auth_fields.pop().unwrap();
                 let authenticator_name: String =
@@ -55,50 +62,63 @@ pub(crate) fn first_auth_request(
             }
         }
         _ => Err(HdbError::Impl(
-            "first_auth_request(): expected Authentication part".to_owned(),
+            "evaluate_first_auth_reply(): expected Authentication part".to_owned(),
+            None,
         )),
     }
 }
 
+// Returns the request and the connect options that are sent with it.
 pub(crate) fn second_auth_request(
-    am_conn_core: &mut AmConnCore,
-    db_user: &str,
-    password: &SecStr,
-    clientlocale: &Option<String>,
-    mut chosen_authenticator: Box<dyn Authenticator>,
+    params: &ConnectParams,
+    o_anchor_connection_id: Option<i32>,
+    chosen_authenticator: &mut dyn Authenticator,
     server_challenge_data: &[u8],
-) -> HdbResult<()> {
+) -> HdbResult<(Request<'static>, ConnectOptions)> {
     let mut request2 = Request::new(RequestType::Connect, 0);
 
     debug!("authenticating with {}", chosen_authenticator.name());
 
     let mut auth_fields = AuthFields::with_capacity(3);
-    auth_fields.push(db_user.as_bytes().to_vec());
+    auth_fields.push(params.dbuser().as_bytes().to_vec());
     auth_fields.push(chosen_authenticator.name_as_bytes());
-    auth_fields.push(chosen_authenticator.client_proof(server_challenge_data, password)?);
+    auth_fields.push(chosen_authenticator.client_proof(server_challenge_data, params.password())?);
     request2.push(Part::new(
         PartKind::Authentication,
         Argument::Auth(auth_fields),
     ));
 
     // how about e.g. TABLEOUTPUTPARAMETER and DESCRIBETABLEOUTPUTPARAMETER?
-    let sent_co = ConnectOptions::for_server(clientlocale, get_os_user());
+    let sent_co = ConnectOptions::for_server(
+        params.clientlocale(),
+        get_os_user(),
+        params.compression(),
+        params.scrollable_cursors(),
+        o_anchor_connection_id,
+    );
     request2.push(Part::new(
         PartKind::ConnectOptions,
         Argument::ConnectOptions(sent_co.clone()),
     ));
+    Ok((request2, sent_co))
+}
 
-    let mut reply = am_conn_core.send(request2)?;
+pub(crate) fn evaluate_second_auth_reply(
+    mut reply: Reply,
+    conn_core: &mut ConnectionCore,
+    sent_co: ConnectOptions,
+    chosen_authenticator: &dyn Authenticator,
+) -> HdbResult<()> {
     reply.assert_expected_reply_type(&ReplyType::Nil)?;
 
-    let mut conn_core = am_conn_core.lock()?;
     conn_core.set_session_id(reply.session_id());
 
     match reply.parts.pop_arg_if_kind(PartKind::TopologyInformation) {
         Some(Argument::TopologyInformation(topology)) => conn_core.set_topology(topology),
         _ => {
             return Err(HdbError::Impl(
-                "second_auth_request(): expected TopologyInformation part".to_owned(),
+                "evaluate_second_auth_reply(): expected TopologyInformation part".to_owned(),
+                None,
             ));
         }
     }
@@ -109,7 +129,8 @@ pub(crate) fn second_auth_request(
         }
         _ => {
             return Err(HdbError::Impl(
-                "second_auth_request(): expected ConnectOptions part".to_owned(),
+                "evaluate_second_auth_reply(): expected ConnectOptions part".to_owned(),
+                None,
             ));
         }
     }
@@ -121,14 +142,18 @@ pub(crate) fn second_auth_request(
                 let method = af.pop().unwrap();
                 chosen_authenticator.evaluate_second_response(&method, &server_proof)
             } else {
-                Err(HdbError::Impl(format!(
-                    "second_auth_request(): got {} authfields, expected 2",
-                    af.len()
-                )))
+                Err(HdbError::Impl(
+                    format!(
+                        "evaluate_second_auth_reply(): got {} authfields, expected 2",
+                        af.len()
+                    ),
+                    None,
+                ))
             }
         }
         _ => Err(HdbError::Impl(
-            "second_auth_request(): expected Authentication part".to_owned(),
+            "evaluate_second_auth_reply(): expected Authentication part".to_owned(),
+            None,
         )),
     }
 }
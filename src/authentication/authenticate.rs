@@ -1,10 +1,12 @@
-use crate::authentication::auth_requests::{first_auth_request, second_auth_request};
+use crate::authentication::auth_requests::{
+    evaluate_first_auth_reply, evaluate_second_auth_reply, first_auth_request, second_auth_request,
+};
 use crate::authentication::authenticator::Authenticator;
 use crate::authentication::scram_pbkdf2_sha256::ScramPbkdf2Sha256;
 use crate::authentication::scram_sha256::ScramSha256;
-use crate::conn_core::AmConnCore;
+use crate::conn_core::connect_params::ConnectParams;
+use crate::conn_core::{AmConnCore, ConnectionCore};
 use crate::hdb_error::HdbResult;
-use secstr::SecStr;
 
 // Do the authentication.
 //
@@ -12,43 +14,108 @@ use secstr::SecStr;
 // So far we only support two; if more are implemented, the password might
 // become optional; if then the password is not given, the pw-related
 // authenticators mut not be added to the list.
-pub(crate) fn authenticate(
-    am_conn_core: &mut AmConnCore,
-    db_user: &str,
-    password: &SecStr,
-    clientlocale: &Option<String>,
-) -> HdbResult<()> {
+pub(crate) fn authenticate(am_conn_core: &mut AmConnCore, params: &ConnectParams) -> HdbResult<()> {
     trace!("authenticate()");
 
     // Propose some authenticators...
-    let authenticators: Vec<Box<dyn Authenticator>> = vec![
-        // Cookie,  Gss, Saml, SapLogon, Jwt, Ldap,
-        ScramSha256::boxed_authenticator(),
-        ScramPbkdf2Sha256::boxed_authenticator(),
-    ];
+    let authenticators = proposed_authenticators();
 
     // ...with the first request.
-    let (selected, server_challenge_data) =
-        first_auth_request(am_conn_core, db_user, &authenticators)?;
+    let reply1 = am_conn_core.send(first_auth_request(params.dbuser(), &authenticators))?;
+    let (selected, server_challenge_data) = evaluate_first_auth_reply(reply1)?;
 
     // Find the selected authenticator ...
-    let chosen_authenticator: Box<dyn Authenticator> = authenticators
-        .into_iter()
-        .find(|a11r| a11r.name() == selected)
-        .unwrap();
+    let mut chosen_authenticator = choose_authenticator(authenticators, &selected);
 
     // ...and use it for the second request
-    second_auth_request(
-        am_conn_core,
-        db_user,
-        password,
-        clientlocale,
-        chosen_authenticator,
+    let (request2, sent_co) = second_auth_request(
+        params,
+        None,
+        &mut *chosen_authenticator,
         &server_challenge_data,
     )?;
+    let reply2 = am_conn_core.send(request2)?;
+    let mut conn_core = am_conn_core.lock()?;
+    evaluate_second_auth_reply(reply2, &mut conn_core, sent_co, &*chosen_authenticator)?;
+    conn_core.set_authenticated(true);
 
+    Ok(())
+}
+
+// Does the same as `authenticate()`, but with the asynchronous transport.
+#[cfg(feature = "async")]
+pub(crate) async fn authenticate_async(
+    am_conn_core: &AmConnCore,
+    params: &ConnectParams,
+) -> HdbResult<()> {
+    trace!("authenticate_async()");
+
+    let authenticators = proposed_authenticators();
+    let reply1 = am_conn_core
+        .send_async(first_auth_request(params.dbuser(), &authenticators))
+        .await?;
+    let (selected, server_challenge_data) = evaluate_first_auth_reply(reply1)?;
+
+    let mut chosen_authenticator = choose_authenticator(authenticators, &selected);
+    let (request2, sent_co) = second_auth_request(
+        params,
+        None,
+        &mut *chosen_authenticator,
+        &server_challenge_data,
+    )?;
+    let reply2 = am_conn_core.send_async(request2).await?;
     let mut conn_core = am_conn_core.lock()?;
+    evaluate_second_auth_reply(reply2, &mut conn_core, sent_co, &*chosen_authenticator)?;
     conn_core.set_authenticated(true);
+    Ok(())
+}
+
+// Does the same as `authenticate()`, on a ConnectionCore that was reconnected
+// after its connection broke; the server is told which session is continued.
+pub(crate) fn reauthenticate(
+    conn_core: &mut ConnectionCore,
+    anchor_connection_id: i32,
+) -> HdbResult<()> {
+    trace!("reauthenticate()");
+    let params = conn_core.connect_params().clone();
+
+    let authenticators = proposed_authenticators();
+    let reply1 = conn_core.roundtrip_once(
+        &first_auth_request(params.dbuser(), &authenticators),
+        None,
+        None,
+        None,
+        &mut None,
+    )?;
+    let (selected, server_challenge_data) = evaluate_first_auth_reply(reply1)?;
 
+    let mut chosen_authenticator = choose_authenticator(authenticators, &selected);
+    let (request2, sent_co) = second_auth_request(
+        &params,
+        Some(anchor_connection_id),
+        &mut *chosen_authenticator,
+        &server_challenge_data,
+    )?;
+    let reply2 = conn_core.roundtrip_once(&request2, None, None, None, &mut None)?;
+    evaluate_second_auth_reply(reply2, conn_core, sent_co, &*chosen_authenticator)?;
+    conn_core.set_authenticated(true);
     Ok(())
 }
+
+fn proposed_authenticators() -> Vec<Box<dyn Authenticator>> {
+    vec![
+        // Cookie,  Gss, Saml, SapLogon, Jwt, Ldap,
+        ScramSha256::boxed_authenticator(),
+        ScramPbkdf2Sha256::boxed_authenticator(),
+    ]
+}
+
+fn choose_authenticator(
+    authenticators: Vec<Box<dyn Authenticator>>,
+    selected: &str,
+) -> Box<dyn Authenticator> {
+    authenticators
+        .into_iter()
+        .find(|a11r| a11r.name() == selected)
+        .unwrap()
+}
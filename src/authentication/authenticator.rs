@@ -1,7 +1,7 @@
 use crate::{HdbError, HdbResult};
 use secstr::SecStr;
 
-pub trait Authenticator {
+pub trait Authenticator: Send {
     fn name(&self) -> &str;
     fn name_as_bytes(&self) -> Vec<u8>;
     fn client_challenge(&self) -> &[u8];
@@ -13,10 +13,13 @@ pub trait Authenticator {
     fn verify_server(&self, server_proof: &[u8]) -> HdbResult<()>;
     fn evaluate_second_response(&self, method: &[u8], server_proof: &[u8]) -> HdbResult<()> {
         if method != self.name().as_bytes() {
-            Err(HdbError::Impl(format!(
-                "Wrong method name detected: {}",
-                String::from_utf8_lossy(method)
-            )))
+            Err(HdbError::Impl(
+                format!(
+                    "Wrong method name detected: {}",
+                    String::from_utf8_lossy(method)
+                ),
+                None,
+            ))
         } else {
             self.verify_server(server_proof)
         }
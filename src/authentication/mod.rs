@@ -6,3 +6,6 @@ mod scram_pbkdf2_sha256;
 mod scram_sha256;
 
 pub(crate) use self::authenticate::authenticate;
+#[cfg(feature = "async")]
+pub(crate) use self::authenticate::authenticate_async;
+pub(crate) use self::authenticate::reauthenticate;
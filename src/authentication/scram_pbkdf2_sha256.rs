@@ -88,10 +88,10 @@ impl Authenticator for ScramPbkdf2Sha256 {
 fn parse_first_server_data(server_data: &[u8]) -> HdbResult<(Vec<u8>, Vec<u8>, u32)> {
     let mut auth_fields = AuthFields::parse(&mut std::io::Cursor::new(server_data))?;
     if auth_fields.len() != 3 {
-        return Err(HdbError::Impl(format!(
-            "got {} auth fields, expected 3",
-            auth_fields.len()
-        )));
+        return Err(HdbError::Impl(
+            format!("got {} auth fields, expected 3", auth_fields.len()),
+            None,
+        ));
     }
 
     let iterations = {
@@ -102,12 +102,15 @@ fn parse_first_server_data(server_data: &[u8]) -> HdbResult<(Vec<u8>, Vec<u8>, u
     let salt = auth_fields.pop().unwrap();
 
     if iterations < 15_000 {
-        Err(HdbError::Impl(format!(
-            "too few iterations: {}",
-            iterations
-        )))
+        Err(HdbError::Impl(
+            format!("too few iterations: {}", iterations),
+            None,
+        ))
     } else if salt.len() < 16 {
-        Err(HdbError::Impl(format!("too little salt: {}", salt.len())))
+        Err(HdbError::Impl(
+            format!("too little salt: {}", salt.len()),
+            None,
+        ))
     } else {
         Ok((salt, server_nonce, iterations))
     }
@@ -58,10 +58,13 @@ impl Authenticator for ScramSha256 {
         if server_proof.is_empty() {
             Ok(())
         } else {
-            Err(HdbError::Impl(format!(
-                "verify_server(): non-empty server_proof: {:?}",
-                server_proof
-            )))
+            Err(HdbError::Impl(
+                format!(
+                    "verify_server(): non-empty server_proof: {:?}",
+                    server_proof
+                ),
+                None,
+            ))
         }
     }
 }
@@ -72,10 +75,10 @@ fn parse_first_server_data(server_data: &[u8]) -> HdbResult<(Vec<u8>, Vec<u8>)>
     let mut rdr = std::io::Cursor::new(server_data);
     let mut af = AuthFields::parse(&mut rdr)?;
     if af.len() != 2 {
-        return Err(HdbError::Impl(format!(
-            "got {} auth fields instead of 2",
-            af.len()
-        )));
+        return Err(HdbError::Impl(
+            format!("got {} auth fields instead of 2", af.len()),
+            None,
+        ));
     }
     let server_nonce = af.pop().unwrap();
     let salt = af.pop().unwrap();
@@ -0,0 +1,259 @@
+use crate::protocol::parts::execution_result::ExecutionResult;
+use crate::protocol::parts::hdb_value::HdbValue;
+use crate::protocol::parts::server_error::ServerError;
+use std::fmt;
+
+/// The outcome of a batch execution, reported per input row.
+///
+/// Is returned from
+/// [`PreparedStatement::execute_batch_detailed()`](struct.PreparedStatement.html#method.execute_batch_detailed).
+///
+/// The row indices correspond to the order in which the rows were added to the batch
+/// with `add_batch()` or `add_row_to_batch()`.
+/// The parameter values of failed rows are kept, so that they can be quarantined,
+/// or corrected and added to the batch again.
+/// Rows that failed without an error of their own, because the server stopped processing
+/// the batch at an earlier row, can be executed again with
+/// [`PreparedStatement::execute_failed_rows()`](struct.PreparedStatement.html#method.execute_failed_rows).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use hdbconnect::{Connection, HdbResult, IntoConnectParams};
+/// # fn foo() -> HdbResult<()> {
+/// # let mut connection = Connection::new("".into_connect_params()?)?;
+/// # let records: Vec<(i32, String)> = vec![];
+/// let mut stmt = connection.prepare("insert into TAB (ID, TEXT) values(?, ?)")?;
+/// for record in &records {
+///     stmt.add_batch(record)?;
+/// }
+/// let batch_result = stmt.execute_batch_detailed()?;
+/// if batch_result.has_failures() {
+///     for (idx, o_error, values) in batch_result.into_failed_rows() {
+///         // rows that failed with a server error are quarantined,
+///         // the others were not processed and are tried again
+///         match o_error {
+///             Some(error) => println!("row {} failed with {}: {:?}", idx, error, values),
+///             None => stmt.add_row_to_batch(values)?,
+///         }
+///     }
+///     stmt.execute_batch_detailed()?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BatchResult {
+    results: Vec<ExecutionResult>,
+    // the parameter values of the failed rows; None for successful rows
+    failed_rows: Vec<Option<Vec<HdbValue<'static>>>>,
+}
+
+impl BatchResult {
+    pub(crate) fn new(
+        results: Vec<ExecutionResult>,
+        rows: Vec<Vec<HdbValue<'static>>>,
+    ) -> BatchResult {
+        let mut rows = rows.into_iter();
+        let failed_rows = results
+            .iter()
+            .map(|result| {
+                let o_row = rows.next();
+                if result.is_failure() {
+                    o_row
+                } else {
+                    None
+                }
+            })
+            .collect();
+        BatchResult {
+            results,
+            failed_rows,
+        }
+    }
+
+    /// Returns the number of rows.
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Returns true if the batch contained no rows.
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Returns true if at least one row failed.
+    pub fn has_failures(&self) -> bool {
+        self.results.iter().any(ExecutionResult::is_failure)
+    }
+
+    /// Returns the results of all rows.
+    pub fn results(&self) -> &[ExecutionResult] {
+        &self.results
+    }
+
+    /// Returns the result of the row with the given index.
+    pub fn result(&self, idx: usize) -> Option<&ExecutionResult> {
+        self.results.get(idx)
+    }
+
+    /// Returns the number of affected rows of the row with the given index,
+    /// or None if the row failed or the number is not known.
+    pub fn affected_rows(&self, idx: usize) -> Option<usize> {
+        match self.results.get(idx) {
+            Some(ExecutionResult::RowsAffected(count)) => Some(*count),
+            _ => None,
+        }
+    }
+
+    /// Returns the indices of the failed rows.
+    pub fn failed_indices(&self) -> Vec<usize> {
+        self.results
+            .iter()
+            .enumerate()
+            .filter(|(_, result)| result.is_failure())
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Returns the server error of the row with the given index, if the row failed
+    /// and the server reported an error for it.
+    ///
+    /// Rows that failed without an error were typically not processed by the server,
+    /// because an earlier row failed.
+    pub fn server_error(&self, idx: usize) -> Option<&ServerError> {
+        match self.results.get(idx) {
+            Some(ExecutionResult::Failure(Some(server_error))) => Some(server_error),
+            _ => None,
+        }
+    }
+
+    /// Returns the parameter values of the row with the given index, if the row failed.
+    pub fn failed_row(&self, idx: usize) -> Option<&[HdbValue<'static>]> {
+        self.failed_rows
+            .get(idx)
+            .and_then(|o_row| o_row.as_ref().map(Vec::as_slice))
+    }
+
+    // Removes the parameter values of the rows that failed without a server error,
+    // and returns them together with their indices.
+    pub(crate) fn take_unprocessed_rows(&mut self) -> (Vec<usize>, Vec<Vec<HdbValue<'static>>>) {
+        let mut indices = Vec::new();
+        let mut rows = Vec::new();
+        for (idx, (result, o_row)) in self
+            .results
+            .iter()
+            .zip(self.failed_rows.iter_mut())
+            .enumerate()
+        {
+            if let ExecutionResult::Failure(None) = result {
+                if let Some(row) = o_row.take() {
+                    indices.push(idx);
+                    rows.push(row);
+                }
+            }
+        }
+        (indices, rows)
+    }
+
+    // Replaces the outcome of the rows with the given indices with the outcome
+    // of their new execution.
+    pub(crate) fn update(&mut self, indices: &[usize], other: BatchResult) {
+        for ((idx, result), o_row) in indices.iter().zip(other.results).zip(other.failed_rows) {
+            self.results[*idx] = result;
+            self.failed_rows[*idx] = o_row;
+        }
+    }
+
+    /// Converts the result into the failed rows, with their index,
+    /// their server error (if any), and their parameter values.
+    ///
+    /// The parameter values can be added to the batch again with
+    /// [`PreparedStatement::add_row_to_batch()`](struct.PreparedStatement.html#method.add_row_to_batch).
+    pub fn into_failed_rows(self) -> Vec<(usize, Option<ServerError>, Vec<HdbValue<'static>>)> {
+        self.results
+            .into_iter()
+            .zip(self.failed_rows)
+            .enumerate()
+            .filter_map(|(idx, (result, o_row))| match (result, o_row) {
+                (ExecutionResult::Failure(o_server_error), Some(row)) => {
+                    Some((idx, o_server_error, row))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for BatchResult {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, result) in self.results.iter().enumerate() {
+            // the Display of ExecutionResult ends with a line break already
+            writeln!(fmt, "row {}: {}", idx, result.to_string().trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BatchResult;
+    use crate::protocol::parts::execution_result::ExecutionResult;
+    use crate::protocol::parts::hdb_value::HdbValue;
+
+    #[test]
+    fn test_failed_rows() {
+        let batch_result = BatchResult::new(
+            vec![
+                ExecutionResult::RowsAffected(1),
+                ExecutionResult::Failure(None),
+                ExecutionResult::SuccessNoInfo,
+                ExecutionResult::Failure(None),
+            ],
+            (0..4).map(|i| vec![HdbValue::INT(i)]).collect(),
+        );
+        assert_eq!(batch_result.len(), 4);
+        assert!(batch_result.has_failures());
+        assert_eq!(batch_result.failed_indices(), vec![1, 3]);
+        assert_eq!(batch_result.affected_rows(0), Some(1));
+        assert_eq!(batch_result.affected_rows(2), None);
+        assert!(batch_result.failed_row(0).is_none());
+        assert!(batch_result.failed_row(1).is_some());
+
+        let failed_rows = batch_result.into_failed_rows();
+        assert_eq!(failed_rows.len(), 2);
+        assert_eq!(failed_rows[1].0, 3);
+        match failed_rows[1].2[0] {
+            HdbValue::INT(i) => assert_eq!(i, 3),
+            ref v => panic!("unexpected value {:?}", v),
+        }
+    }
+
+    #[test]
+    fn test_update_unprocessed_rows() {
+        let mut batch_result = BatchResult::new(
+            vec![
+                ExecutionResult::RowsAffected(1),
+                ExecutionResult::Failure(None),
+                ExecutionResult::Failure(None),
+            ],
+            (0..3).map(|i| vec![HdbValue::INT(i)]).collect(),
+        );
+        let (indices, rows) = batch_result.take_unprocessed_rows();
+        assert_eq!(indices, vec![1, 2]);
+        assert_eq!(rows.len(), 2);
+
+        let new_result = BatchResult::new(
+            vec![
+                ExecutionResult::RowsAffected(1),
+                ExecutionResult::Failure(None),
+            ],
+            rows,
+        );
+        batch_result.update(&indices, new_result);
+        assert_eq!(batch_result.affected_rows(1), Some(1));
+        assert_eq!(batch_result.failed_indices(), vec![2]);
+        assert!(batch_result.failed_row(2).is_some());
+        assert_eq!(batch_result.to_string().lines().count(), 3);
+    }
+}
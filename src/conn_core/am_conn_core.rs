@@ -1,8 +1,7 @@
 use super::connection_core::ConnectionCore;
 use crate::conn_core::connect_params::ConnectParams;
-use crate::hdb_error::HdbResult;
+use crate::hdb_error::{HdbError, HdbResult};
 use crate::protocol::argument::Argument;
-use crate::protocol::part::Part;
 use crate::protocol::partkind::PartKind;
 use crate::protocol::parts::parameter_descriptor::ParameterDescriptors;
 use crate::protocol::parts::resultset::ResultSet;
@@ -17,9 +16,42 @@ use std::sync::{Arc, LockResult, Mutex, MutexGuard};
 pub(crate) struct AmConnCore(Arc<Mutex<ConnectionCore>>);
 
 impl AmConnCore {
+    // If the connect parameters name a tenant database, the connection is redirected
+    // to the location of the tenant database that the system database reports.
     pub fn try_new(conn_params: ConnectParams) -> HdbResult<AmConnCore> {
-        let conn_core = ConnectionCore::try_new(conn_params)?;
-        Ok(AmConnCore(Arc::new(Mutex::new(conn_core))))
+        let conn_core = ConnectionCore::try_new(conn_params.clone())?;
+        let mut am_conn_core = AmConnCore(Arc::new(Mutex::new(conn_core)));
+        if let Some(database_name) = conn_params.database_name() {
+            let reply = am_conn_core.send(Request::new_for_db_connect_info(database_name))?;
+            if let Some((host, port)) = tenant_location(reply, database_name)? {
+                let conn_core = ConnectionCore::try_new(conn_params.for_host(&host, port))?;
+                am_conn_core = AmConnCore(Arc::new(Mutex::new(conn_core)));
+            }
+        }
+        Ok(am_conn_core)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn try_new_async(conn_params: &ConnectParams) -> HdbResult<AmConnCore> {
+        let conn_core = ConnectionCore::try_new_async(conn_params).await?;
+        let mut am_conn_core = AmConnCore(Arc::new(Mutex::new(conn_core)));
+        if let Some(database_name) = conn_params.database_name() {
+            let reply = am_conn_core
+                .send_async(Request::new_for_db_connect_info(database_name))
+                .await?;
+            if let Some((host, port)) = tenant_location(reply, database_name)? {
+                let conn_core =
+                    ConnectionCore::try_new_async(&conn_params.for_host(&host, port)).await?;
+                am_conn_core = AmConnCore(Arc::new(Mutex::new(conn_core)));
+            }
+        }
+        Ok(am_conn_core)
+    }
+
+    // Whether both refer to the same connection.
+    #[cfg(feature = "alpha_routing")]
+    pub fn is_same(&self, other: &AmConnCore) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
     }
 
     pub fn lock(&self) -> LockResult<MutexGuard<ConnectionCore>> {
@@ -31,7 +63,7 @@ impl AmConnCore {
     }
 
     pub fn full_send(
-        &mut self,
+        &self,
         mut request: Request,
         o_rs_md: Option<&ResultSetMetadata>,
         o_descriptors: Option<&ParameterDescriptors>,
@@ -42,28 +74,134 @@ impl AmConnCore {
             request.request_type,
         );
         let _start = Local::now();
+        let has_query_timeout = request.has_query_timeout();
         let mut conn_core = self.lock()?;
+        let cancel_state = conn_core.cancel_state();
+        let request_no = cancel_state.start_request();
+        conn_core.augment_request(&mut request);
+        let result = conn_core.roundtrip(&mut request, &self, o_rs_md, o_descriptors, o_rs);
+        cancel_state.end_request(request_no);
+        let reply = result.map_err(|e| {
+            cancel_state
+                .evaluate_error(e, request_no, has_query_timeout)
+                .with_context(request.error_context())
+        })?;
 
-        match conn_core.statement_sequence() {
-            None => {}
-            Some(ssi_value) => request.add_statement_context(*ssi_value),
-        }
+        debug!(
+            "AmConnCore::full_send() took {} ms",
+            (Local::now().signed_duration_since(_start)).num_milliseconds()
+        );
+        Ok(reply)
+    }
+}
 
-        if conn_core.is_client_info_touched() {
-            request.push(Part::new(
-                PartKind::ClientInfo,
-                Argument::ClientInfo(conn_core.get_client_info_for_sending()),
-            ));
-        }
+#[cfg(feature = "async")]
+impl AmConnCore {
+    pub async fn send_async(&self, request: Request<'_>) -> HdbResult<Reply> {
+        self.full_send_async(request, None, None, &mut None).await
+    }
 
-        let reply = conn_core.roundtrip(request, &self, o_rs_md, o_descriptors, o_rs)?;
+    // The lock on the stream is held during the complete roundtrip, including the
+    // evaluation of the reply, while the lock on the ConnectionCore is never held
+    // across an await point.
+    pub async fn full_send_async(
+        &self,
+        mut request: Request<'_>,
+        o_rs_md: Option<&ResultSetMetadata>,
+        o_descriptors: Option<&ParameterDescriptors>,
+        o_rs: &mut Option<&mut ResultSet>,
+    ) -> HdbResult<Reply> {
+        trace!(
+            "AmConnCore::full_send_async() with requestType = {:?}",
+            request.request_type,
+        );
+        let _start = Local::now();
+        let has_query_timeout = request.has_query_timeout();
+        let stream = self.lock()?.async_stream()?;
+        let mut stream = stream.lock().await;
+
+        let (request_bytes, cancel_state) = {
+            let mut conn_core = self.lock()?;
+            conn_core.check_timed_out()?;
+            conn_core.augment_request(&mut request);
+            (
+                conn_core
+                    .emit_request(&request, o_descriptors)
+                    .map_err(|e| e.with_context(request.error_context()))?,
+                conn_core.cancel_state(),
+            )
+        };
+        let request_no = cancel_state.start_request();
+        let result = match stream.send(&request_bytes).await {
+            Ok(()) => stream.receive().await,
+            Err(e) => Err(e),
+        };
+        cancel_state.end_request(request_no);
+        let reply_bytes = match result {
+            Ok(reply_bytes) => reply_bytes,
+            Err(e) => return Err(self.lock()?.evaluate_socket_error(HdbError::from(e))),
+        };
+
+        let reply = self
+            .lock()?
+            .parse_reply(reply_bytes, self, o_rs_md, o_descriptors, o_rs)
+            .map_err(|e| {
+                cancel_state
+                    .evaluate_error(e, request_no, has_query_timeout)
+                    .with_context(request.error_context())
+            })?;
 
         debug!(
-            "AmConnCore::full_send() took {} ms",
+            "AmConnCore::full_send_async() took {} ms",
             (Local::now().signed_duration_since(_start)).num_milliseconds()
         );
         Ok(reply)
     }
+
+    // Used in drop implementations, where we cannot wait for the reply:
+    // the roundtrip is handed over to the tokio runtime, if one is available.
+    pub fn send_in_background(&self, request: Request<'static>) {
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                let am_conn_core = self.clone();
+                handle.spawn(async move {
+                    if let Err(e) = am_conn_core.send_async(request).await {
+                        debug!("background request failed with {:?}", e);
+                    }
+                });
+            }
+            Err(_) => warn!("no tokio runtime available, request is not sent"),
+        }
+    }
+}
+
+// Evaluates the reply to a DbConnectInfo request; returns None if the connection
+// already leads to the tenant database.
+fn tenant_location(mut reply: Reply, database_name: &str) -> HdbResult<Option<(String, u16)>> {
+    match reply.extract_first_arg_of_type(PartKind::DbConnectInfo) {
+        Some(Argument::DbConnectInfo(db_connect_info)) => {
+            if db_connect_info.is_connected() {
+                return Ok(None);
+            }
+            match db_connect_info.location() {
+                Some((host, port)) => {
+                    debug!(
+                        "redirecting to database {} at {}:{}",
+                        database_name, host, port
+                    );
+                    Ok(Some((host, port)))
+                }
+                None => Err(HdbError::Impl(
+                    format!("no location received for database {}", database_name),
+                    None,
+                )),
+            }
+        }
+        _ => Err(HdbError::Impl(
+            "tenant_location(): expected DbConnectInfo part".to_owned(),
+            None,
+        )),
+    }
 }
 
 impl Clone for AmConnCore {
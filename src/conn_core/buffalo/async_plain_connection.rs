@@ -0,0 +1,124 @@
+use crate::conn_core::buffalo::{log_failover, set_tcp_keepalive};
+use crate::conn_core::connect_params::ConnectParams;
+use byteorder::{ByteOrder, LittleEndian};
+use socket2::SockRef;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader, BufWriter};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+
+const MESSAGE_HEADER_SIZE: usize = 32;
+
+#[derive(Debug)]
+pub struct AsyncPlainConnection {
+    stream: Arc<Mutex<AsyncStream>>,
+}
+
+impl AsyncPlainConnection {
+    /// Returns an initialized asynchronous plain tcp connection
+    pub async fn try_new(params: &ConnectParams) -> std::io::Result<AsyncPlainConnection> {
+        let tcpstream = connect_with_failover(params).await?;
+        if let Some(idle_time) = params.tcp_keepalive() {
+            set_tcp_keepalive(SockRef::from(&tcpstream), idle_time)?;
+        }
+        let (reader, writer) = tcpstream.into_split();
+        Ok(AsyncPlainConnection {
+            stream: Arc::new(Mutex::new(AsyncStream {
+                reader: BufReader::new(reader),
+                writer: BufWriter::new(writer),
+                read_timeout: params.read_timeout(),
+                write_timeout: params.write_timeout(),
+            })),
+        })
+    }
+
+    // The stream is shared, so that it can be used without holding the lock
+    // on the ConnectionCore; locking the stream serializes the roundtrips.
+    pub fn stream(&self) -> Arc<Mutex<AsyncStream>> {
+        Arc::clone(&self.stream)
+    }
+}
+
+#[derive(Debug)]
+pub struct AsyncStream {
+    reader: BufReader<OwnedReadHalf>,
+    writer: BufWriter<OwnedWriteHalf>,
+    read_timeout: Option<Duration>,
+    write_timeout: Option<Duration>,
+}
+
+impl AsyncStream {
+    /// Writes a completely serialized request to the wire.
+    pub async fn send(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        let writer = &mut self.writer;
+        with_timeout(self.write_timeout, async move {
+            writer.write_all(bytes).await?;
+            writer.flush().await
+        })
+        .await
+    }
+
+    /// Reads exactly the given number of bytes.
+    pub async fn receive_exact(&mut self, len: usize) -> std::io::Result<Vec<u8>> {
+        let mut buf = vec![0_u8; len];
+        with_timeout(self.read_timeout, self.reader.read_exact(&mut buf)).await?;
+        Ok(buf)
+    }
+
+    /// Reads a complete reply message, i.e. the message header and the variable part
+    /// whose length is given in the message header.
+    pub async fn receive(&mut self) -> std::io::Result<Vec<u8>> {
+        let mut buf = self.receive_exact(MESSAGE_HEADER_SIZE).await?;
+        let varpart_size = LittleEndian::read_u32(&buf[12..16]) as usize;
+        buf.resize(MESSAGE_HEADER_SIZE + varpart_size, 0);
+        with_timeout(
+            self.read_timeout,
+            self.reader.read_exact(&mut buf[MESSAGE_HEADER_SIZE..]),
+        )
+        .await?;
+        Ok(buf)
+    }
+}
+
+// Tries the hosts of the connect parameters one after the other,
+// until a connection can be established.
+async fn connect_with_failover(params: &ConnectParams) -> std::io::Result<TcpStream> {
+    let candidates = params.candidates();
+    let mut last_error = None;
+    for candidate in &candidates {
+        if let Some(ref e) = last_error {
+            log_failover(candidate, e);
+        }
+        match with_timeout(
+            params.connect_timeout(),
+            TcpStream::connect(candidate.addr()),
+        )
+        .await
+        {
+            Ok(tcpstream) => return Ok(tcpstream),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap(/* there is always at least one candidate */))
+}
+
+// Applies the optional timeout to an io operation; an expiry is reported with
+// ErrorKind::TimedOut, like the blocking streams do.
+async fn with_timeout<T, F>(o_timeout: Option<Duration>, f: F) -> std::io::Result<T>
+where
+    F: Future<Output = std::io::Result<T>>,
+{
+    match o_timeout {
+        Some(timeout) => match tokio::time::timeout(timeout, f).await {
+            Ok(result) => result,
+            Err(_) => Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "network operation timed out",
+            )),
+        },
+        None => f.await,
+    }
+}
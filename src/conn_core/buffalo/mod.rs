@@ -1,23 +1,36 @@
+#[cfg(feature = "async")]
+mod async_plain_connection;
 mod plain_connection;
 #[cfg(feature = "tls")]
 mod tls_connection;
 #[cfg(feature = "tls")]
 mod tls_stream;
 
+#[cfg(feature = "async")]
+use crate::conn_core::buffalo::async_plain_connection::AsyncPlainConnection;
+#[cfg(feature = "async")]
+pub(crate) use crate::conn_core::buffalo::async_plain_connection::AsyncStream;
 use crate::conn_core::buffalo::plain_connection::PlainConnection;
 #[cfg(feature = "tls")]
 use crate::conn_core::buffalo::tls_connection::TlsConnection;
 use crate::conn_core::connect_params::ConnectParams;
 use chrono::Local;
+use socket2::{SockRef, TcpKeepalive};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
 
 /// A buffered tcp connection, with or without TLS.
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum Buffalo {
     /// A buffered tcp connection without TLS.
     Plain(PlainConnection),
     /// A buffered tcp connection with TLS.
     #[cfg(feature = "tls")]
     Secure(TlsConnection),
+    /// A buffered tcp connection without TLS, for use with an async runtime.
+    #[cfg(feature = "async")]
+    Async(AsyncPlainConnection),
 }
 impl Buffalo {
     /// Constructs a buffered tcp connection, with or without TLS,
@@ -54,12 +67,106 @@ impl Buffalo {
         Ok(buffalo)
     }
 
+    /// Constructs a buffered asynchronous tcp connection.
+    ///
+    /// TLS is so far not supported with asynchronous connections.
+    #[cfg(feature = "async")]
+    pub async fn try_new_async(params: &ConnectParams) -> std::io::Result<Buffalo> {
+        trace!("Connecting asynchronously to {:?})", params.addr());
+        if params.use_tls() {
+            return Err(std::io::Error::other(
+                "TLS is not supported with asynchronous connections",
+            ));
+        }
+        Ok(Buffalo::Async(AsyncPlainConnection::try_new(params).await?))
+    }
+
+    /// Replaces the tcp connection with a new one to the same address.
+    pub fn reconnect(&self) -> std::io::Result<()> {
+        trace!("Reconnecting a connection of type {}", self.s_type());
+        match self {
+            Buffalo::Plain(pc) => pc.reconnect(),
+            #[cfg(feature = "tls")]
+            Buffalo::Secure(sc) => sc.reconnect(),
+            #[cfg(feature = "async")]
+            Buffalo::Async(_) => Err(std::io::Error::other(
+                "reconnect is not supported with asynchronous connections",
+            )),
+        }
+    }
+
     /// Returns a descriptor of the chosen type
     pub fn s_type(&self) -> &'static str {
         match self {
             Buffalo::Plain(_) => "Plain TCP",
             #[cfg(feature = "tls")]
             Buffalo::Secure(_) => "TLS",
+            #[cfg(feature = "async")]
+            Buffalo::Async(_) => "Async TCP",
         }
     }
 }
+
+// Tries the hosts of the connect parameters one after the other,
+// until a connection can be established.
+fn connect_with_failover<T, F>(params: &ConnectParams, connect: F) -> std::io::Result<T>
+where
+    F: Fn(&ConnectParams) -> std::io::Result<T>,
+{
+    let candidates = params.candidates();
+    let mut last_error = None;
+    for candidate in &candidates {
+        if let Some(ref e) = last_error {
+            log_failover(candidate, e);
+        }
+        match connect(candidate) {
+            Ok(t) => return Ok(t),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap(/* there is always at least one candidate */))
+}
+
+fn log_failover(next_candidate: &ConnectParams, error: &std::io::Error) {
+    warn!(
+        "Connecting failed with \"{}\", failing over to {}",
+        error,
+        next_candidate.addr()
+    );
+}
+
+// Opens a tcp connection and applies the timeouts and keepalive settings
+// of the connect parameters.
+fn connect_tcp(params: &ConnectParams) -> std::io::Result<TcpStream> {
+    let tcpstream = match params.connect_timeout() {
+        Some(timeout) => connect_with_timeout(params.addr(), timeout)?,
+        None => TcpStream::connect(params.addr())?,
+    };
+    tcpstream.set_read_timeout(params.read_timeout())?;
+    tcpstream.set_write_timeout(params.write_timeout())?;
+    if let Some(idle_time) = params.tcp_keepalive() {
+        set_tcp_keepalive(SockRef::from(&tcpstream), idle_time)?;
+    }
+    Ok(tcpstream)
+}
+
+// Tries all addresses the given address resolves to, like TcpStream::connect() does.
+fn connect_with_timeout(addr: &str, timeout: Duration) -> std::io::Result<TcpStream> {
+    let mut last_error = None;
+    for socket_addr in addr.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&socket_addr, timeout) {
+            Ok(tcpstream) => return Ok(tcpstream),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "could not resolve to any address",
+        )
+    }))
+}
+
+fn set_tcp_keepalive(socket: SockRef, idle_time: Duration) -> std::io::Result<()> {
+    socket.set_tcp_keepalive(&TcpKeepalive::new().with_time(idle_time))
+}
@@ -1,3 +1,4 @@
+use crate::conn_core::buffalo::{connect_tcp, connect_with_failover};
 use crate::conn_core::connect_params::ConnectParams;
 use std::cell::RefCell;
 use std::net::TcpStream;
@@ -12,7 +13,7 @@ pub struct PlainConnection {
 impl PlainConnection {
     /// Returns an initialized plain tcp connection
     pub fn try_new(params: ConnectParams) -> std::io::Result<(PlainConnection)> {
-        let tcpstream = TcpStream::connect(params.addr())?;
+        let tcpstream = connect_with_failover(&params, connect_tcp)?;
         Ok(PlainConnection {
             params,
             writer: RefCell::new(std::io::BufWriter::new(tcpstream.try_clone()?)),
@@ -28,9 +29,8 @@ impl PlainConnection {
         &self.reader
     }
 
-    #[allow(dead_code)]
     pub fn reconnect(&self) -> std::io::Result<()> {
-        let tcpstream = TcpStream::connect(self.params.addr())?;
+        let tcpstream = connect_with_failover(&self.params, connect_tcp)?;
         self.writer
             .replace(std::io::BufWriter::new(tcpstream.try_clone()?));
         self.reader.replace(std::io::BufReader::new(tcpstream));
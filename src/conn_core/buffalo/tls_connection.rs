@@ -1,3 +1,4 @@
+use crate::conn_core::buffalo::connect_with_failover;
 use crate::conn_core::buffalo::tls_stream::TlsStream;
 use crate::conn_core::connect_params::ConnectParams;
 use std::cell::RefCell;
@@ -15,7 +16,7 @@ impl fmt::Debug for TlsConnection {
 }
 impl TlsConnection {
     pub fn try_new(params: ConnectParams) -> std::io::Result<(TlsConnection)> {
-        let tlsstream = TlsStream::try_new(&params)?;
+        let tlsstream = connect_with_failover(&params, TlsStream::try_new)?;
         Ok(TlsConnection {
             params,
             reader: RefCell::new(std::io::BufReader::new(tlsstream.try_clone()?)),
@@ -23,9 +24,8 @@ impl TlsConnection {
         })
     }
 
-    #[allow(dead_code)]
     pub fn reconnect(&self) -> std::io::Result<()> {
-        let tlsstream = TlsStream::try_new(&self.params)?;
+        let tlsstream = connect_with_failover(&self.params, TlsStream::try_new)?;
         self.reader
             .replace(std::io::BufReader::new(tlsstream.try_clone()?));
         self.writer.replace(std::io::BufWriter::new(tlsstream));
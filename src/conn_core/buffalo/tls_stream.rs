@@ -1,3 +1,4 @@
+use crate::conn_core::buffalo::connect_tcp as connect_plain_tcp;
 use crate::conn_core::connect_params::{ConnectParams, ServerCerts};
 use rustls::{ClientConfig, ClientSession, Session};
 use std::net::TcpStream;
@@ -36,7 +37,7 @@ fn connect_tcp(
 ) -> std::io::Result<(TcpStream, Arc<ClientConfig>, ClientSession)> {
     debug!("connect_tcp(): Connecting to {:?}", params.addr());
 
-    let tcpstream = TcpStream::connect(params.addr())?;
+    let tcpstream = connect_plain_tcp(params)?;
 
     trace!("tcpstream working");
 
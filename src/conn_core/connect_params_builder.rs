@@ -1,8 +1,10 @@
-use crate::conn_core::connect_params::ConnectParams;
+use crate::conn_core::connect_params::{ConnectParams, FailoverOrder};
+use crate::connection::non_zero;
 #[cfg(feature = "tls")]
 use crate::conn_core::connect_params::ServerCerts;
 use crate::{HdbError, HdbResult};
 use secstr::SecStr;
+use std::time::Duration;
 
 /// A builder for `ConnectParams`.
 ///
@@ -28,6 +30,15 @@ pub struct ConnectParamsBuilder {
     dbuser: Option<String>,
     password: Option<String>,
     clientlocale: Option<String>,
+    database_name: Option<String>,
+    compression: bool,
+    scrollable_cursors: bool,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    write_timeout: Option<Duration>,
+    tcp_keepalive: Option<Duration>,
+    failover_hosts: Vec<(String, u16)>,
+    failover_order: FailoverOrder,
     #[cfg(feature = "tls")]
     server_certs: Option<ServerCerts>,
     options: Vec<(String, String)>,
@@ -42,6 +53,15 @@ impl ConnectParamsBuilder {
             dbuser: None,
             password: None,
             clientlocale: None,
+            database_name: None,
+            compression: false,
+            scrollable_cursors: false,
+            connect_timeout: None,
+            read_timeout: None,
+            write_timeout: None,
+            tcp_keepalive: None,
+            failover_hosts: vec![],
+            failover_order: FailoverOrder::InOrder,
             #[cfg(feature = "tls")]
             server_certs: None,
             options: vec![],
@@ -88,6 +108,91 @@ impl ConnectParamsBuilder {
         self
     }
 
+    /// Sets the name of the tenant database to connect to.
+    ///
+    /// With this option, hostname and port denote the system database, which is asked
+    /// for the location of the tenant database; the connection is then established
+    /// transparently to the tenant database.
+    pub fn database_name<D: AsRef<str>>(&mut self, database_name: D) -> &mut ConnectParamsBuilder {
+        self.database_name = Some(database_name.as_ref().to_owned());
+        self
+    }
+
+    /// Makes the driver propose message compression to the server.
+    ///
+    /// If the server agrees, larger requests and replies are transferred in
+    /// compressed form. This can be worthwhile with slow networks.
+    pub fn compression(&mut self, compression: bool) -> &mut ConnectParamsBuilder {
+        self.compression = compression;
+        self
+    }
+
+    /// Makes the server create scrollable cursors for resultsets.
+    ///
+    /// Only then the rows of a resultset can be accessed in arbitrary order with
+    /// [`ResultSet::fetch_absolute()`](struct.ResultSet.html#method.fetch_absolute) and
+    /// its siblings.
+    pub fn scrollable_cursors(&mut self, scrollable_cursors: bool) -> &mut ConnectParamsBuilder {
+        self.scrollable_cursors = scrollable_cursors;
+        self
+    }
+
+    /// Sets a timeout for establishing the TCP connection.
+    ///
+    /// `Duration::from_secs(0)` switches the timeout off (which is the default).
+    pub fn connect_timeout(&mut self, timeout: Duration) -> &mut ConnectParamsBuilder {
+        self.connect_timeout = non_zero(timeout);
+        self
+    }
+
+    /// Sets a timeout for reading from the TCP connection.
+    ///
+    /// Protects against hanging forever on a connection that was silently dropped,
+    /// e.g. by a firewall. Note that the timeout also applies when the server takes
+    /// long to process a statement.
+    /// `Duration::from_secs(0)` switches the timeout off (which is the default).
+    pub fn read_timeout(&mut self, timeout: Duration) -> &mut ConnectParamsBuilder {
+        self.read_timeout = non_zero(timeout);
+        self
+    }
+
+    /// Sets a timeout for writing to the TCP connection.
+    ///
+    /// `Duration::from_secs(0)` switches the timeout off (which is the default).
+    pub fn write_timeout(&mut self, timeout: Duration) -> &mut ConnectParamsBuilder {
+        self.write_timeout = non_zero(timeout);
+        self
+    }
+
+    /// Activates TCP keepalive, with the given idle time before the first probe is sent.
+    ///
+    /// `Duration::from_secs(0)` switches keepalive off (which is the default).
+    pub fn tcp_keepalive(&mut self, idle_time: Duration) -> &mut ConnectParamsBuilder {
+        self.tcp_keepalive = non_zero(idle_time);
+        self
+    }
+
+    /// Adds a further host that is tried if no connection can be established
+    /// to the host given with `hostname()` and `port()`.
+    ///
+    /// Can be called repeatedly, e.g. for the hosts of a scale-out or system
+    /// replication setup.
+    pub fn failover_host<H: AsRef<str>>(
+        &mut self,
+        hostname: H,
+        port: u16,
+    ) -> &mut ConnectParamsBuilder {
+        self.failover_hosts
+            .push((hostname.as_ref().to_owned(), port));
+        self
+    }
+
+    /// Sets the order in which the hosts are tried.
+    pub fn failover_order(&mut self, failover_order: FailoverOrder) -> &mut ConnectParamsBuilder {
+        self.failover_order = failover_order;
+        self
+    }
+
     /// Makes the driver use TLS for the connection to the database.
     ///
     /// Requires that the server's certificate is provided with one of the
@@ -147,6 +252,15 @@ impl ConnectParamsBuilder {
                 Some(_) => Some(self.clientlocale.take().unwrap()),
                 None => None,
             },
+            database_name: self.database_name.clone(),
+            compression: self.compression,
+            scrollable_cursors: self.scrollable_cursors,
+            connect_timeout: self.connect_timeout,
+            read_timeout: self.read_timeout,
+            write_timeout: self.write_timeout,
+            tcp_keepalive: self.tcp_keepalive,
+            failover_hosts: self.failover_hosts.clone(),
+            failover_order: self.failover_order,
             #[cfg(feature = "tls")]
             use_tls: self.server_certs.is_some(),
 
@@ -159,6 +273,8 @@ impl ConnectParamsBuilder {
 #[cfg(test)]
 mod test {
     use super::ConnectParamsBuilder;
+    use crate::FailoverOrder;
+    use std::time::Duration;
 
     #[test]
     fn test_connect_params_builder() {
@@ -171,6 +287,95 @@ mod test {
             .unwrap();
 
         assert_eq!(connect_params.dbuser(), "MEIER");
+        assert!(!connect_params.compression());
+    }
+
+    #[test]
+    fn test_connect_params_builder_database_name() {
+        let connect_params = ConnectParamsBuilder::new()
+            .hostname("abcd123")
+            .port(30013)
+            .dbuser("MEIER")
+            .password("schlau")
+            .database_name("HXE")
+            .build()
+            .unwrap();
+
+        assert_eq!(connect_params.database_name(), Some("HXE"));
+    }
+
+    #[test]
+    fn test_connect_params_builder_compression() {
+        let connect_params = ConnectParamsBuilder::new()
+            .hostname("abcd123")
+            .port(2222)
+            .dbuser("MEIER")
+            .password("schlau")
+            .compression(true)
+            .build()
+            .unwrap();
+
+        assert!(connect_params.compression());
+    }
+
+    #[test]
+    fn test_connect_params_builder_scrollable_cursors() {
+        let connect_params = ConnectParamsBuilder::new()
+            .hostname("abcd123")
+            .port(2222)
+            .dbuser("MEIER")
+            .password("schlau")
+            .scrollable_cursors(true)
+            .build()
+            .unwrap();
+
+        assert!(connect_params.scrollable_cursors());
+    }
+
+    #[test]
+    fn test_connect_params_builder_timeouts() {
+        let connect_params = ConnectParamsBuilder::new()
+            .hostname("abcd123")
+            .port(2222)
+            .dbuser("MEIER")
+            .password("schlau")
+            .connect_timeout(Duration::from_secs(5))
+            .read_timeout(Duration::from_secs(60))
+            .write_timeout(Duration::from_secs(0))
+            .tcp_keepalive(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            connect_params.connect_timeout(),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(connect_params.read_timeout(), Some(Duration::from_secs(60)));
+        assert_eq!(connect_params.write_timeout(), None);
+        assert_eq!(
+            connect_params.tcp_keepalive(),
+            Some(Duration::from_secs(30))
+        );
+    }
+
+    #[test]
+    fn test_connect_params_builder_failover() {
+        let connect_params = ConnectParamsBuilder::new()
+            .hostname("abcd123")
+            .port(2222)
+            .failover_host("efgh456", 3333)
+            .failover_order(FailoverOrder::Random)
+            .dbuser("MEIER")
+            .password("schlau")
+            .build()
+            .unwrap();
+
+        assert_eq!(connect_params.addr(), "abcd123:2222");
+        assert_eq!(
+            connect_params.failover_hosts(),
+            &[("efgh456".to_string(), 3333)]
+        );
+        assert_eq!(connect_params.failover_order(), FailoverOrder::Random);
     }
 
 }
@@ -1,10 +1,19 @@
+use crate::authentication;
+use crate::cancel_handle::CancelState;
 use crate::conn_core::am_conn_core::AmConnCore;
+#[cfg(feature = "async")]
+use crate::conn_core::buffalo::AsyncStream;
 use crate::conn_core::buffalo::Buffalo;
 use crate::conn_core::connect_params::ConnectParams;
 use crate::conn_core::initial_request;
-use crate::conn_core::session_state::SessionState;
+#[cfg(feature = "alpha_routing")]
+use crate::conn_core::routing::{Routing, RoutingSetup};
+use crate::conn_core::session_state::{
+    IsolationLevel, SessionSettings, SessionState, TransactionState,
+};
+use crate::conn_core::statement_cache::StatementCache;
 use crate::protocol::argument::Argument;
-use crate::protocol::part::Parts;
+use crate::protocol::part::{Part, Parts};
 use crate::protocol::partkind::PartKind;
 use crate::protocol::parts::client_info::ClientInfo;
 use crate::protocol::parts::connect_options::ConnectOptions;
@@ -18,13 +27,23 @@ use crate::protocol::parts::topology::Topology;
 use crate::protocol::parts::transactionflags::TransactionFlags;
 use crate::protocol::reply::Reply;
 use crate::protocol::request::Request;
+use crate::protocol::request_type::RequestType;
 use crate::protocol::server_resource_consumption_info::ServerResourceConsumptionInfo;
 use crate::{HdbError, HdbResult};
-use std::io::Write;
+#[cfg(feature = "alpha_routing")]
+use std::collections::HashMap;
+use std::io::{ErrorKind, Write};
 use std::mem;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// Pause between two attempts to reconnect.
+const RECONNECT_INTERVAL: Duration = Duration::from_millis(500);
 
 #[derive(Debug)]
 pub(crate) struct ConnectionCore {
+    connect_params: ConnectParams,
+    anchor_connection_id: Option<i32>, // id of the connection that was originally established
     authenticated: bool,
     session_id: i64,
     client_info: ClientInfo,
@@ -33,59 +52,138 @@ pub(crate) struct ConnectionCore {
     auto_commit: bool,
     server_resource_consumption_info: ServerResourceConsumptionInfo,
     fetch_size: u32,
+    read_ahead: bool,
+    query_timeout: Option<Duration>,
+    cancel_state: Arc<CancelState>,
     lob_read_length: u32,
     lob_write_length: usize,
+    max_batch_rows: usize,
+    max_batch_bytes: usize,
+    statement_cache: StatementCache,
+    transaction_retries: u32,
+    transaction_retry_backoff: Duration,
     session_state: SessionState,
+    ended_transactions: u64, // counts the transactions that were committed, rolled back or lost
+    session_settings: SessionSettings,
+    timed_out: bool, // a network operation timed out, the stream is unusable
     statement_sequence: Option<i64>, // statement sequence within the transaction
     connect_options: ConnectOptions,
     topology: Option<Topology>,
+    #[cfg(feature = "alpha_routing")]
+    o_current_schema: Option<String>, // as reported by the server
+    #[cfg(feature = "alpha_routing")]
+    routing_connections: HashMap<i32, AmConnCore>, // by volume id, opened lazily
     pub warnings: Vec<ServerError>,
     buffalo: Buffalo,
 }
 
 impl<'a> ConnectionCore {
     pub(crate) fn try_new(params: ConnectParams) -> HdbResult<ConnectionCore> {
-        let mut buffalo = Buffalo::try_new(params)?;
-        initial_request::send_and_receive(&mut buffalo)?;
-
-        Ok(ConnectionCore {
+        let mut buffalo =
+            Buffalo::try_new(params.clone()).map_err(|e| HdbError::from(e).for_socket_io())?;
+        initial_request::send_and_receive(&mut buffalo).map_err(HdbError::for_socket_io)?;
+        Ok(ConnectionCore::new(params, buffalo))
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) async fn try_new_async(params: &ConnectParams) -> HdbResult<ConnectionCore> {
+        let buffalo = Buffalo::try_new_async(params)
+            .await
+            .map_err(|e| HdbError::from(e).for_socket_io())?;
+        let conn_core = ConnectionCore::new(params.clone(), buffalo);
+        initial_request::send_and_receive_async(conn_core.async_stream()?)
+            .await
+            .map_err(HdbError::for_socket_io)?;
+        Ok(conn_core)
+    }
+
+    fn new(connect_params: ConnectParams, buffalo: Buffalo) -> ConnectionCore {
+        ConnectionCore {
+            connect_params,
+            anchor_connection_id: None,
             authenticated: false,
             session_id: 0,
             seq_number: 0,
             auto_commit: true,
             server_resource_consumption_info: Default::default(),
             fetch_size: crate::DEFAULT_FETCH_SIZE,
+            read_ahead: false,
+            query_timeout: None,
+            cancel_state: Default::default(),
             lob_read_length: crate::DEFAULT_LOB_READ_LENGTH,
             lob_write_length: crate::DEFAULT_LOB_WRITE_LENGTH,
+            max_batch_rows: crate::DEFAULT_MAX_BATCH_ROWS,
+            max_batch_bytes: crate::DEFAULT_MAX_BATCH_BYTES,
+            statement_cache: Default::default(),
+            transaction_retries: 0,
+            transaction_retry_backoff: crate::DEFAULT_TRANSACTION_RETRY_BACKOFF,
             client_info: Default::default(),
             client_info_touched: false,
             session_state: Default::default(),
+            ended_transactions: 0,
+            session_settings: Default::default(),
+            timed_out: false,
             statement_sequence: None,
             connect_options: Default::default(),
             topology: None,
+            #[cfg(feature = "alpha_routing")]
+            o_current_schema: None,
+            #[cfg(feature = "alpha_routing")]
+            routing_connections: HashMap::new(),
             warnings: Vec::<ServerError>::new(),
             buffalo,
-        })
+        }
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn is_async(&self) -> bool {
+        matches!(self.buffalo, Buffalo::Async(_))
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn async_stream(&self) -> HdbResult<Arc<tokio::sync::Mutex<AsyncStream>>> {
+        match self.buffalo {
+            Buffalo::Async(ref ac) => Ok(ac.stream()),
+            _ => Err(HdbError::usage_(
+                "asynchronous call on a blocking connection",
+            )),
+        }
+    }
+
+    pub(crate) fn connect_params(&self) -> &ConnectParams {
+        &self.connect_params
     }
 
     pub(crate) fn set_application_version(&mut self, version: &str) -> HdbResult<()> {
         self.client_info.set_application_version(version);
         self.client_info_touched = true;
+        #[cfg(feature = "alpha_routing")]
+        self.discard_routing_connections();
         Ok(())
     }
 
     pub(crate) fn set_application_source(&mut self, source: &str) -> HdbResult<()> {
         self.client_info.set_application_source(source);
         self.client_info_touched = true;
+        #[cfg(feature = "alpha_routing")]
+        self.discard_routing_connections();
         Ok(())
     }
 
     pub(crate) fn set_application_user(&mut self, application_user: &str) -> HdbResult<()> {
         self.client_info.set_application_user(application_user);
         self.client_info_touched = true;
+        #[cfg(feature = "alpha_routing")]
+        self.discard_routing_connections();
         Ok(())
     }
 
+    #[cfg(feature = "alpha_routing")]
+    pub(crate) fn set_client_info(&mut self, client_info: ClientInfo) {
+        self.client_info = client_info;
+        self.client_info_touched = true;
+    }
+
     pub(crate) fn is_client_info_touched(&self) -> bool {
         self.client_info_touched
     }
@@ -109,8 +207,16 @@ impl<'a> ConnectionCore {
             stmt_ctx.get_server_cpu_time(),
             stmt_ctx.get_server_memory_usage(),
         );
+        #[cfg(feature = "alpha_routing")]
+        {
+            if let Some(schema) = stmt_ctx.get_schema_name() {
+                if self.o_current_schema.as_deref() != Some(schema) {
+                    self.o_current_schema = Some(schema.to_string());
+                    self.discard_routing_connections();
+                }
+            }
+        }
         // FIXME do not ignore the other content of StatementContext
-        // StatementContextId::SchemaName => 3,
         // StatementContextId::FlagSet => 4,
         // StatementContextId::QueryTimeout => 5,
         // StatementContextId::ClientReconnectionWaitTimeout => 6,
@@ -138,6 +244,26 @@ impl<'a> ConnectionCore {
         self.fetch_size = fetch_size;
     }
 
+    pub(crate) fn is_read_ahead(&self) -> bool {
+        self.read_ahead
+    }
+
+    pub(crate) fn set_read_ahead(&mut self, read_ahead: bool) {
+        self.read_ahead = read_ahead;
+    }
+
+    pub(crate) fn get_query_timeout(&self) -> Option<Duration> {
+        self.query_timeout
+    }
+
+    pub(crate) fn set_query_timeout(&mut self, query_timeout: Option<Duration>) {
+        self.query_timeout = query_timeout;
+    }
+
+    pub(crate) fn cancel_state(&self) -> Arc<CancelState> {
+        Arc::clone(&self.cancel_state)
+    }
+
     pub(crate) fn get_lob_read_length(&self) -> u32 {
         self.lob_read_length
     }
@@ -154,6 +280,46 @@ impl<'a> ConnectionCore {
         self.lob_write_length = lob_write_length;
     }
 
+    pub(crate) fn get_max_batch_rows(&self) -> usize {
+        self.max_batch_rows
+    }
+
+    pub(crate) fn set_max_batch_rows(&mut self, max_batch_rows: usize) {
+        self.max_batch_rows = std::cmp::max(max_batch_rows, 1);
+    }
+
+    pub(crate) fn get_max_batch_bytes(&self) -> usize {
+        self.max_batch_bytes
+    }
+
+    pub(crate) fn set_max_batch_bytes(&mut self, max_batch_bytes: usize) {
+        self.max_batch_bytes = max_batch_bytes;
+    }
+
+    pub(crate) fn get_transaction_retries(&self) -> u32 {
+        self.transaction_retries
+    }
+
+    pub(crate) fn set_transaction_retries(&mut self, transaction_retries: u32) {
+        self.transaction_retries = transaction_retries;
+    }
+
+    pub(crate) fn get_transaction_retry_backoff(&self) -> Duration {
+        self.transaction_retry_backoff
+    }
+
+    pub(crate) fn set_transaction_retry_backoff(&mut self, transaction_retry_backoff: Duration) {
+        self.transaction_retry_backoff = transaction_retry_backoff;
+    }
+
+    pub(crate) fn statement_cache(&self) -> &StatementCache {
+        &self.statement_cache
+    }
+
+    pub(crate) fn statement_cache_mut(&mut self) -> &mut StatementCache {
+        &mut self.statement_cache
+    }
+
     pub(crate) fn set_session_id(&mut self, session_id: i64) {
         self.session_id = session_id;
     }
@@ -162,6 +328,72 @@ impl<'a> ConnectionCore {
         self.topology = Some(topology);
     }
 
+    // Looks up the connection to the index server that holds the given volume,
+    // if a statement on this volume can be routed there.
+    //
+    // Routing is only done if it does not change the transactional behavior,
+    // i.e. with auto-commit and outside of write transactions, because the
+    // routing connection has its own session.
+    // A missing routing connection is opened by the caller, after releasing the lock
+    // of this connection, and then registered with add_routing_connection().
+    #[cfg(feature = "alpha_routing")]
+    pub(crate) fn routing(&self, volume_id: i32) -> Routing {
+        if !self.auto_commit || self.is_in_write_transaction() {
+            return Routing::Local;
+        }
+        #[cfg(feature = "async")]
+        {
+            if self.is_async() {
+                return Routing::Local;
+            }
+        }
+        if let Some(am_conn_core) = self.routing_connections.get(&volume_id) {
+            return Routing::Connected(am_conn_core.clone());
+        }
+        let topology = match self.topology {
+            Some(ref topology) if !topology.is_current_session_volume(volume_id) => topology,
+            _ => return Routing::Local,
+        };
+        match topology.location_of_volume(volume_id) {
+            Some((host, port)) => Routing::Unconnected(Box::new(RoutingSetup {
+                params: self.connect_params.for_host(&host, port),
+                fetch_size: self.fetch_size,
+                lob_read_length: self.lob_read_length,
+                lob_write_length: self.lob_write_length,
+                max_batch_rows: self.max_batch_rows,
+                max_batch_bytes: self.max_batch_bytes,
+                client_info: self.client_info.clone(),
+                o_schema: self.o_current_schema.clone(),
+                session_settings: self.session_settings.clone(),
+            })),
+            None => Routing::Local,
+        }
+    }
+
+    // Registers a freshly opened routing connection; if meanwhile another one was
+    // registered for the same volume, that one is kept and returned.
+    #[cfg(feature = "alpha_routing")]
+    pub(crate) fn add_routing_connection(
+        &mut self,
+        volume_id: i32,
+        am_conn_core: AmConnCore,
+    ) -> AmConnCore {
+        self.routing_connections
+            .entry(volume_id)
+            .or_insert(am_conn_core)
+            .clone()
+    }
+
+    // The routing connections are opened again when they are needed next,
+    // with the changed session state.
+    #[cfg(feature = "alpha_routing")]
+    fn discard_routing_connections(&mut self) {
+        if !self.routing_connections.is_empty() {
+            debug!("discarding the routing connections");
+            self.routing_connections.clear();
+        }
+    }
+
     pub(crate) fn digest_server_connect_options(
         &mut self,
         new_conn_opts: ConnectOptions,
@@ -177,6 +409,10 @@ impl<'a> ConnectionCore {
 
     pub(crate) fn set_authenticated(&mut self, authenticated: bool) {
         self.authenticated = authenticated;
+        if authenticated {
+            self.cancel_state
+                .set_connection_id(self.connect_options.get_connection_id());
+        }
     }
 
     pub(crate) fn statement_sequence(&self) -> &Option<i64> {
@@ -199,8 +435,66 @@ impl<'a> ConnectionCore {
         self.seq_number
     }
 
+    pub(crate) fn get_isolation_level(&self) -> IsolationLevel {
+        self.session_state.isolation_level
+    }
+
+    // Records the isolation level that was set with a statement, for a reconnect,
+    // and checks that the server reported it with the transaction flags.
+    pub(crate) fn confirm_isolation_level(
+        &mut self,
+        isolation_level: IsolationLevel,
+    ) -> HdbResult<()> {
+        self.session_settings_mut()
+            .set_isolation_level(isolation_level);
+        if self.session_state.isolation_level == isolation_level {
+            Ok(())
+        } else {
+            Err(HdbError::Evaluation(format!(
+                "server reports isolation level {:?} instead of {:?}",
+                self.session_state.isolation_level, isolation_level
+            )))
+        }
+    }
+
+    pub(crate) fn session_settings_mut(&mut self) -> &mut SessionSettings {
+        #[cfg(feature = "alpha_routing")]
+        self.discard_routing_connections();
+        &mut self.session_settings
+    }
+
+    // Identifies the current transaction, in the sense that the value changes
+    // whenever a transaction ends.
+    pub(crate) fn get_transaction_number(&self) -> u64 {
+        self.ended_transactions
+    }
+
+    pub(crate) fn get_transaction_state(&self) -> TransactionState {
+        self.session_state.ta_state
+    }
+
+    pub(crate) fn is_read_only(&self) -> bool {
+        self.session_state.read_only_mode
+    }
+
+    // Records the read-only mode that was set with a statement, for a reconnect,
+    // and checks that the server reported it with the transaction flags.
+    pub(crate) fn confirm_read_only(&mut self, read_only: bool) -> HdbResult<()> {
+        self.session_settings_mut().set_read_only(read_only);
+        if self.session_state.read_only_mode == read_only {
+            Ok(())
+        } else {
+            Err(HdbError::Evaluation(format!(
+                "server reports read-only mode {} instead of {}",
+                self.session_state.read_only_mode, read_only
+            )))
+        }
+    }
+
     pub(crate) fn evaluate_ta_flags(&mut self, ta_flags: TransactionFlags) -> HdbResult<()> {
-        self.session_state.update(ta_flags);
+        if self.session_state.update(ta_flags) {
+            self.ended_transactions += 1;
+        }
         if self.session_state.dead {
             Err(HdbError::DbIssue(
                 "SessionclosingTaError received".to_owned(),
@@ -224,27 +518,161 @@ impl<'a> ConnectionCore {
         &self.connect_options
     }
 
+    // Adds the statement sequence info and the client info, if necessary.
+    pub(crate) fn augment_request(&mut self, request: &mut Request) {
+        if let Some(ssi_value) = self.statement_sequence() {
+            request.add_statement_context(*ssi_value);
+        }
+
+        if self.is_client_info_touched() {
+            request.push(Part::new(
+                PartKind::ClientInfo,
+                Argument::ClientInfo(self.get_client_info_for_sending()),
+            ));
+        }
+    }
+
+    // Serializes the request into a buffer, for the asynchronous transport.
+    #[cfg(feature = "async")]
+    pub(crate) fn emit_request(
+        &mut self,
+        request: &Request,
+        o_descriptors: Option<&ParameterDescriptors>,
+    ) -> HdbResult<Vec<u8>> {
+        let auto_commit_flag: i8 = if self.is_auto_commit() { 1 } else { 0 };
+        let compress = self.connect_options.is_compression_active();
+        let nsn = self.next_seq_number();
+        let mut buf = Vec::<u8>::new();
+        request.emit(
+            self.session_id(),
+            nsn,
+            auto_commit_flag,
+            compress,
+            o_descriptors,
+            &mut buf,
+        )?;
+        Ok(buf)
+    }
+
+    // Parses a reply that was completely received by the asynchronous transport.
+    #[cfg(feature = "async")]
+    pub(crate) fn parse_reply(
+        &mut self,
+        bytes: Vec<u8>,
+        am_conn_core: &AmConnCore,
+        o_rs_md: Option<&ResultSetMetadata>,
+        o_descriptors: Option<&ParameterDescriptors>,
+        o_rs: &mut Option<&mut ResultSet>,
+    ) -> HdbResult<Reply> {
+        let mut rdr = std::io::Cursor::new(bytes);
+        let mut reply = Reply::parse(o_rs_md, o_descriptors, o_rs, Some(am_conn_core), &mut rdr)?;
+        self.handle_db_error(&mut reply.parts)?;
+        Ok(reply)
+    }
+
+    // Sends the request and returns the reply.
+    //
+    // If the connection turns out to be broken, it is re-established, and the
+    // request is repeated if this is safe.
     pub(crate) fn roundtrip(
         &mut self,
-        request: Request<'a>,
+        request: &mut Request<'a>,
         am_conn_core: &AmConnCore,
         o_rs_md: Option<&ResultSetMetadata>,
         o_descriptors: Option<&ParameterDescriptors>,
         o_rs: &mut Option<&mut ResultSet>,
     ) -> HdbResult<Reply> {
+        let in_write_transaction = self.is_in_write_transaction();
+        let (io_error, repeat_is_safe) = match self.send_request(request, o_descriptors) {
+            Err(HdbError::Io(e)) if self.is_connection_broken(&e) => (e, !in_write_transaction),
+            Err(e) => return Err(e),
+            Ok(()) => match self.receive_reply(Some(am_conn_core), o_rs_md, o_descriptors, o_rs) {
+                // The server might have executed the request already, which is only
+                // harmless if its effect is rolled back together with the broken session
+                Err(HdbError::Io(e)) if self.is_connection_broken(&e) => {
+                    (e, !in_write_transaction && !self.auto_commit)
+                }
+                result => return result,
+            },
+        };
+
+        warn!("Connection is broken ({}), trying to reconnect", io_error);
+        if let Err(e) = self.reconnect() {
+            warn!("Reconnect failed with {:?}", e);
+            return Err(HdbError::Io(io_error));
+        }
+        if in_write_transaction {
+            return Err(HdbError::DbIssue(
+                "Connection was re-established, but the open transaction was lost".to_owned(),
+            ));
+        }
+        if !repeat_is_safe {
+            info!("Connection was re-established, but the request is not repeated");
+            return Err(HdbError::Io(io_error));
+        }
+
+        debug!("Connection was re-established, repeating the request");
+        request.drop_statement_sequence_info();
+        request.drop_parts_of_kind(PartKind::ClientInfo);
+        self.augment_request(request);
+        self.roundtrip_once(request, Some(am_conn_core), o_rs_md, o_descriptors, o_rs)
+    }
+
+    // Sends the request and returns the reply, without trying to reconnect.
+    pub(crate) fn roundtrip_once(
+        &mut self,
+        request: &Request,
+        o_am_conn_core: Option<&AmConnCore>,
+        o_rs_md: Option<&ResultSetMetadata>,
+        o_descriptors: Option<&ParameterDescriptors>,
+        o_rs: &mut Option<&mut ResultSet>,
+    ) -> HdbResult<Reply> {
+        self.send_request(request, o_descriptors)?;
+        self.receive_reply(o_am_conn_core, o_rs_md, o_descriptors, o_rs)
+    }
+
+    // A request or reply that was interrupted by a timeout leaves unread or incomplete
+    // data on the stream, so the stream must not be used anymore.
+    pub(crate) fn check_timed_out(&self) -> HdbResult<()> {
+        if self.timed_out {
+            Err(HdbError::Io(std::io::Error::new(
+                ErrorKind::NotConnected,
+                "connection is unusable since a network operation timed out",
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn evaluate_socket_error(&mut self, error: HdbError) -> HdbError {
+        let error = error.for_socket_io();
+        if let HdbError::Timeout(_) = error {
+            self.timed_out = true;
+        }
+        error
+    }
+
+    fn send_request(
+        &mut self,
+        request: &Request,
+        o_descriptors: Option<&ParameterDescriptors>,
+    ) -> HdbResult<()> {
+        self.check_timed_out()?;
         let auto_commit_flag: i8 = if self.is_auto_commit() { 1 } else { 0 };
+        let compress = self.connect_options.is_compression_active();
         let nsn = self.next_seq_number();
 
-        match self.buffalo {
+        let result = match self.buffalo {
             Buffalo::Plain(ref pc) => {
                 let writer = &mut *(pc.writer()).borrow_mut();
                 request.emit(
                     self.session_id(),
                     nsn,
                     auto_commit_flag,
+                    compress,
                     o_descriptors,
                     writer,
-                )?;
+                )
             }
             #[cfg(feature = "tls")]
             Buffalo::Secure(ref sc) => {
@@ -253,28 +681,152 @@ impl<'a> ConnectionCore {
                     self.session_id(),
                     nsn,
                     auto_commit_flag,
+                    compress,
                     o_descriptors,
                     writer,
-                )?;
+                )
             }
-        }
+            #[cfg(feature = "async")]
+            Buffalo::Async(_) => Err(HdbError::usage_(
+                "blocking call on an asynchronous connection",
+            )),
+        };
+        result.map_err(|e| self.evaluate_socket_error(e))
+    }
 
-        let mut reply = match self.buffalo {
+    fn receive_reply(
+        &mut self,
+        o_am_conn_core: Option<&AmConnCore>,
+        o_rs_md: Option<&ResultSetMetadata>,
+        o_descriptors: Option<&ParameterDescriptors>,
+        o_rs: &mut Option<&mut ResultSet>,
+    ) -> HdbResult<Reply> {
+        let result = match self.buffalo {
             Buffalo::Plain(ref pc) => {
                 let reader = &mut *(pc.reader()).borrow_mut();
-                Reply::parse(o_rs_md, o_descriptors, o_rs, Some(am_conn_core), reader)?
+                Reply::parse(o_rs_md, o_descriptors, o_rs, o_am_conn_core, reader)
             }
             #[cfg(feature = "tls")]
             Buffalo::Secure(ref sc) => {
                 let reader = &mut *(sc.reader()).borrow_mut();
-                Reply::parse(o_rs_md, o_descriptors, o_rs, Some(am_conn_core), reader)?
+                Reply::parse(o_rs_md, o_descriptors, o_rs, o_am_conn_core, reader)
             }
+            #[cfg(feature = "async")]
+            Buffalo::Async(_) => unreachable!("asynchronous connection in blocking roundtrip"),
         };
+        let mut reply = result.map_err(|e| self.evaluate_socket_error(e))?;
 
         self.handle_db_error(&mut reply.parts)?;
         Ok(reply)
     }
 
+    fn is_in_write_transaction(&self) -> bool {
+        matches!(
+            self.session_state.ta_state,
+            TransactionState::WriteTransaction
+        )
+    }
+
+    // Only established sessions are reconnected.
+    fn is_connection_broken(&self, io_error: &std::io::Error) -> bool {
+        self.authenticated
+            && matches!(
+                io_error.kind(),
+                ErrorKind::BrokenPipe
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::ConnectionReset
+                    | ErrorKind::NotConnected
+                    | ErrorKind::UnexpectedEof
+            )
+    }
+
+    // Replaces the broken connection with a new one and continues the session
+    // on it, within the time the server grants for reconnecting.
+    //
+    // Client-side settings like auto-commit, fetch size and lob lengths are kept,
+    // the client info is sent again with the next request, and the session settings
+    // (isolation level, read-only mode, session variables) are applied again.
+    // The statement ids of the statement cache are forgotten, since they belong to the
+    // broken connection.
+    fn reconnect(&mut self) -> HdbResult<()> {
+        let anchor_connection_id = match self.anchor_connection_id {
+            Some(id) => id,
+            None => self.connect_options.get_connection_id(),
+        };
+        let wait_timeout = match self.connect_options.get_client_reconnect_wait_timeout() {
+            Some(secs) if secs > 0 => Duration::from_secs(secs as u64),
+            _ => Duration::from_secs(0),
+        };
+
+        let start = Instant::now();
+        while let Err(e) = self.buffalo.reconnect() {
+            if start.elapsed() >= wait_timeout {
+                return Err(HdbError::Io(e));
+            }
+            debug!("Reconnect failed with {}, trying again", e);
+            std::thread::sleep(RECONNECT_INTERVAL);
+        }
+        initial_request::send_and_receive(&mut self.buffalo).map_err(HdbError::for_socket_io)?;
+
+        self.authenticated = false;
+        self.session_id = 0;
+        self.seq_number = 0;
+        self.statement_sequence = None;
+        self.timed_out = false;
+        self.session_state = Default::default();
+        self.ended_transactions += 1;
+        self.connect_options = Default::default();
+        self.statement_cache.clear();
+        self.client_info_touched = true;
+        self.anchor_connection_id = Some(anchor_connection_id);
+        authentication::reauthenticate(self, anchor_connection_id)?;
+        self.apply_session_settings()?;
+        info!(
+            "Reconnected session {} of connection {}",
+            self.session_id, anchor_connection_id
+        );
+        Ok(())
+    }
+
+    // Applies the settings that were changed with the API to a new session.
+    fn apply_session_settings(&mut self) -> HdbResult<()> {
+        for stmt in self.session_settings.statements() {
+            debug!("Restoring session setting: {}", stmt);
+            self.execute_setting(&stmt)?;
+        }
+        Ok(())
+    }
+
+    // Takes over the settings of another connection and applies them to this session.
+    #[cfg(feature = "alpha_routing")]
+    pub(crate) fn adopt_session_settings(
+        &mut self,
+        session_settings: SessionSettings,
+    ) -> HdbResult<()> {
+        self.session_settings = session_settings;
+        self.apply_session_settings()
+    }
+
+    // Executes a statement that changes the state of the session.
+    pub(crate) fn execute_setting(&mut self, stmt: &str) -> HdbResult<()> {
+        let mut request = Request::new(RequestType::ExecuteDirect, 0);
+        request.push(Part::new(PartKind::Command, Argument::Command(stmt)));
+        self.augment_request(&mut request);
+        let mut reply = self.roundtrip_once(&request, None, None, None, &mut None)?;
+        while let Some(arg) = reply.parts.pop_arg() {
+            match arg {
+                Argument::StatementContext(ref stmt_ctx) => {
+                    self.evaluate_statement_context(stmt_ctx)?;
+                }
+                Argument::TransactionFlags(ta_flags) => {
+                    self.evaluate_ta_flags(ta_flags)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
     fn handle_db_error(&mut self, parts: &mut Parts) -> HdbResult<()> {
         self.warnings.clear();
 
@@ -374,15 +926,31 @@ impl<'a> ConnectionCore {
             match self.buffalo {
                 Buffalo::Plain(ref pc) => {
                     let writer = &mut *(pc.writer()).borrow_mut();
-                    request.emit(self.session_id(), nsn, 0, None, writer)?;
+                    request.emit(self.session_id(), nsn, 0, false, None, writer)?;
                     writer.flush()?;
                 }
                 #[cfg(feature = "tls")]
                 Buffalo::Secure(ref sc) => {
                     let writer = &mut *(sc.writer()).borrow_mut();
-                    request.emit(self.session_id(), nsn, 0, None, writer)?;
+                    request.emit(self.session_id(), nsn, 0, false, None, writer)?;
                     writer.flush()?;
                 }
+                #[cfg(feature = "async")]
+                Buffalo::Async(ref ac) => {
+                    // we cannot wait in drop, so we hand the request over to the runtime
+                    let mut buf = Vec::<u8>::new();
+                    request.emit(self.session_id(), nsn, 0, false, None, &mut buf)?;
+                    let handle = tokio::runtime::Handle::try_current().map_err(|_| {
+                        HdbError::usage_("no tokio runtime available for sending the disconnect")
+                    })?;
+                    let stream = ac.stream();
+                    handle.spawn(async move {
+                        let mut stream = stream.lock().await;
+                        if let Err(e) = stream.send(&buf).await {
+                            warn!("Disconnect request failed with {:?}", e);
+                        }
+                    });
+                }
             }
             trace!("Disconnect: request successfully sent");
         }
@@ -1,3 +1,5 @@
+#[cfg(feature = "async")]
+use crate::conn_core::buffalo::AsyncStream;
 use crate::conn_core::buffalo::Buffalo;
 use crate::protocol::util;
 use crate::HdbResult;
@@ -16,6 +18,12 @@ pub fn send_and_receive(buffalo: &mut Buffalo) -> HdbResult<()> {
             let writer = &mut *(sc.writer()).borrow_mut();
             emit_initial_request(writer)?;
         }
+        #[cfg(feature = "async")]
+        Buffalo::Async(_) => {
+            return Err(crate::HdbError::usage_(
+                "initial request of an asynchronous connection must be sent asynchronously",
+            ));
+        }
     }
 
     match buffalo {
@@ -28,12 +36,29 @@ pub fn send_and_receive(buffalo: &mut Buffalo) -> HdbResult<()> {
             let reader = &mut *(sc.reader()).borrow_mut();
             util::skip_bytes(8, reader)?; // ignore the response content
         }
+        #[cfg(feature = "async")]
+        Buffalo::Async(_) => unreachable!("async connection in blocking initial request"),
     }
 
     debug!("successfully initialized");
     Ok(())
 }
 
+#[cfg(feature = "async")]
+pub async fn send_and_receive_async(
+    stream: std::sync::Arc<tokio::sync::Mutex<AsyncStream>>,
+) -> HdbResult<()> {
+    trace!("send_and_receive_async()");
+    let mut buf = Vec::<u8>::with_capacity(14);
+    emit_initial_request(&mut buf)?;
+    let mut stream = stream.lock().await;
+    stream.send(&buf).await?;
+    stream.receive_exact(8).await?; // ignore the response content
+
+    debug!("successfully initialized");
+    Ok(())
+}
+
 fn emit_initial_request<W: std::io::Write>(w: &mut W) -> HdbResult<()> {
     const FILLER: i32 = -1;
     const MAJOR_PRODUCT_VERSION: i8 = 4;
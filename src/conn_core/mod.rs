@@ -8,6 +8,17 @@ pub mod connect_params;
 pub mod connect_params_builder;
 mod connection_core;
 mod initial_request;
+mod routing;
 mod session_state;
+mod statement_cache;
 
 pub(crate) use self::am_conn_core::AmConnCore;
+pub(crate) use self::connection_core::ConnectionCore;
+#[cfg(feature = "alpha_routing")]
+pub(crate) use self::routing::Routing;
+pub(crate) use self::routing::{routing_target, RoutingTarget};
+pub use self::session_state::{IsolationLevel, TransactionState};
+pub(crate) use self::session_state::{
+    isolation_level_statement, read_only_statement, session_variable_statement,
+};
+pub(crate) use self::statement_cache::CachedStatement;
@@ -0,0 +1,244 @@
+#[cfg(feature = "alpha_routing")]
+use crate::authentication;
+#[cfg(feature = "alpha_routing")]
+use crate::conn_core::am_conn_core::AmConnCore;
+#[cfg(feature = "alpha_routing")]
+use crate::conn_core::connect_params::ConnectParams;
+#[cfg(feature = "alpha_routing")]
+use crate::conn_core::session_state::SessionSettings;
+#[cfg(feature = "alpha_routing")]
+use crate::protocol::parts::client_info::ClientInfo;
+use crate::protocol::parts::hdb_value::HdbValue;
+use crate::protocol::parts::parameter_descriptor::ParameterDescriptors;
+use crate::protocol::parts::partiton_information::PartitionInformation;
+#[cfg(feature = "alpha_routing")]
+use crate::HdbResult;
+use std::sync::Arc;
+
+// Where the executions of a prepared statement can be routed to.
+#[derive(Clone, Debug)]
+pub(crate) enum RoutingTarget {
+    // the tables, or all partitions of the table, are located on this volume
+    Volume(i32),
+    // the volume is determined for each execution from the parameter values
+    Partitioned(Arc<PartitionInformation>),
+}
+
+impl RoutingTarget {
+    // The volume to which an execution with the given rows of input values can be routed;
+    // rows that belong to different volumes are not routed.
+    #[cfg_attr(not(feature = "alpha_routing"), allow(dead_code))]
+    pub fn volume_of<'v, I>(&self, mut rows: I, descriptors: &ParameterDescriptors) -> Option<i32>
+    where
+        I: Iterator<Item = &'v [HdbValue<'v>]>,
+    {
+        match self {
+            RoutingTarget::Volume(volume_id) => Some(*volume_id),
+            RoutingTarget::Partitioned(partition_info) => {
+                let volume_id = partition_info.volume_of(rows.next()?, descriptors)?;
+                if rows.all(|row| partition_info.volume_of(row, descriptors) == Some(volume_id)) {
+                    Some(volume_id)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+// Decides, when a statement is prepared, to which volume its executions can be routed.
+//
+// If the server sends partition information, the table is partitioned; if all partitions
+// are located on the same volume, the statement is routed to it, otherwise the partition
+// is determined from the parameter values of each execution.
+// Without partition information, the statement is routed if all its tables are located
+// on the same volume.
+pub(crate) fn routing_target(
+    o_table_location: Option<&[i32]>,
+    o_partition_info: Option<PartitionInformation>,
+) -> Option<RoutingTarget> {
+    let volume_ids = match o_partition_info {
+        Some(partition_info) => match partition_info.partition_volumes() {
+            Some(partition_volumes) => {
+                if all_equal(&partition_volumes) {
+                    partition_volumes
+                } else {
+                    return Some(RoutingTarget::Partitioned(Arc::new(partition_info)));
+                }
+            }
+            None => o_table_location?.to_vec(),
+        },
+        None => o_table_location?.to_vec(),
+    };
+    if all_equal(&volume_ids) {
+        volume_ids.first().cloned().map(RoutingTarget::Volume)
+    } else {
+        None
+    }
+}
+
+fn all_equal(volume_ids: &[i32]) -> bool {
+    volume_ids.windows(2).all(|pair| pair[0] == pair[1])
+}
+
+// The outcome of looking up the routing connection for a volume.
+#[cfg(feature = "alpha_routing")]
+pub(crate) enum Routing {
+    // the statement is executed on the primary connection
+    Local,
+    // the routing connection exists already
+    Connected(AmConnCore),
+    // the routing connection has to be opened with the given setup
+    Unconnected(Box<RoutingSetup>),
+}
+
+// What is needed to open a routing connection and to bring its session into the
+// state of the primary connection's session.
+// Opening the connection happens without holding the lock of the primary connection.
+#[cfg(feature = "alpha_routing")]
+pub(crate) struct RoutingSetup {
+    pub params: ConnectParams,
+    pub fetch_size: u32,
+    pub lob_read_length: u32,
+    pub lob_write_length: usize,
+    pub max_batch_rows: usize,
+    pub max_batch_bytes: usize,
+    pub client_info: ClientInfo,
+    pub o_schema: Option<String>,
+    pub session_settings: SessionSettings,
+}
+
+#[cfg(feature = "alpha_routing")]
+impl RoutingSetup {
+    pub fn open(self) -> HdbResult<AmConnCore> {
+        let mut am_conn_core = AmConnCore::try_new(self.params.clone())?;
+        authentication::authenticate(&mut am_conn_core, &self.params)?;
+        let mut conn_core = am_conn_core.lock()?;
+        conn_core.set_fetch_size(self.fetch_size);
+        conn_core.set_lob_read_length(self.lob_read_length);
+        conn_core.set_lob_write_length(self.lob_write_length);
+        conn_core.set_max_batch_rows(self.max_batch_rows);
+        conn_core.set_max_batch_bytes(self.max_batch_bytes);
+        conn_core.set_client_info(self.client_info);
+        if let Some(schema) = self.o_schema {
+            conn_core.execute_setting(&schema_statement(&schema))?;
+        }
+        conn_core.adopt_session_settings(self.session_settings)?;
+        drop(conn_core);
+        Ok(am_conn_core)
+    }
+}
+
+#[cfg(feature = "alpha_routing")]
+fn schema_statement(schema: &str) -> String {
+    format!("SET SCHEMA \"{}\"", schema.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{routing_target, RoutingTarget};
+    use crate::protocol::parts::hdb_value::HdbValue;
+    use crate::protocol::parts::parameter_descriptor::ParameterDescriptors;
+    use crate::protocol::parts::partiton_information::PartitionInformation;
+    use byteorder::{LittleEndian, WriteBytesExt};
+
+    // Serializes partition information with hash partitioning by the first parameter.
+    fn partition_info(partitions: &[(i32, i32)]) -> PartitionInformation {
+        let mut buf = Vec::<u8>::new();
+        buf.push(2); // hash
+        buf.extend_from_slice(&[0; 7]);
+        buf.write_i32::<LittleEndian>(1).unwrap();
+        buf.write_i32::<LittleEndian>(partitions.len() as i32)
+            .unwrap();
+        buf.write_i32::<LittleEndian>(1).unwrap(); // parameter index
+        buf.extend_from_slice(&[0, 3, 0, 0]); // function, attribute type, filler
+        for (partition_id, volume_id) in partitions {
+            buf.write_i32::<LittleEndian>(*partition_id).unwrap();
+            buf.write_i32::<LittleEndian>(*volume_id).unwrap();
+        }
+        PartitionInformation::parse(&mut std::io::Cursor::new(buf)).unwrap()
+    }
+
+    // The volume of a statement whose executions are routed independent of their values.
+    fn routing_volume(
+        o_table_location: Option<&[i32]>,
+        o_partition_info: Option<PartitionInformation>,
+    ) -> Option<i32> {
+        match routing_target(o_table_location, o_partition_info) {
+            Some(RoutingTarget::Volume(volume_id)) => Some(volume_id),
+            _ => None,
+        }
+    }
+
+    // Serializes the metadata of a single INT input parameter.
+    fn int_descriptors() -> ParameterDescriptors {
+        let mut buf = Vec::<u8>::new();
+        buf.extend_from_slice(&[0, 3, 1, 0]); // options, type id INT, mode IN, filler
+        buf.write_i32::<LittleEndian>(-1).unwrap(); // no name
+        buf.write_i16::<LittleEndian>(10).unwrap(); // length
+        buf.write_i16::<LittleEndian>(0).unwrap(); // fraction
+        buf.extend_from_slice(&[0; 4]);
+        ParameterDescriptors::parse(1, &mut std::io::Cursor::new(buf)).unwrap()
+    }
+
+    #[test]
+    fn test_routing_volume() {
+        // no location information
+        assert_eq!(routing_volume(None, None), None);
+        assert_eq!(routing_volume(Some(&[]), None), None);
+
+        // unpartitioned tables
+        assert_eq!(routing_volume(Some(&[3]), None), Some(3));
+        assert_eq!(routing_volume(Some(&[3, 3]), None), Some(3));
+        assert_eq!(routing_volume(Some(&[3, 4]), None), None);
+
+        // the partition information takes precedence over the table location
+        let on_one_volume = partition_info(&[(1, 4), (2, 4)]);
+        assert_eq!(routing_volume(Some(&[3]), Some(on_one_volume)), Some(4));
+        let on_one_volume = partition_info(&[(1, 4), (2, 4)]);
+        assert_eq!(routing_volume(None, Some(on_one_volume)), Some(4));
+        let without_partitions = partition_info(&[]);
+        assert_eq!(
+            routing_volume(Some(&[3]), Some(without_partitions)),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_routing_by_value() {
+        let on_two_volumes = partition_info(&[(1, 3), (2, 4)]);
+        let target = routing_target(Some(&[3]), Some(on_two_volumes)).unwrap();
+        let descriptors = int_descriptors();
+
+        // every value is assigned to one of the volumes, always to the same
+        let mut volumes = Vec::new();
+        for i in 0..20 {
+            let row = vec![HdbValue::INT(i)];
+            let volume = target.volume_of(std::iter::once(row.as_slice()), &descriptors);
+            assert!(volume == Some(3) || volume == Some(4));
+            assert_eq!(
+                volume,
+                target.volume_of(std::iter::once(row.as_slice()), &descriptors)
+            );
+            volumes.push(volume);
+        }
+        assert!(volumes.contains(&Some(3)) && volumes.contains(&Some(4)));
+
+        // rows of different volumes, and NULL values, are not routed
+        let row_3 = vec![HdbValue::INT(
+            volumes.iter().position(|v| *v == Some(3)).unwrap() as i32,
+        )];
+        let row_4 = vec![HdbValue::INT(
+            volumes.iter().position(|v| *v == Some(4)).unwrap() as i32,
+        )];
+        let rows = vec![row_3.as_slice(), row_4.as_slice()];
+        assert_eq!(target.volume_of(rows.into_iter(), &descriptors), None);
+        let rows = vec![row_3.as_slice(), row_3.as_slice()];
+        assert_eq!(target.volume_of(rows.into_iter(), &descriptors), Some(3));
+        let null_row = vec![HdbValue::NULL];
+        assert_eq!(
+            target.volume_of(std::iter::once(null_row.as_slice()), &descriptors),
+            None
+        );
+    }
+}
@@ -1,11 +1,12 @@
 use crate::protocol::parts::option_value::OptionValue;
 use crate::protocol::parts::transactionflags::{TaFlagId, TransactionFlags};
+use std::fmt;
 
 // Session state.
 #[derive(Debug)]
 pub(crate) struct SessionState {
     pub ta_state: TransactionState,
-    pub isolation_level: u8,
+    pub isolation_level: IsolationLevel,
     pub ddl_commit_mode: bool, // unclear
     pub read_only_mode: bool,  // unclear
     pub dead: bool,
@@ -14,7 +15,7 @@ impl Default for SessionState {
     fn default() -> SessionState {
         SessionState {
             ta_state: TransactionState::Initial,
-            isolation_level: 0,
+            isolation_level: IsolationLevel::ReadCommitted,
             ddl_commit_mode: true,
             read_only_mode: false,
             dead: false,
@@ -22,14 +23,18 @@ impl Default for SessionState {
     }
 }
 impl SessionState {
-    pub fn update(&mut self, transaction_flags: TransactionFlags) {
+    // Returns true if the flags report the end of the transaction.
+    pub fn update(&mut self, transaction_flags: TransactionFlags) -> bool {
+        let mut transaction_ended = false;
         for (id, value) in transaction_flags {
             match (id, value) {
                 (TaFlagId::RolledBack, OptionValue::BOOLEAN(true)) => {
-                    self.ta_state = TransactionState::RolledBack
+                    self.ta_state = TransactionState::RolledBack;
+                    transaction_ended = true;
                 }
                 (TaFlagId::Committed, OptionValue::BOOLEAN(true)) => {
                     self.ta_state = TransactionState::Committed;
+                    transaction_ended = true;
                 }
                 (TaFlagId::WriteTaStarted, OptionValue::BOOLEAN(true)) => {
                     self.ta_state = TransactionState::WriteTransaction;
@@ -38,7 +43,10 @@ impl SessionState {
                     self.ta_state = TransactionState::ReadTransaction;
                 }
                 (TaFlagId::NewIsolationlevel, OptionValue::INT(i)) => {
-                    self.isolation_level = i as u8;
+                    match IsolationLevel::from_i32(i) {
+                        Some(isolation_level) => self.isolation_level = isolation_level,
+                        None => warn!("unknown isolation level {} ignored", i),
+                    }
                 }
                 (TaFlagId::SessionclosingTaError, OptionValue::BOOLEAN(b)) => {
                     self.dead = b;
@@ -57,14 +65,143 @@ impl SessionState {
                 }
             }
         }
+        transaction_ended
     }
 }
 
-#[derive(Debug)]
+// The settings of the session that were changed with the API.
+//
+// They are lost when the session is replaced in a reconnect, so they are applied
+// again to the new session.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SessionSettings {
+    o_isolation_level: Option<IsolationLevel>,
+    o_read_only: Option<bool>,
+    variables: Vec<(String, String)>, // in the order in which they were set
+}
+impl SessionSettings {
+    pub fn set_isolation_level(&mut self, isolation_level: IsolationLevel) {
+        self.o_isolation_level = Some(isolation_level);
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.o_read_only = Some(read_only);
+    }
+
+    pub fn set_variable(&mut self, name: &str, value: &str) {
+        self.variables.retain(|(n, _)| n != name);
+        self.variables.push((name.to_string(), value.to_string()));
+    }
+
+    // The statements that apply the settings to a new session.
+    pub fn statements(&self) -> Vec<String> {
+        let mut statements = Vec::new();
+        if let Some(isolation_level) = self.o_isolation_level {
+            statements.push(isolation_level_statement(isolation_level));
+        }
+        if let Some(read_only) = self.o_read_only {
+            statements.push(read_only_statement(read_only).to_string());
+        }
+        for (name, value) in &self.variables {
+            statements.push(session_variable_statement(name, value));
+        }
+        statements
+    }
+}
+
+pub(crate) fn isolation_level_statement(isolation_level: IsolationLevel) -> String {
+    format!("SET TRANSACTION ISOLATION LEVEL {}", isolation_level)
+}
+
+pub(crate) fn read_only_statement(read_only: bool) -> &'static str {
+    if read_only {
+        "SET TRANSACTION READ ONLY"
+    } else {
+        "SET TRANSACTION READ WRITE"
+    }
+}
+
+pub(crate) fn session_variable_statement(name: &str, value: &str) -> String {
+    format!(
+        "SET '{}' = '{}'",
+        name.replace('\'', "''"),
+        value.replace('\'', "''")
+    )
+}
+
+/// The state of the current transaction, as it was reported by the server.
+///
+/// Is returned from
+/// [`Connection::transaction_state()`](struct.Connection.html#method.transaction_state).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TransactionState {
+    /// No statement was executed yet.
     Initial,
+    /// The last transaction was rolled back.
     RolledBack,
+    /// The last transaction was committed.
     Committed,
+    /// A transaction is open that did not yet write.
     ReadTransaction,
+    /// A transaction is open that has written, and must be committed or rolled back.
     WriteTransaction,
 }
+
+/// The transaction isolation levels that HANA supports.
+///
+/// Is used in
+/// [`Connection::set_isolation_level()`](struct.Connection.html#method.set_isolation_level).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IsolationLevel {
+    /// Each statement sees the data that were committed before it started (the default).
+    ReadCommitted,
+    /// The transaction sees the data that were committed before its first statement.
+    RepeatableRead,
+    /// Like `RepeatableRead`, and concurrent writes of the same data are rejected.
+    Serializable,
+}
+impl IsolationLevel {
+    // The values with which the server reports the isolation level in the transaction flags.
+    fn from_i32(i: i32) -> Option<IsolationLevel> {
+        match i {
+            1 => Some(IsolationLevel::ReadCommitted),
+            2 => Some(IsolationLevel::RepeatableRead),
+            3 => Some(IsolationLevel::Serializable),
+            _ => None,
+        }
+    }
+}
+impl fmt::Display for IsolationLevel {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(match self {
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IsolationLevel, SessionSettings};
+
+    #[test]
+    fn test_session_settings() {
+        let mut settings = SessionSettings::default();
+        assert!(settings.statements().is_empty());
+        settings.set_variable("APP", "a");
+        settings.set_read_only(true);
+        settings.set_variable("USER'S", "b");
+        settings.set_variable("APP", "c");
+        settings.set_isolation_level(IsolationLevel::Serializable);
+        assert_eq!(
+            settings.statements(),
+            vec![
+                "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE",
+                "SET TRANSACTION READ ONLY",
+                "SET 'USER''S' = 'b'",
+                "SET 'APP' = 'c'",
+            ]
+        );
+    }
+}
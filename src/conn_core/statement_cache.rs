@@ -0,0 +1,179 @@
+use crate::conn_core::RoutingTarget;
+use crate::protocol::parts::parameter_descriptor::ParameterDescriptors;
+use crate::protocol::parts::resultset_metadata::ResultSetMetadata;
+use std::sync::Arc;
+
+// The result of preparing a statement, as it is shared between the cache
+// and the prepared statements that use it.
+#[derive(Debug)]
+pub(crate) struct CachedStatement {
+    pub sql: String,
+    pub statement_id: u64,
+    pub o_descriptors: Option<ParameterDescriptors>,
+    pub o_rs_md: Option<ResultSetMetadata>,
+    pub o_routing: Option<RoutingTarget>,
+}
+
+// LRU cache of prepared statements, keyed by their SQL text.
+//
+// A server-side statement id must be dropped exactly once, when neither the cache
+// nor a prepared statement uses it anymore. Since all clones of the Arcs are created
+// and dropped while the ConnectionCore is locked, the last user can be determined
+// reliably with Arc::try_unwrap().
+#[derive(Debug, Default)]
+pub(crate) struct StatementCache {
+    capacity: usize,
+    entries: Vec<Arc<CachedStatement>>, // the most recently used entry is the last one
+    hits: u64,
+    misses: u64,
+}
+
+impl StatementCache {
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub(crate) fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub(crate) fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    // Returns the statement ids of the evicted entries that must be dropped.
+    pub(crate) fn set_capacity(&mut self, capacity: usize) -> Vec<u64> {
+        self.capacity = capacity;
+        let no_of_evictions = self.entries.len().saturating_sub(capacity);
+        self.evict(no_of_evictions)
+    }
+
+    pub(crate) fn get(&mut self, sql: &str) -> Option<Arc<CachedStatement>> {
+        if self.capacity == 0 {
+            return None;
+        }
+        match self.entries.iter().position(|entry| entry.sql == sql) {
+            Some(idx) => {
+                self.hits += 1;
+                let entry = self.entries.remove(idx);
+                self.entries.push(Arc::clone(&entry));
+                Some(entry)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    // Adds an entry, unless the cache is switched off, and returns it.
+    // The statement ids of the evicted entries that must be dropped are returned as well.
+    pub(crate) fn insert(
+        &mut self,
+        cached: CachedStatement,
+    ) -> (Option<Arc<CachedStatement>>, Vec<u64>) {
+        if self.capacity == 0 {
+            return (None, Vec::new());
+        }
+        let mut to_drop = self.remove(&cached.sql);
+        let no_of_evictions = (self.entries.len() + 1).saturating_sub(self.capacity);
+        to_drop.extend(self.evict(no_of_evictions));
+        let entry = Arc::new(cached);
+        self.entries.push(Arc::clone(&entry));
+        (Some(entry), to_drop)
+    }
+
+    // Is called when a prepared statement that uses the entry is dropped;
+    // returns true if the statement id must be dropped.
+    pub(crate) fn release(&self, cached: Arc<CachedStatement>) -> bool {
+        !self.entries.iter().any(|entry| Arc::ptr_eq(entry, &cached))
+            && Arc::try_unwrap(cached).is_ok()
+    }
+
+    // Forgets all entries, e.g. because their statement ids became invalid with a reconnect.
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    // Removes the entry, e.g. because the server does not know its statement id anymore;
+    // returns the statement id if it must be dropped.
+    pub(crate) fn remove(&mut self, sql: &str) -> Vec<u64> {
+        match self.entries.iter().position(|entry| entry.sql == sql) {
+            Some(idx) => unused_statement_id(self.entries.remove(idx))
+                .into_iter()
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn evict(&mut self, no_of_evictions: usize) -> Vec<u64> {
+        self.entries
+            .drain(0..no_of_evictions)
+            .filter_map(unused_statement_id)
+            .collect()
+    }
+}
+
+// Entries that are still in use are dropped by their last user.
+fn unused_statement_id(entry: Arc<CachedStatement>) -> Option<u64> {
+    Arc::try_unwrap(entry)
+        .ok()
+        .map(|cached| cached.statement_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CachedStatement, StatementCache};
+
+    fn cached(sql: &str, statement_id: u64) -> CachedStatement {
+        CachedStatement {
+            sql: sql.to_string(),
+            statement_id,
+            o_descriptors: None,
+            o_rs_md: None,
+            o_routing: None,
+        }
+    }
+
+    #[test]
+    fn test_statement_cache() {
+        let mut cache = StatementCache::default();
+        assert!(cache.insert(cached("a", 1)).0.is_none());
+        assert!(cache.get("a").is_none());
+        assert_eq!(cache.misses(), 0);
+
+        cache.set_capacity(2);
+        let (o_a, to_drop) = cache.insert(cached("a", 1));
+        assert!(to_drop.is_empty());
+        let (_, to_drop) = cache.insert(cached("b", 2));
+        assert!(to_drop.is_empty());
+
+        // "a" becomes the most recently used entry, so "b" is evicted
+        assert_eq!(cache.get("a").unwrap().statement_id, 1);
+        let (_, to_drop) = cache.insert(cached("c", 3));
+        assert_eq!(to_drop, vec![2]);
+        assert!(cache.get("b").is_none());
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        // "a" is still in use, so it is not dropped on eviction, but on its release
+        let to_drop = cache.set_capacity(0);
+        assert_eq!(to_drop, vec![3]);
+        assert!(cache.release(o_a.unwrap()));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut cache = StatementCache::default();
+        cache.set_capacity(2);
+        let (o_a, _) = cache.insert(cached("a", 1));
+        cache.insert(cached("b", 2));
+
+        // "a" is still in use, so it is dropped on its release
+        assert!(cache.remove("a").is_empty());
+        assert!(cache.get("a").is_none());
+        assert!(cache.release(o_a.unwrap()));
+
+        assert_eq!(cache.remove("b"), vec![2]);
+        assert!(cache.remove("b").is_empty());
+    }
+}
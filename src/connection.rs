@@ -1,20 +1,27 @@
 use crate::authentication;
+use crate::cancel_handle::CancelHandle;
 use crate::conn_core::connect_params::ConnectParams;
-use crate::conn_core::AmConnCore;
-use crate::prepared_statement::PreparedStatement;
+use crate::conn_core::{
+    isolation_level_statement, read_only_statement, session_variable_statement, AmConnCore,
+    IsolationLevel, TransactionState,
+};
+use crate::prepared_statement::{drop_statement_ids, PreparedStatement};
 use crate::protocol::argument::Argument;
 use crate::protocol::part::Part;
 use crate::protocol::partkind::PartKind;
 use crate::protocol::parts::command_info::CommandInfo;
-use crate::protocol::parts::resultset::ResultSet;
+use crate::protocol::parts::resultset::{FetchOverrides, ResultSet};
 use crate::protocol::parts::server_error::ServerError;
 use crate::protocol::request::{Request, HOLD_CURSORS_OVER_COMMIT};
 use crate::protocol::request_type::RequestType;
 use crate::protocol::server_resource_consumption_info::ServerResourceConsumptionInfo;
+use crate::savepoint::Savepoint;
 use crate::xa_impl::new_resource_manager;
-use crate::{HdbError, HdbResponse, HdbResult};
+use crate::{HdbError, HdbErrorKind, HdbResponse, HdbResult};
 use chrono::Local;
 use dist_tx::rm::ResourceManager;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Duration;
 
 /// A connection to the database.
 ///
@@ -22,6 +29,7 @@ use dist_tx::rm::ResourceManager;
 pub struct Connection {
     params: ConnectParams,
     am_conn_core: AmConnCore,
+    in_transaction: bool, // transaction() is running
 }
 
 impl Connection {
@@ -43,12 +51,7 @@ impl Connection {
 
         let mut am_conn_core = AmConnCore::try_new(params.clone())?;
 
-        authentication::authenticate(
-            &mut (am_conn_core),
-            params.dbuser(),
-            params.password(),
-            params.clientlocale(),
-        )?;
+        authentication::authenticate(&mut (am_conn_core), &params)?;
 
         {
             let conn_core = am_conn_core.lock()?;
@@ -67,6 +70,7 @@ impl Connection {
         Ok(Connection {
             params,
             am_conn_core,
+            in_transaction: false,
         })
     }
 
@@ -94,7 +98,12 @@ impl Connection {
     /// # }
     /// ```
     pub fn statement<S: AsRef<str>>(&mut self, stmt: S) -> HdbResult<HdbResponse> {
-        execute(&mut self.am_conn_core, stmt.as_ref(), None)
+        execute(
+            &mut self.am_conn_core,
+            stmt.as_ref(),
+            None,
+            FetchOverrides::default(),
+        )
     }
 
     /// Executes a statement and expects a single ResultSet.
@@ -119,6 +128,34 @@ impl Connection {
         self.statement(stmt)?.into_resultset()
     }
 
+    /// Executes a statement and expects a single ResultSet, which is fetched with
+    /// the given fetch size instead of the connection's fetch size.
+    ///
+    /// Allows e.g. running a big export query with a large fetch size,
+    /// without changing the fetch size for the other statements of the connection.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use hdbconnect::{Connection, HdbResult, IntoConnectParams, ResultSet};
+    /// # fn main() -> HdbResult<()> {
+    /// # let mut connection = Connection::new("".into_connect_params()?)?;
+    /// let mut rs = connection.query_with_fetch_size("select * from BIG_TABLE", 100_000)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn query_with_fetch_size<S: AsRef<str>>(
+        &mut self,
+        stmt: S,
+        fetch_size: u32,
+    ) -> HdbResult<ResultSet> {
+        let overrides = FetchOverrides {
+            o_fetch_size: Some(fetch_size),
+            o_lob_read_length: None,
+        };
+        execute(&mut self.am_conn_core, stmt.as_ref(), None, overrides)?.into_resultset()
+    }
+
     /// Executes a statement and expects a single number of affected rows.
     ///
     /// Should be used for DML statements only, i.e., INSERT, UPDATE, DELETE, UPSERT.
@@ -194,6 +231,43 @@ impl Connection {
         )?)
     }
 
+    /// Prepares a statement that uses named parameters (`:name`) instead of `?`.
+    ///
+    /// The driver replaces the named parameters with `?` before sending the statement
+    /// to the database; string literals, quoted identifiers and comments are not changed.
+    /// A name can be used more than once. Named and positional parameters cannot be mixed.
+    ///
+    /// The input of
+    /// [`PreparedStatement::execute()`](struct.PreparedStatement.html#method.execute)
+    /// must be a struct or a map, whose fields are bound to the parameters by their names.
+    /// Fields that are not used as parameters are ignored.
+    ///
+    /// Note that the driver cannot distinguish named parameters from SQLScript variables
+    /// (`:var`) in procedure bodies; such statements must be prepared with
+    /// [`prepare()`](#method.prepare).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use hdbconnect::{Connection, HdbResult, IntoConnectParams};
+    /// # use serde_derive::Serialize;
+    /// # fn main() -> HdbResult<()> {
+    /// # let mut connection = Connection::new("".into_connect_params()?)?;
+    /// #[derive(Serialize)]
+    /// struct Phrase<'a> {
+    ///     text: &'a str,
+    ///     id: i32,
+    /// }
+    /// let mut statement =
+    ///     connection.prepare_named("select * from phrases where ID = :id and TEXT = :text")?;
+    /// let hdbresponse = statement.execute(&Phrase { text: "Foo is bar", id: 42 })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prepare_named<S: AsRef<str>>(&self, stmt: S) -> HdbResult<PreparedStatement> {
+        PreparedStatement::try_new_named(self.am_conn_core.clone(), stmt.as_ref())
+    }
+
     /// Commits the current transaction.
     pub fn commit(&mut self) -> HdbResult<()> {
         self.statement("commit")?.into_success()
@@ -204,6 +278,109 @@ impl Connection {
         self.statement("rollback")?.into_success()
     }
 
+    /// Sets a savepoint in the current transaction.
+    ///
+    /// Auto-commit must be switched off, e.g. by using
+    /// [`transaction()`](#method.transaction).
+    /// The name is used as a quoted identifier.
+    /// See [`Savepoint`](struct.Savepoint.html) for an example.
+    pub fn savepoint<S: AsRef<str>>(&self, name: S) -> HdbResult<Savepoint> {
+        Savepoint::try_new(self.am_conn_core.clone(), name.as_ref())
+    }
+
+    /// Runs the given closure in a transaction.
+    ///
+    /// The transaction is committed if the closure returns `Ok`, and rolled back
+    /// if it returns `Err` or panics. Auto-commit is switched off while the closure runs,
+    /// and is restored afterwards.
+    ///
+    /// If the transaction fails because of a deadlock or a serialization failure
+    /// (see [`HdbErrorKind`](enum.HdbErrorKind.html)), the server has rolled it back,
+    /// and the closure is called again, up to the number of times that is configured with
+    /// [`set_transaction_retries()`](#method.set_transaction_retries) (default: 0),
+    /// with a pause in between that starts with the
+    /// [transaction retry backoff](#method.set_transaction_retry_backoff)
+    /// and is doubled with each repetition.
+    /// Other errors, including lock wait timeouts and errors in the communication
+    /// with the database, are returned without repeating the transaction.
+    ///
+    /// Transactions cannot be nested; use [savepoints](#method.savepoint) instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use hdbconnect::{Connection, HdbResult, IntoConnectParams};
+    /// # fn main() -> HdbResult<()> {
+    /// # let mut connection = Connection::new("".into_connect_params()?)?;
+    /// connection.set_transaction_retries(3)?;
+    /// let count = connection.transaction(|tx| {
+    ///     tx.dml("update ACCOUNTS set BALANCE = BALANCE - 10 where ID = 1")?;
+    ///     tx.dml("update ACCOUNTS set BALANCE = BALANCE + 10 where ID = 2")
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transaction<T, F>(&mut self, mut f: F) -> HdbResult<T>
+    where
+        F: FnMut(&mut Connection) -> HdbResult<T>,
+    {
+        if self.in_transaction {
+            return Err(HdbError::Usage(
+                "A transaction is already open; transactions cannot be nested".to_owned(),
+            ));
+        }
+        let (auto_commit, retries, mut backoff) = {
+            let conn_core = self.am_conn_core.lock()?;
+            (
+                conn_core.is_auto_commit(),
+                conn_core.get_transaction_retries(),
+                conn_core.get_transaction_retry_backoff(),
+            )
+        };
+        self.set_auto_commit(false)?;
+        self.in_transaction = true;
+
+        let mut attempt = 0;
+        let result = loop {
+            let e = match panic::catch_unwind(AssertUnwindSafe(|| f(self))) {
+                Ok(Ok(value)) => match self.commit() {
+                    Ok(()) => break Ok(value),
+                    Err(e) => e,
+                },
+                Ok(Err(e)) => e,
+                Err(payload) => {
+                    if let Err(rollback_error) = self.rollback() {
+                        warn!("rollback after panic failed with {}", rollback_error);
+                    }
+                    self.in_transaction = false;
+                    self.set_auto_commit(auto_commit).ok();
+                    panic::resume_unwind(payload);
+                }
+            };
+            if let Err(rollback_error) = self.rollback() {
+                warn!("rollback failed with {}", rollback_error);
+            }
+            let is_conflict = matches!(
+                e.kind(),
+                HdbErrorKind::Deadlock | HdbErrorKind::SerializationFailure
+            );
+            if !is_conflict || attempt >= retries {
+                break Err(e);
+            }
+            attempt += 1;
+            debug!(
+                "transaction failed with {}, repeating it (attempt {})",
+                e, attempt
+            );
+            std::thread::sleep(backoff);
+            backoff *= 2;
+        };
+
+        self.in_transaction = false;
+        self.set_auto_commit(auto_commit)?;
+        result
+    }
+
     /// Creates a new connection object with the same settings and
     /// authentication.
     pub fn spawn(&self) -> HdbResult<Connection> {
@@ -212,7 +389,18 @@ impl Connection {
             let am_conn_core = self.am_conn_core.lock()?;
             other_conn.set_auto_commit(am_conn_core.is_auto_commit())?;
             other_conn.set_fetch_size(am_conn_core.get_fetch_size())?;
+            other_conn.set_read_ahead(am_conn_core.is_read_ahead())?;
+            other_conn.set_max_batch_rows(am_conn_core.get_max_batch_rows())?;
+            other_conn.set_max_batch_bytes(am_conn_core.get_max_batch_bytes())?;
+            other_conn.set_statement_cache_size(am_conn_core.statement_cache().capacity())?;
+            other_conn.set_transaction_retries(am_conn_core.get_transaction_retries())?;
+            other_conn
+                .set_transaction_retry_backoff(am_conn_core.get_transaction_retry_backoff())?;
             other_conn.set_lob_read_length(am_conn_core.get_lob_read_length())?;
+            other_conn
+                .am_conn_core
+                .lock()?
+                .set_query_timeout(am_conn_core.get_query_timeout());
         }
         Ok(other_conn)
     }
@@ -256,11 +444,118 @@ impl Connection {
         Ok(self.am_conn_core.lock()?.is_auto_commit())
     }
 
+    /// Sets the isolation level of the session's transactions.
+    ///
+    /// Should be called when no transaction is open.
+    /// Fails with `HdbError::Evaluation` if the server does not report the new
+    /// isolation level.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use hdbconnect::{Connection, HdbResult, IntoConnectParams, IsolationLevel};
+    /// # fn main() -> HdbResult<()> {
+    /// # let mut connection = Connection::new("".into_connect_params()?)?;
+    /// connection.set_isolation_level(IsolationLevel::Serializable)?;
+    /// assert_eq!(connection.isolation_level()?, IsolationLevel::Serializable);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_isolation_level(&mut self, isolation_level: IsolationLevel) -> HdbResult<()> {
+        self.exec(isolation_level_statement(isolation_level))?;
+        self.am_conn_core
+            .lock()?
+            .confirm_isolation_level(isolation_level)
+    }
+
+    /// Returns the isolation level of the session's transactions.
+    ///
+    /// The value is kept in sync with the isolation level that the server reports,
+    /// so it also reflects changes with plain `SET TRANSACTION` statements.
+    pub fn isolation_level(&self) -> HdbResult<IsolationLevel> {
+        Ok(self.am_conn_core.lock()?.get_isolation_level())
+    }
+
+    /// Returns the state of the current transaction, as it was reported by the server
+    /// with the last roundtrip.
+    pub fn transaction_state(&self) -> HdbResult<TransactionState> {
+        Ok(self.am_conn_core.lock()?.get_transaction_state())
+    }
+
+    /// Switches the session's transactions to read-only mode, or back.
+    ///
+    /// In read-only mode, the server rejects all writing statements.
+    /// Fails with `HdbError::Evaluation` if the server does not report the new mode.
+    pub fn set_read_only(&mut self, read_only: bool) -> HdbResult<()> {
+        self.exec(read_only_statement(read_only))?;
+        self.am_conn_core.lock()?.confirm_read_only(read_only)
+    }
+
+    /// Returns true if the session's transactions are in read-only mode.
+    pub fn is_read_only(&self) -> HdbResult<bool> {
+        Ok(self.am_conn_core.lock()?.is_read_only())
+    }
+
+    /// Sets a session variable on the server.
+    ///
+    /// Session variables can be read in SQL with `SESSION_CONTEXT('<name>')`.
+    /// Like the isolation level and the read-only mode, the session variables that are
+    /// set with this method are set again if the connection has to be re-established.
+    pub fn set_session_variable(&mut self, name: &str, value: &str) -> HdbResult<()> {
+        self.exec(session_variable_statement(name, value))?;
+        self.am_conn_core
+            .lock()?
+            .session_settings_mut()
+            .set_variable(name, value);
+        Ok(())
+    }
+
     /// Configures the connection's fetch size for future calls.
     pub fn set_fetch_size(&mut self, fetch_size: u32) -> HdbResult<()> {
         self.am_conn_core.lock()?.set_fetch_size(fetch_size);
         Ok(())
     }
+
+    /// Switches read-ahead on or off for resultsets that are created in future calls.
+    ///
+    /// With read-ahead, a resultset requests the next rows from the database in a
+    /// background thread while the application is still processing the rows it already has,
+    /// so the application does not need to wait for each fetch roundtrip.
+    /// This is worthwhile for large resultsets that are consumed row by row,
+    /// e.g. with [`ResultSet::next_row()`](struct.ResultSet.html#method.next_row).
+    ///
+    /// While a fetch is running in the background, other calls on the connection wait
+    /// until it is finished. Read-ahead is off by default.
+    pub fn set_read_ahead(&mut self, read_ahead: bool) -> HdbResult<()> {
+        self.am_conn_core.lock()?.set_read_ahead(read_ahead);
+        Ok(())
+    }
+
+    /// Returns whether read-ahead is switched on.
+    pub fn is_read_ahead(&self) -> HdbResult<bool> {
+        Ok(self.am_conn_core.lock()?.is_read_ahead())
+    }
+
+    /// Configures the query timeout for future statement executions.
+    ///
+    /// If the execution of a statement takes longer, the server cancels it, and
+    /// the call returns with `HdbError::QueryTimeout`.
+    /// The server works with full seconds.
+    /// `Duration::from_secs(0)` switches the query timeout off (which is the default).
+    ///
+    /// The query timeout can be overridden for individual prepared statements with
+    /// [`PreparedStatement::set_query_timeout()`](struct.PreparedStatement.html#method.set_query_timeout).
+    pub fn set_query_timeout(&mut self, query_timeout: Duration) -> HdbResult<()> {
+        self.am_conn_core
+            .lock()?
+            .set_query_timeout(non_zero(query_timeout));
+        Ok(())
+    }
+
+    /// Returns the connection's query timeout, if one is set.
+    pub fn get_query_timeout(&self) -> HdbResult<Option<Duration>> {
+        Ok(self.am_conn_core.lock()?.get_query_timeout())
+    }
     /// Configures the connection's lob read length for future calls.
     pub fn get_lob_read_length(&self) -> HdbResult<u32> {
         Ok(self.am_conn_core.lock()?.get_lob_read_length())
@@ -287,6 +582,110 @@ impl Connection {
         Ok(())
     }
 
+    /// Returns the maximal number of rows that are sent to the database in a single
+    /// roundtrip when a batch is executed.
+    pub fn get_max_batch_rows(&self) -> HdbResult<usize> {
+        Ok(self.am_conn_core.lock()?.get_max_batch_rows())
+    }
+    /// Configures the maximal number of rows that are sent to the database in a single
+    /// roundtrip when a batch is executed.
+    ///
+    /// Bigger batches are split and sent in several roundtrips; the numbers of affected
+    /// rows of all roundtrips are combined into a single response.
+    /// If the server does not support bulk operations with more than 32K rows or
+    /// parameters, the number of rows is limited further.
+    ///
+    /// Note that with auto-commit, each roundtrip is committed separately.
+    /// If a roundtrip fails with an error that is not a database error,
+    /// e.g. because the connection is lost, this error is returned, although the rows
+    /// of earlier roundtrips may have been processed.
+    pub fn set_max_batch_rows(&mut self, max_batch_rows: usize) -> HdbResult<()> {
+        self.am_conn_core.lock()?.set_max_batch_rows(max_batch_rows);
+        Ok(())
+    }
+
+    /// Returns the maximal number of bytes that are sent to the database in a single
+    /// roundtrip when a batch is executed.
+    pub fn get_max_batch_bytes(&self) -> HdbResult<usize> {
+        Ok(self.am_conn_core.lock()?.get_max_batch_bytes())
+    }
+    /// Configures the maximal number of bytes that are sent to the database in a single
+    /// roundtrip when a batch is executed.
+    ///
+    /// A row that is bigger than this limit is sent alone.
+    /// See also [`set_max_batch_rows()`](#method.set_max_batch_rows).
+    pub fn set_max_batch_bytes(&mut self, max_batch_bytes: usize) -> HdbResult<()> {
+        self.am_conn_core
+            .lock()?
+            .set_max_batch_bytes(max_batch_bytes);
+        Ok(())
+    }
+
+    /// Returns the number of times that a transaction is repeated
+    /// if it fails because of a deadlock or a serialization failure.
+    pub fn get_transaction_retries(&self) -> HdbResult<u32> {
+        Ok(self.am_conn_core.lock()?.get_transaction_retries())
+    }
+    /// Configures the number of times that [`transaction()`](#method.transaction)
+    /// repeats a transaction if it fails because of a deadlock or a serialization failure.
+    pub fn set_transaction_retries(&mut self, transaction_retries: u32) -> HdbResult<()> {
+        self.am_conn_core
+            .lock()?
+            .set_transaction_retries(transaction_retries);
+        Ok(())
+    }
+
+    /// Returns the pause before the first repetition of a transaction.
+    pub fn get_transaction_retry_backoff(&self) -> HdbResult<Duration> {
+        Ok(self.am_conn_core.lock()?.get_transaction_retry_backoff())
+    }
+    /// Configures the pause before the first repetition of a transaction;
+    /// the pause is doubled with each further repetition.
+    ///
+    /// See [`transaction()`](#method.transaction).
+    pub fn set_transaction_retry_backoff(&mut self, backoff: Duration) -> HdbResult<()> {
+        self.am_conn_core
+            .lock()?
+            .set_transaction_retry_backoff(backoff);
+        Ok(())
+    }
+
+    /// Returns the maximal number of prepared statements that are kept in the
+    /// connection's statement cache.
+    pub fn get_statement_cache_size(&self) -> HdbResult<usize> {
+        Ok(self.am_conn_core.lock()?.statement_cache().capacity())
+    }
+    /// Configures the maximal number of prepared statements that are kept in the
+    /// connection's statement cache; 0, the default, switches the cache off.
+    ///
+    /// With the cache, [`prepare()`](#method.prepare) reuses the server-side statement
+    /// of an earlier call with the same SQL text, instead of preparing it again;
+    /// dropping a `PreparedStatement` leaves its server-side statement in the cache.
+    /// If the cache is full, the least recently used statement is removed.
+    /// The cache is cleared when the connection is re-established; prepared statements
+    /// of the former session are prepared again when they are executed next.
+    pub fn set_statement_cache_size(&mut self, size: usize) -> HdbResult<()> {
+        let to_drop = self
+            .am_conn_core
+            .lock()?
+            .statement_cache_mut()
+            .set_capacity(size);
+        drop_statement_ids(&mut self.am_conn_core, to_drop);
+        Ok(())
+    }
+
+    /// Returns how often [`prepare()`](#method.prepare) found the statement in the
+    /// statement cache.
+    pub fn get_statement_cache_hits(&self) -> HdbResult<u64> {
+        Ok(self.am_conn_core.lock()?.statement_cache().hits())
+    }
+
+    /// Returns how often [`prepare()`](#method.prepare) did not find the statement in
+    /// the statement cache.
+    pub fn get_statement_cache_misses(&self) -> HdbResult<u64> {
+        Ok(self.am_conn_core.lock()?.statement_cache().misses())
+    }
+
     /// Returns the ID of the connection.
     ///
     /// The ID is set by the server. Can be handy for logging.
@@ -298,6 +697,19 @@ impl Connection {
             .get_connection_id())
     }
 
+    /// Returns a handle with which the currently running statement of this connection
+    /// can be cancelled from another thread.
+    ///
+    /// The handle must be obtained before the statement is started, and stays valid
+    /// also if the connection is re-established.
+    /// See [`CancelHandle`](struct.CancelHandle.html).
+    pub fn cancel_handle(&self) -> HdbResult<CancelHandle> {
+        Ok(CancelHandle::new(
+            self.params.clone(),
+            self.am_conn_core.lock()?.cancel_state(),
+        ))
+    }
+
     ///
     pub fn get_server_resource_consumption_info(&self) -> HdbResult<ServerResourceConsumptionInfo> {
         Ok(self
@@ -383,6 +795,7 @@ impl Connection {
             &mut self.am_conn_core,
             stmt,
             Some(CommandInfo::new(line, module.as_ref())),
+            FetchOverrides::default(),
         )
     }
 
@@ -418,10 +831,11 @@ impl Connection {
     }
 }
 
-fn execute<S>(
+pub(crate) fn execute<S>(
     am_conn_core: &mut AmConnCore,
     stmt: S,
     o_command_info: Option<CommandInfo>,
+    overrides: FetchOverrides,
 ) -> HdbResult<HdbResponse>
 where
     S: AsRef<str>,
@@ -430,26 +844,44 @@ where
         "connection[{:?}]::execute()",
         am_conn_core.lock()?.connect_options().get_connection_id()
     );
+    let request = execute_direct_request(am_conn_core, stmt.as_ref(), o_command_info, overrides)?;
+    let reply = am_conn_core.send(request)?;
+    reply.into_hdbresponse_with(am_conn_core, overrides)
+}
+
+pub(crate) fn execute_direct_request<'a>(
+    am_conn_core: &AmConnCore,
+    stmt: &'a str,
+    o_command_info: Option<CommandInfo>,
+    overrides: FetchOverrides,
+) -> HdbResult<Request<'a>> {
     let mut request = Request::new(RequestType::ExecuteDirect, HOLD_CURSORS_OVER_COMMIT);
-    {
-        let conn_core = am_conn_core.lock()?;
-        let fetch_size = conn_core.get_fetch_size();
-        request.push(Part::new(
-            PartKind::FetchSize,
-            Argument::FetchSize(fetch_size),
-        ));
-        if let Some(command_info) = o_command_info {
-            request.push(Part::new(
-                PartKind::CommandInfo,
-                Argument::CommandInfo(command_info),
-            ));
-        }
+    let conn_core = am_conn_core.lock()?;
+    let fetch_size = overrides
+        .o_fetch_size
+        .unwrap_or_else(|| conn_core.get_fetch_size());
+    request.push(Part::new(
+        PartKind::FetchSize,
+        Argument::FetchSize(fetch_size),
+    ));
+    if let Some(command_info) = o_command_info {
         request.push(Part::new(
-            PartKind::Command,
-            Argument::Command(stmt.as_ref()),
+            PartKind::CommandInfo,
+            Argument::CommandInfo(command_info),
         ));
     }
+    request.push(Part::new(PartKind::Command, Argument::Command(stmt)));
+    if let Some(query_timeout) = conn_core.get_query_timeout() {
+        request.set_query_timeout(query_timeout);
+    }
+    Ok(request)
+}
 
-    let reply = am_conn_core.send(request)?;
-    reply.into_hdbresponse(am_conn_core)
+// A zero duration switches a timeout off.
+pub(crate) fn non_zero(duration: Duration) -> Option<Duration> {
+    if duration == Duration::from_secs(0) {
+        None
+    } else {
+        Some(duration)
+    }
 }
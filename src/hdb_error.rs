@@ -8,6 +8,28 @@ use std::fmt;
 use std::result;
 use std::sync;
 
+// Error codes of the server that are classified with HdbErrorKind.
+const ERR_OUT_OF_MEMORY: i32 = 4;
+const ERR_AUTHENTICATION_FAILED: i32 = 10;
+const ERR_LOCK_WAIT_TIMEOUT: i32 = 131;
+const ERR_DEADLOCK: i32 = 133;
+const ERR_SERIALIZATION_FAILURE: i32 = 138;
+// Error code with which the server reports the cancellation of a statement.
+const ERR_CANCELLED: i32 = 139;
+const ERR_SQL_SYNTAX: i32 = 257;
+const ERR_INSUFFICIENT_PRIVILEGE: i32 = 258;
+const ERR_INVALID_TABLE_NAME: i32 = 259;
+const ERR_INVALID_COLUMN_NAME: i32 = 260;
+const ERR_NOT_NULL_VIOLATION: i32 = 287;
+const ERR_UNIQUE_CONSTRAINT_VIOLATION: i32 = 301;
+const ERR_FOREIGN_KEY_VIOLATION: i32 = 461;
+// Error code with which the server rejects requests it cannot process on the protocol level,
+// including the execution of a statement id that it does not know (anymore).
+const ERR_PROTOCOL: i32 = 1033;
+
+// Longer statements are truncated in the ErrorContext.
+const MAX_STATEMENT_LENGTH: usize = 200;
+
 /// Abbreviation of `Result<T, HdbError>`.
 pub type HdbResult<T> = result::Result<T, HdbError>;
 
@@ -16,10 +38,10 @@ pub type HdbResult<T> = result::Result<T, HdbError>;
 pub enum HdbError {
     // FIXME subsume into Deserialization?? -> has to be done in serde_db!
     /// Conversion of single db value to rust type failed.
-    Conversion(ConversionError),
+    Conversion(ConversionError, Option<Box<ErrorContext>>),
 
     /// Error occured in deserialization of data structures into an application-defined structure.
-    Deserialization(DeserializationError),
+    Deserialization(DeserializationError, Option<Box<ErrorContext>>),
 
     /// Database server responded with an error.
     DbError(ServerError),
@@ -27,32 +49,266 @@ pub enum HdbError {
     /// Database server has a severe issue.
     DbIssue(String),
 
+    /// Database server cancelled the statement because its query timeout was exceeded.
+    QueryTimeout(ServerError),
+
+    /// Statement was cancelled via a [`CancelHandle`](struct.CancelHandle.html).
+    Cancelled,
+
     /// Database server responded with at least one error.
     MixedResults(Vec<ExecutionResult>),
 
     /// Some error occured while reading CESU-8.
-    Cesu8(Cesu8DecodingError),
+    Cesu8(Cesu8DecodingError, Option<Box<ErrorContext>>),
 
     /// Error occured while evaluating a HdbResponse object.
     Evaluation(String),
 
     /// Missing or wrong implementation of HANA's wire protocol.
-    Impl(String),
+    Impl(String, Option<Box<ErrorContext>>),
 
     /// IO error occured in communication with the database.
     Io(std::io::Error),
 
+    /// Network operation exceeded one of the timeouts of the
+    /// [`ConnectParams`](struct.ConnectParams.html).
+    Timeout(std::io::Error),
+
     /// Error occured in thread synchronization.
     Poison(String),
 
     /// Error occured in serialization of rust data into values for the
     /// database.
-    Serialization(SerializationError),
+    Serialization(SerializationError, Option<Box<ErrorContext>>),
 
     /// Error due to wrong usage of API.
     Usage(String),
 }
 
+/// Information about where an error occured.
+///
+/// Is attached to the errors that are reported from the database server
+/// (see [`ServerError::context()`](struct.ServerError.html#method.context)),
+/// and to the errors that occur in the conversion of parameter and column values
+/// or in the processing of a request.
+/// Use [`HdbError::context()`](enum.HdbError.html#method.context) to access it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ErrorContext {
+    o_statement: Option<String>,
+    o_request_type: Option<String>,
+    o_parameter: Option<usize>,
+    o_parameter_name: Option<String>,
+    o_column: Option<usize>,
+}
+
+impl ErrorContext {
+    /// Returns the SQL statement, truncated to 200 characters.
+    pub fn statement(&self) -> Option<&str> {
+        self.o_statement.as_deref()
+    }
+
+    /// Returns the type of the request that was sent to the database server,
+    /// like `ExecuteDirect`, `Prepare`, `Execute`, or `FetchNext`.
+    pub fn request_type(&self) -> Option<&str> {
+        self.o_request_type.as_deref()
+    }
+
+    /// Returns the zero-based index of the input parameter.
+    pub fn parameter(&self) -> Option<usize> {
+        self.o_parameter
+    }
+
+    /// Returns the name of the input parameter, for statements with named parameters.
+    pub fn parameter_name(&self) -> Option<&str> {
+        self.o_parameter_name.as_deref()
+    }
+
+    /// Returns the zero-based index of the column.
+    pub fn column(&self) -> Option<usize> {
+        self.o_column
+    }
+
+    pub(crate) fn for_parameter(idx: usize) -> ErrorContext {
+        ErrorContext {
+            o_parameter: Some(idx),
+            ..ErrorContext::default()
+        }
+    }
+
+    pub(crate) fn for_named_parameter(idx: usize, name: &str) -> ErrorContext {
+        ErrorContext {
+            o_parameter: Some(idx),
+            o_parameter_name: Some(name.to_string()),
+            ..ErrorContext::default()
+        }
+    }
+
+    pub(crate) fn for_column(idx: usize) -> ErrorContext {
+        ErrorContext {
+            o_column: Some(idx),
+            ..ErrorContext::default()
+        }
+    }
+
+    pub(crate) fn for_request(request_type: String, o_statement: Option<&str>) -> ErrorContext {
+        ErrorContext {
+            o_statement: o_statement.map(truncate),
+            o_request_type: Some(request_type),
+            ..ErrorContext::default()
+        }
+    }
+
+    // The information that is already present is kept.
+    pub(crate) fn merge(&mut self, other: ErrorContext) {
+        if self.o_statement.is_none() {
+            self.o_statement = other.o_statement;
+        }
+        if self.o_request_type.is_none() {
+            self.o_request_type = other.o_request_type;
+        }
+        if self.o_parameter.is_none() {
+            self.o_parameter = other.o_parameter;
+        }
+        if self.o_parameter_name.is_none() {
+            self.o_parameter_name = other.o_parameter_name;
+        }
+        if self.o_column.is_none() {
+            self.o_column = other.o_column;
+        }
+    }
+}
+
+// The information of the own context takes precedence.
+fn merge(o_own_context: Option<Box<ErrorContext>>, context: ErrorContext) -> Box<ErrorContext> {
+    match o_own_context {
+        Some(mut own_context) => {
+            own_context.merge(context);
+            own_context
+        }
+        None => Box::new(context),
+    }
+}
+
+fn truncate(stmt: &str) -> String {
+    match stmt.char_indices().nth(MAX_STATEMENT_LENGTH) {
+        Some((pos, _)) => format!("{}...", &stmt[..pos]),
+        None => stmt.to_string(),
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let mut sep = "";
+        if let Some(ref request_type) = self.o_request_type {
+            write!(fmt, "request type: {}", request_type)?;
+            sep = ", ";
+        }
+        if let Some(ref statement) = self.o_statement {
+            write!(fmt, "{}statement: {:?}", sep, statement)?;
+            sep = ", ";
+        }
+        if let Some(parameter) = self.o_parameter {
+            write!(fmt, "{}parameter: {}", sep, parameter)?;
+            if let Some(ref name) = self.o_parameter_name {
+                write!(fmt, " (:{})", name)?;
+            }
+            sep = ", ";
+        }
+        if let Some(column) = self.o_column {
+            write!(fmt, "{}column: {}", sep, column)?;
+        }
+        Ok(())
+    }
+}
+
+/// Classification of an [`HdbError`](enum.HdbError.html), as it is returned from
+/// [`HdbError::kind()`](enum.HdbError.html#method.kind).
+///
+/// The errors reported from the database server are classified by their error code,
+/// for the most common codes; all other server errors have the kind `OtherDbError`.
+///
+/// More kinds can be added in future versions, so matches on `HdbErrorKind`
+/// need a wildcard arm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HdbErrorKind {
+    /// The user could not be authenticated (server error 10).
+    AuthenticationFailed,
+    /// The statement is not valid SQL (server error 257).
+    SqlSyntaxError,
+    /// The user lacks a privilege that is required for the statement (server error 258).
+    InsufficientPrivilege,
+    /// The statement refers to a table that does not exist (server error 259).
+    InvalidTableName,
+    /// The statement refers to a column that does not exist (server error 260).
+    InvalidColumnName,
+    /// A NULL value was inserted into a column that does not allow NULL (server error 287).
+    NotNullViolation,
+    /// A unique constraint was violated (server error 301).
+    UniqueConstraintViolation,
+    /// A foreign key constraint was violated (server error 461).
+    ForeignKeyViolation,
+    /// The transaction was rolled back because of a deadlock (server error 133).
+    Deadlock,
+    /// The transaction was rolled back because a lock could not be acquired
+    /// in time (server error 131).
+    LockWaitTimeout,
+    /// The transaction was rolled back because it could not be serialized
+    /// with a concurrent one (server error 138).
+    SerializationFailure,
+    /// The database server ran out of memory (server error 4).
+    OutOfMemory,
+    /// The statement was cancelled, explicitly or because of its query timeout.
+    Cancelled,
+    /// The server rejected the request on the protocol level (server error 1033).
+    Protocol,
+    /// Any other error that was reported from the database server.
+    OtherDbError,
+    /// The communication with the database failed, or exceeded a network timeout.
+    Io,
+    /// The error occured on the client side, e.g. due to wrong usage of the API,
+    /// or in the conversion of values.
+    Client,
+}
+
+impl HdbErrorKind {
+    /// Returns the kind of a server error with the given error code.
+    pub fn from_code(code: i32) -> HdbErrorKind {
+        match code {
+            ERR_OUT_OF_MEMORY => HdbErrorKind::OutOfMemory,
+            ERR_AUTHENTICATION_FAILED => HdbErrorKind::AuthenticationFailed,
+            ERR_LOCK_WAIT_TIMEOUT => HdbErrorKind::LockWaitTimeout,
+            ERR_DEADLOCK => HdbErrorKind::Deadlock,
+            ERR_SERIALIZATION_FAILURE => HdbErrorKind::SerializationFailure,
+            ERR_CANCELLED => HdbErrorKind::Cancelled,
+            ERR_SQL_SYNTAX => HdbErrorKind::SqlSyntaxError,
+            ERR_INSUFFICIENT_PRIVILEGE => HdbErrorKind::InsufficientPrivilege,
+            ERR_INVALID_TABLE_NAME => HdbErrorKind::InvalidTableName,
+            ERR_INVALID_COLUMN_NAME => HdbErrorKind::InvalidColumnName,
+            ERR_NOT_NULL_VIOLATION => HdbErrorKind::NotNullViolation,
+            ERR_UNIQUE_CONSTRAINT_VIOLATION => HdbErrorKind::UniqueConstraintViolation,
+            ERR_FOREIGN_KEY_VIOLATION => HdbErrorKind::ForeignKeyViolation,
+            ERR_PROTOCOL => HdbErrorKind::Protocol,
+            _ => HdbErrorKind::OtherDbError,
+        }
+    }
+
+    /// Returns true if repeating the failed operation can succeed.
+    ///
+    /// This is the case for transactions that were rolled back because of a conflict
+    /// with a concurrent transaction, and for failures in the communication with the
+    /// database, where the operation should be repeated with a fresh connection.
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            HdbErrorKind::Deadlock
+                | HdbErrorKind::LockWaitTimeout
+                | HdbErrorKind::SerializationFailure
+                | HdbErrorKind::Io
+        )
+    }
+}
+
 impl HdbError {
     /// Return the contained server_error, if any.
     ///
@@ -81,19 +337,186 @@ impl HdbError {
     /// ```
     pub fn server_error(&self) -> Option<&ServerError> {
         match self {
-            HdbError::DbError(server_error) => Some(&server_error),
+            HdbError::DbError(server_error) | HdbError::QueryTimeout(server_error) => {
+                Some(&server_error)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the information about where the error occured, if available.
+    ///
+    /// Example:
+    ///
+    /// ```rust,no_run
+    /// # use hdbconnect::{Connection, HdbResult, IntoConnectParams};
+    /// # fn main() -> HdbResult<()> {
+    /// # let mut connection = Connection::new("".into_connect_params()?)?;
+    /// if let Err(e) = connection.dml("insert into TAB (ID) values(1)") {
+    ///     if let Some(context) = e.context() {
+    ///         println!("{} ({})", e, context);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            HdbError::DbError(server_error) | HdbError::QueryTimeout(server_error) => {
+                server_error.context()
+            }
+            HdbError::Conversion(_, o_context)
+            | HdbError::Deserialization(_, o_context)
+            | HdbError::Serialization(_, o_context)
+            | HdbError::Cesu8(_, o_context)
+            | HdbError::Impl(_, o_context) => o_context.as_deref(),
             _ => None,
         }
     }
+
+    /// Returns the classification of the error.
+    ///
+    /// Example:
+    ///
+    /// ```rust,no_run
+    /// # use hdbconnect::{Connection, HdbErrorKind, HdbResult, IntoConnectParams};
+    /// # fn main() -> HdbResult<()> {
+    /// # let mut connection = Connection::new("".into_connect_params()?)?;
+    /// match connection.dml("insert into TAB (ID) values(1)") {
+    ///     Err(ref e) if e.kind() == HdbErrorKind::UniqueConstraintViolation => {
+    ///         println!("ID 1 exists already");
+    ///     }
+    ///     result => {
+    ///         result?;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn kind(&self) -> HdbErrorKind {
+        match self {
+            HdbError::DbError(server_error) => server_error.kind(),
+            HdbError::QueryTimeout(_) | HdbError::Cancelled => HdbErrorKind::Cancelled,
+            HdbError::DbIssue(_) | HdbError::MixedResults(_) => HdbErrorKind::OtherDbError,
+            HdbError::Io(_) | HdbError::Timeout(_) => HdbErrorKind::Io,
+            HdbError::Conversion(_, _)
+            | HdbError::Deserialization(_, _)
+            | HdbError::Cesu8(_, _)
+            | HdbError::Evaluation(_)
+            | HdbError::Impl(_, _)
+            | HdbError::Poison(_)
+            | HdbError::Serialization(_, _)
+            | HdbError::Usage(_) => HdbErrorKind::Client,
+        }
+    }
+
+    /// Returns true if repeating the failed operation can succeed.
+    ///
+    /// See [`HdbErrorKind::is_retryable()`](enum.HdbErrorKind.html#method.is_retryable).
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+
+    // The server does not know the executed statement id, e.g. because the statement
+    // was invalidated by a DDL statement; it can be prepared again and executed.
+    pub(crate) fn is_invalid_statement_id(&self) -> bool {
+        match self {
+            HdbError::DbError(server_error) => {
+                server_error.code() == ERR_PROTOCOL
+                    && server_error
+                        .text()
+                        .to_lowercase()
+                        .contains("invalid statement id")
+            }
+            _ => false,
+        }
+    }
 }
 // Factory methods
 impl HdbError {
     pub(crate) fn impl_<S: AsRef<str>>(s: S) -> HdbError {
-        HdbError::Impl(s.as_ref().to_owned())
+        HdbError::Impl(s.as_ref().to_owned(), None)
     }
     pub(crate) fn usage_<S: AsRef<str>>(s: S) -> HdbError {
         HdbError::Usage(s.as_ref().to_owned())
     }
+
+    // Adds the context to server errors, and to errors in the conversion of values
+    // or in the processing of the protocol; other errors are returned unchanged.
+    pub(crate) fn with_context(self, context: ErrorContext) -> HdbError {
+        match self {
+            HdbError::DbError(mut server_error) => {
+                server_error.add_context(context);
+                HdbError::DbError(server_error)
+            }
+            HdbError::QueryTimeout(mut server_error) => {
+                server_error.add_context(context);
+                HdbError::QueryTimeout(server_error)
+            }
+            HdbError::MixedResults(mut execution_results) => {
+                for execution_result in &mut execution_results {
+                    if let ExecutionResult::Failure(Some(ref mut server_error)) = execution_result {
+                        server_error.add_context(context.clone());
+                    }
+                }
+                HdbError::MixedResults(execution_results)
+            }
+            HdbError::Conversion(error, o_context) => {
+                HdbError::Conversion(error, Some(merge(o_context, context)))
+            }
+            HdbError::Deserialization(error, o_context) => {
+                HdbError::Deserialization(error, Some(merge(o_context, context)))
+            }
+            HdbError::Serialization(error, o_context) => {
+                HdbError::Serialization(error, Some(merge(o_context, context)))
+            }
+            HdbError::Cesu8(error, o_context) => {
+                HdbError::Cesu8(error, Some(merge(o_context, context)))
+            }
+            HdbError::Impl(s, o_context) => HdbError::Impl(s, Some(merge(o_context, context))),
+            e => e,
+        }
+    }
+
+    // The server reports both an exceeded query timeout and an explicit cancellation
+    // as a cancellation of the statement; an explicit cancellation takes precedence.
+    // A cancellation that was not requested for this statement (e.g. from another session)
+    // is reported as cancellation as well.
+    pub(crate) fn for_cancelled_statement(
+        self,
+        cancel_requested: bool,
+        has_query_timeout: bool,
+    ) -> HdbError {
+        match self {
+            HdbError::DbError(server_error)
+                if server_error.code() == ERR_CANCELLED
+                    && has_query_timeout
+                    && !cancel_requested =>
+            {
+                HdbError::QueryTimeout(server_error)
+            }
+            HdbError::DbError(ref server_error) if server_error.code() == ERR_CANCELLED => {
+                HdbError::Cancelled
+            }
+            e => e,
+        }
+    }
+
+    // Is applied to the errors of io operations on the socket of a connection,
+    // where the read and write timeouts of the ConnectParams apply.
+    // Depending on the platform, an expired socket timeout is reported
+    // with one or the other kind.
+    pub(crate) fn for_socket_io(self) -> HdbError {
+        match self {
+            HdbError::Io(error)
+                if error.kind() == std::io::ErrorKind::TimedOut
+                    || error.kind() == std::io::ErrorKind::WouldBlock =>
+            {
+                HdbError::Timeout(error)
+            }
+            e => e,
+        }
+    }
 }
 
 impl error::Error for HdbError {
@@ -101,29 +524,35 @@ impl error::Error for HdbError {
         match *self {
             HdbError::DbError(_) => "Error from database server",
             HdbError::DbIssue(_) => "Issue on database server",
+            HdbError::QueryTimeout(_) => "Query timeout was exceeded",
+            HdbError::Cancelled => "Statement was cancelled",
             HdbError::MixedResults(_) => "Database server responded with at least one error",
-            HdbError::Conversion(_) => "Conversion of database type to rust type failed",
-            HdbError::Deserialization(ref e) => e.description(),
-            HdbError::Cesu8(ref e) => e.description(),
+            HdbError::Conversion(_, _) => "Conversion of database type to rust type failed",
+            HdbError::Deserialization(ref e, _) => e.description(),
+            HdbError::Cesu8(ref e, _) => e.description(),
             HdbError::Io(ref e) => e.description(),
-            HdbError::Serialization(ref e) => e.description(),
-            HdbError::Impl(ref s)
+            HdbError::Timeout(_) => "Network operation timed out",
+            HdbError::Serialization(ref e, _) => e.description(),
+            HdbError::Impl(ref s, _)
             | HdbError::Evaluation(ref s)
             | HdbError::Usage(ref s)
             | HdbError::Poison(ref s) => s,
         }
     }
 
-    fn cause(&self) -> Option<&error::Error> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
-            HdbError::Cesu8(ref e) => Some(e),
-            HdbError::Conversion(ref error) => Some(error),
-            HdbError::Deserialization(ref error) => Some(error),
-            HdbError::Io(ref error) => Some(error),
-            HdbError::Serialization(ref error) => Some(error),
-            HdbError::DbError(ref server_error) => Some(server_error),
-            HdbError::Impl(_)
+            HdbError::Cesu8(ref e, _) => Some(e),
+            HdbError::Conversion(ref error, _) => Some(error),
+            HdbError::Deserialization(ref error, _) => Some(error),
+            HdbError::Io(ref error) | HdbError::Timeout(ref error) => Some(error),
+            HdbError::Serialization(ref error, _) => Some(error),
+            HdbError::DbError(ref server_error) | HdbError::QueryTimeout(ref server_error) => {
+                Some(server_error)
+            }
+            HdbError::Impl(_, _)
             | HdbError::DbIssue(_)
+            | HdbError::Cancelled
             | HdbError::MixedResults(_)
             | HdbError::Usage(_)
             | HdbError::Poison(_)
@@ -135,17 +564,35 @@ impl error::Error for HdbError {
 impl fmt::Display for HdbError {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            HdbError::Cesu8(ref e) => write!(fmt, "{}", e),
-            HdbError::Conversion(ref e) => write!(fmt, "{}", e),
-            HdbError::Deserialization(ref error) => write!(fmt, "{:?}", error),
+            HdbError::Cesu8(ref e, ref o_context) => {
+                write!(fmt, "{}", e)?;
+                fmt_context(o_context, fmt)
+            }
+            HdbError::Conversion(ref e, ref o_context) => {
+                write!(fmt, "{}", e)?;
+                fmt_context(o_context, fmt)
+            }
+            HdbError::Deserialization(ref error, ref o_context) => {
+                write!(fmt, "{:?}", error)?;
+                fmt_context(o_context, fmt)
+            }
             HdbError::Io(ref error) => write!(fmt, "{:?}", error),
-            HdbError::Impl(ref error) => write!(fmt, "{:?}", error),
-            HdbError::Serialization(ref error) => write!(fmt, "{:?}", error),
+            HdbError::Timeout(ref error) => write!(fmt, "Network operation timed out: {}", error),
+            HdbError::Impl(ref error, ref o_context) => {
+                write!(fmt, "{:?}", error)?;
+                fmt_context(o_context, fmt)
+            }
+            HdbError::Serialization(ref error, ref o_context) => {
+                write!(fmt, "{:?}", error)?;
+                fmt_context(o_context, fmt)
+            }
             HdbError::Evaluation(ref s)
             | HdbError::Usage(ref s)
             | HdbError::Poison(ref s)
             | HdbError::DbIssue(ref s) => write!(fmt, "{:?}", s),
             HdbError::DbError(ref se) => write!(fmt, "{:?}", se),
+            HdbError::QueryTimeout(ref se) => write!(fmt, "Query timeout exceeded: {:?}", se),
+            HdbError::Cancelled => write!(fmt, "Statement was cancelled"),
             HdbError::MixedResults(ref vec_rows_affected) => {
                 write!(fmt, "MixedResults[")?;
                 let mut first = true;
@@ -172,21 +619,28 @@ impl fmt::Display for HdbError {
     }
 }
 
+fn fmt_context(o_context: &Option<Box<ErrorContext>>, fmt: &mut fmt::Formatter) -> fmt::Result {
+    match o_context {
+        Some(context) => write!(fmt, " ({})", context),
+        None => Ok(()),
+    }
+}
+
 impl From<ConversionError> for HdbError {
     fn from(error: ConversionError) -> HdbError {
-        HdbError::Conversion(error)
+        HdbError::Conversion(error, None)
     }
 }
 
 impl From<DeserializationError> for HdbError {
     fn from(error: DeserializationError) -> HdbError {
-        HdbError::Deserialization(error)
+        HdbError::Deserialization(error, None)
     }
 }
 
 impl From<SerializationError> for HdbError {
     fn from(error: SerializationError) -> HdbError {
-        HdbError::Serialization(error)
+        HdbError::Serialization(error, None)
     }
 }
 
@@ -210,7 +664,7 @@ impl From<fmt::Error> for HdbError {
 
 impl From<Cesu8DecodingError> for HdbError {
     fn from(error: Cesu8DecodingError) -> HdbError {
-        HdbError::Cesu8(error)
+        HdbError::Cesu8(error, None)
     }
 }
 
@@ -219,3 +673,91 @@ impl<'a, T> From<sync::PoisonError<sync::MutexGuard<'a, T>>> for HdbError {
         HdbError::Poison(error.description().to_owned())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorContext, HdbError, HdbErrorKind};
+    use crate::protocol::parts::server_error::{ServerError, Severity};
+    use serde_db::de::ConversionError;
+    use serde_db::ser::SerializationError;
+    use std::error::Error;
+
+    #[test]
+    fn test_kind() {
+        assert_eq!(
+            HdbErrorKind::from_code(301),
+            HdbErrorKind::UniqueConstraintViolation
+        );
+        assert_eq!(HdbErrorKind::from_code(133), HdbErrorKind::Deadlock);
+        assert_eq!(HdbErrorKind::from_code(12345), HdbErrorKind::OtherDbError);
+        assert!(HdbErrorKind::from_code(131).is_retryable());
+        assert!(!HdbErrorKind::from_code(258).is_retryable());
+
+        let io_error = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset");
+        let error = HdbError::from(io_error);
+        assert_eq!(error.kind(), HdbErrorKind::Io);
+        assert!(error.is_retryable());
+
+        let error = HdbError::usage_("wrong");
+        assert_eq!(error.kind(), HdbErrorKind::Client);
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn test_invalid_statement_id() {
+        let server_error = |code, text: &str| {
+            HdbError::DbError(ServerError::new(
+                code,
+                0,
+                Severity::Error,
+                b"HY000".to_vec(),
+                text.to_string(),
+            ))
+        };
+        assert!(
+            server_error(1033, "error while parsing protocol: invalid statement id")
+                .is_invalid_statement_id()
+        );
+        assert!(!server_error(1033, "error while parsing protocol").is_invalid_statement_id());
+        assert!(!server_error(259, "invalid table name").is_invalid_statement_id());
+    }
+
+    #[test]
+    fn test_context() {
+        let error = HdbError::Conversion(ConversionError::ValueType("no int".to_string()), None)
+            .with_context(ErrorContext::for_column(2))
+            .with_context(ErrorContext::for_request(
+                "Execute".to_string(),
+                Some(&"x".repeat(300)),
+            ));
+        let context = error.context().unwrap();
+        assert_eq!(context.column(), Some(2));
+        assert_eq!(context.parameter(), None);
+        assert_eq!(context.request_type(), Some("Execute"));
+        assert_eq!(context.statement().unwrap().chars().count(), 203);
+        assert!(error.source().is_some());
+
+        // the variant is kept, the context is appended to the error
+        match error {
+            HdbError::Conversion(ref e, _) => assert_eq!(
+                error.to_string(),
+                format!(
+                    "{} (request type: Execute, statement: {:?}, column: 2)",
+                    e,
+                    context.statement().unwrap()
+                )
+            ),
+            ref e => panic!("unexpected error {:?}", e),
+        }
+
+        let error = HdbError::Serialization(SerializationError::StructuralMismatch("no int"), None)
+            .with_context(ErrorContext::for_named_parameter(1, "id"));
+        assert_eq!(error.context().unwrap().parameter_name(), Some("id"));
+        assert!(error.to_string().ends_with(" (parameter: 1 (:id))"));
+
+        // errors that are not related to values or the server are not changed
+        let error = HdbError::usage_("wrong").with_context(ErrorContext::for_column(2));
+        assert!(error.context().is_none());
+        assert!(error.source().is_none());
+    }
+}
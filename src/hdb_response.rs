@@ -201,16 +201,20 @@ impl HdbResponse {
             .count()
             > 0
         {
-            return Err(HdbError::Impl(format!(
-                "resultset(): Unexpected InternalReturnValue(s) received: {:?}",
-                int_return_values
-            )));
+            return Err(HdbError::Impl(
+                format!(
+                    "resultset(): Unexpected InternalReturnValue(s) received: {:?}",
+                    int_return_values
+                ),
+                None,
+            ));
         }
 
         if rs_count > 1 || pm_count > 1 {
             return Err(HdbError::Impl(
                 "resultset(): too many InternalReturnValue(s) of expected types received"
                     .to_owned(),
+                None,
             ));
         }
         Ok(match (int_return_values.pop(), int_return_values.pop()) {
@@ -233,6 +237,7 @@ impl HdbResponse {
             (None, None) | (_, _) => {
                 return Err(HdbError::Impl(
                     "Nothing found, but a single Resultset was expected".to_owned(),
+                    None,
                 ));
             }
         })
@@ -244,6 +249,7 @@ impl HdbResponse {
         if int_return_values.len() > 1 {
             return Err(HdbError::Impl(
                 "Only a single AffectedRows was expected".to_owned(),
+                None,
             ));
         }
         match int_return_values.pop() {
@@ -256,6 +262,7 @@ impl HdbResponse {
                         ExecutionResult::Failure(_) => {
                             return Err(HdbError::Impl(
                                 "Found unexpected returnvalue ExecutionFailed".to_owned(),
+                                None,
                             ));
                         }
                     }
@@ -267,18 +274,23 @@ impl HdbResponse {
             }
             Some(InternalReturnValue::OutputParameters(_)) => Err(HdbError::Impl(
                 "Found OutputParameters, but a single AffectedRows was expected".to_owned(),
+                None,
             )),
             Some(InternalReturnValue::ParameterMetadata(_)) => Err(HdbError::Impl(
                 "Found ParameterMetadata, but a single AffectedRows was expected".to_owned(),
+                None,
             )),
             Some(InternalReturnValue::ResultSet(_)) => Err(HdbError::Impl(
                 "Found ResultSet, but a single AffectedRows was expected".to_owned(),
+                None,
             )),
             Some(InternalReturnValue::WriteLobReply(_)) => Err(HdbError::Impl(
                 "Found WriteLobReply, but a single AffectedRows was expected".to_owned(),
+                None,
             )),
             None => Err(HdbError::Impl(
                 "Nothing found, but a single AffectedRows was expected".to_owned(),
+                None,
             )),
         }
     }
@@ -295,6 +307,7 @@ impl HdbResponse {
             return Err(HdbError::Impl(
                 "found multiple InternalReturnValues, but only a single Success was expected"
                     .to_owned(),
+                None,
             ));
         }
         match int_return_values.pop() {
@@ -304,6 +317,7 @@ impl HdbResponse {
                         "found no or multiple affected-row-counts, but only a single Success was \
                          expected"
                             .to_owned(),
+                        None,
                     ));
                 }
                 match vec_ra.pop().unwrap() {
@@ -313,6 +327,7 @@ impl HdbResponse {
                                 "found an affected-row-count > 0, but only a single Success was \
                                  expected"
                                     .to_owned(),
+                                None,
                             ))
                         } else {
                             Ok(HdbResponse {
@@ -327,23 +342,29 @@ impl HdbResponse {
                     }),
                     ExecutionResult::Failure(_) => Err(HdbError::Impl(
                         "Found unexpected returnvalue ExecutionFailed".to_owned(),
+                        None,
                     )),
                 }
             }
             Some(InternalReturnValue::OutputParameters(_)) => Err(HdbError::Impl(
                 "Found OutputParameters, but a single Success was expected".to_owned(),
+                None,
             )),
             Some(InternalReturnValue::ParameterMetadata(_)) => Err(HdbError::Impl(
                 "Found ParameterMetadata, but a single Success was expected".to_owned(),
+                None,
             )),
             Some(InternalReturnValue::ResultSet(_)) => Err(HdbError::Impl(
                 "Found ResultSet, but a single Success was expected".to_owned(),
+                None,
             )),
             Some(InternalReturnValue::WriteLobReply(_)) => Err(HdbError::Impl(
                 "Found WriteLobReply, but a single Success was expected".to_owned(),
+                None,
             )),
             None => Err(HdbError::Impl(
                 "Nothing found, but a single Success was expected".to_owned(),
+                None,
             )),
         }
     }
@@ -365,6 +386,7 @@ impl HdbResponse {
                             ExecutionResult::Failure(_) => {
                                 return Err(HdbError::Impl(
                                     "Found unexpected returnvalue 'ExecutionFailed'".to_owned(),
+                                    None,
                                 ));
                             }
                         }
@@ -383,6 +405,7 @@ impl HdbResponse {
                 InternalReturnValue::WriteLobReply(_) => {
                     return Err(HdbError::Impl(
                         "found WriteLobReply in multiple_return_values()".to_owned(),
+                        None,
                     ));
                 }
             }
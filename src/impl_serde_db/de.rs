@@ -1,11 +1,13 @@
-use crate::HdbError;
+use crate::{ErrorContext, HdbError, HdbResult};
 use bigdecimal::ToPrimitive;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use serde_db::de::{
     ConversionError, DbValue, DbValueInto, DeserializableResultset, DeserializableRow,
     DeserializationError, DeserializationResult,
 };
+use std::cell::Cell;
 use std::error::Error;
+use std::rc::Rc;
 use std::{fmt, i16, i32, i64, i8, u16, u32, u8};
 
 use crate::protocol::parts::hdb_value::HdbValue;
@@ -54,6 +56,104 @@ impl DeserializableRow for Row {
     }
 }
 
+// Converts the row into a rust value; a failing value conversion is reported
+// with the column of the value.
+pub(crate) fn row_into_typed<'de, T>(row: Row) -> HdbResult<T>
+where
+    T: serde::de::Deserialize<'de>,
+{
+    let column = Rc::new(Cell::new(None));
+    DeserializableRow::into_typed(TrackedRow::new(row, Rc::clone(&column)))
+        .map_err(|e| with_column(e, column.get()))
+}
+
+// Converts the resultset into a rust value; a failing value conversion is reported
+// with the column of the value.
+pub(crate) fn resultset_into_typed<'de, T>(rs: ResultSet) -> HdbResult<T>
+where
+    T: serde::de::Deserialize<'de>,
+{
+    let column = Rc::new(Cell::new(None));
+    DeserializableResultset::into_typed(TrackedResultSet {
+        rs,
+        column: Rc::clone(&column),
+    })
+    .map_err(|e| with_column(e, column.get()))
+}
+
+fn with_column(error: HdbError, o_column: Option<usize>) -> HdbError {
+    match (error, o_column) {
+        (
+            error @ HdbError::Deserialization(DeserializationError::ConversionError(_), _),
+            Some(column),
+        ) => error.with_context(ErrorContext::for_column(column)),
+        (error, _) => error,
+    }
+}
+
+// The deserializers of serde_db consume the rows, so the column of the value
+// that was handed out last is kept in a cell that outlives them.
+struct TrackedResultSet {
+    rs: ResultSet,
+    column: Rc<Cell<Option<usize>>>,
+}
+
+impl DeserializableResultset for TrackedResultSet {
+    type ROW = TrackedRow;
+    type E = HdbError;
+
+    fn has_multiple_rows(&mut self) -> Result<bool, DeserializationError> {
+        DeserializableResultset::has_multiple_rows(&mut self.rs)
+    }
+
+    fn next(&mut self) -> DeserializationResult<Option<TrackedRow>> {
+        Ok(DeserializableResultset::next(&mut self.rs)?
+            .map(|row| TrackedRow::new(row, Rc::clone(&self.column))))
+    }
+
+    fn number_of_fields(&self) -> usize {
+        DeserializableResultset::number_of_fields(&self.rs)
+    }
+
+    fn fieldname(&self, i: usize) -> Option<&String> {
+        DeserializableResultset::fieldname(&self.rs, i)
+    }
+}
+
+struct TrackedRow {
+    row: Row,
+    column: Rc<Cell<Option<usize>>>,
+}
+
+impl TrackedRow {
+    fn new(row: Row, column: Rc<Cell<Option<usize>>>) -> TrackedRow {
+        TrackedRow { row, column }
+    }
+}
+
+impl DeserializableRow for TrackedRow {
+    type V = HdbValue<'static>;
+    type E = HdbError;
+
+    fn len(&self) -> usize {
+        self.row.len()
+    }
+
+    fn next(&mut self) -> Option<HdbValue<'static>> {
+        self.column
+            .set(Some(self.row.number_of_fields() - self.row.len()));
+        self.row.next_value()
+    }
+
+    fn number_of_fields(&self) -> usize {
+        self.row.number_of_fields()
+    }
+
+    fn fieldname(&self, field_idx: usize) -> Option<&String> {
+        DeserializableRow::fieldname(&self.row, field_idx)
+    }
+}
+
 impl DbValue for HdbValue<'static> {
     fn is_null(&self) -> bool {
         match *self {
@@ -3,7 +3,9 @@ use std::num::ParseFloatError;
 use std::num::ParseIntError;
 
 use crate::protocol::parts::hdb_value::HdbValue;
-use crate::protocol::parts::parameter_descriptor::ParameterDescriptor;
+use crate::protocol::parts::parameter_descriptor::{
+    ParameterDescriptor, ParameterDescriptors, ParameterDirection,
+};
 use crate::protocol::parts::type_id::TypeId;
 use crate::types::DayDate;
 use crate::types::LongDate;
@@ -12,10 +14,13 @@ use crate::types::SecondTime;
 
 use bigdecimal::BigDecimal;
 use bigdecimal::FromPrimitive;
-use serde_db::ser::{parse_error, DbvFactory, SerializationError};
+use serde::ser::{Impossible, Serialize, SerializeMap, SerializeStruct, Serializer};
+use serde_db::ser::{parse_error, to_params, DbvFactory, SerializationError};
 use std::str::FromStr;
 use std::{i16, i32, i64, i8, u16, u32, u8};
 
+use crate::{ErrorContext, HdbError, HdbResult};
+
 impl DbvFactory for &ParameterDescriptor {
     type DBV = HdbValue<'static>;
 
@@ -348,3 +353,311 @@ fn type_mismatch(value_type: &'static str, db_type: String) -> SerializationErro
         db_type,
     }
 }
+
+// Converts a struct or a map into a row of parameters,
+// where the fields are assigned to the parameters by their names.
+//
+// `names` are the parameter names, in the order of the parameter descriptors.
+// Fields that are not used as parameter are ignored.
+pub(crate) fn to_named_params<T: Serialize>(
+    input: &T,
+    names: &[String],
+    descriptors: &ParameterDescriptors,
+) -> HdbResult<Vec<HdbValue<'static>>> {
+    if names.len() != descriptors.ref_inner().len() {
+        return Err(HdbError::Usage(format!(
+            "The statement has {} named parameters, but {} parameters were described",
+            names.len(),
+            descriptors.ref_inner().len()
+        )));
+    }
+    let in_parameters: Vec<(&str, &ParameterDescriptor)> = names
+        .iter()
+        .zip(descriptors.ref_inner())
+        .filter(|(_, descriptor)| descriptor.direction() != ParameterDirection::OUT)
+        .map(|(name, descriptor)| (name.as_str(), descriptor))
+        .collect();
+
+    let mut serializer = NamedSerializer {
+        values: in_parameters.iter().map(|_| None).collect(),
+        in_parameters,
+        o_key: None,
+        o_failed: None,
+    };
+    if let Err(e) = input.serialize(&mut serializer) {
+        return Err(match serializer.o_failed {
+            Some(idx) => HdbError::from(e).with_context(ErrorContext::for_named_parameter(
+                idx,
+                serializer.in_parameters[idx].0,
+            )),
+            None => HdbError::from(e),
+        });
+    }
+
+    let mut values = Vec::with_capacity(serializer.values.len());
+    for ((name, _), o_value) in serializer.in_parameters.iter().zip(serializer.values) {
+        values.push(o_value.ok_or_else(|| {
+            HdbError::Usage(format!("No value provided for parameter :{}", name))
+        })?);
+    }
+    Ok(values)
+}
+
+struct NamedSerializer<'a> {
+    in_parameters: Vec<(&'a str, &'a ParameterDescriptor)>,
+    values: Vec<Option<HdbValue<'static>>>,
+    o_key: Option<String>,
+    o_failed: Option<usize>, // the index of the parameter whose conversion failed
+}
+
+impl<'a> NamedSerializer<'a> {
+    // A name can be used for several parameters.
+    fn set_value<T: ?Sized + Serialize>(
+        &mut self,
+        name: &str,
+        value: &T,
+    ) -> Result<(), SerializationError> {
+        for (idx, (_, descriptor)) in self
+            .in_parameters
+            .iter()
+            .enumerate()
+            .filter(|(_, (n, _))| *n == name)
+        {
+            self.o_failed = Some(idx);
+            let mut hdb_values = to_params(value, &mut std::iter::once(*descriptor))?;
+            if hdb_values.len() != 1 {
+                return Err(SerializationError::StructuralMismatch(
+                    "a named parameter needs exactly one value",
+                ));
+            }
+            self.values[idx] = hdb_values.pop();
+        }
+        self.o_failed = None;
+        Ok(())
+    }
+}
+
+fn not_by_name() -> SerializationError {
+    SerializationError::StructuralMismatch(
+        "named parameters can only be provided as a struct or a map",
+    )
+}
+
+fn not_a_name() -> SerializationError {
+    SerializationError::StructuralMismatch("the keys of the map must be strings")
+}
+
+macro_rules! reject_values {
+    ($error:ident; $($method:ident: $t:ty),*) => {
+        $(
+            fn $method(self, _value: $t) -> Result<Self::Ok, SerializationError> {
+                Err($error())
+            }
+        )*
+    };
+}
+
+macro_rules! reject_compounds {
+    ($error:ident) => {
+        fn serialize_unit(self) -> Result<Self::Ok, SerializationError> {
+            Err($error())
+        }
+        fn serialize_unit_struct(
+            self,
+            _name: &'static str,
+        ) -> Result<Self::Ok, SerializationError> {
+            Err($error())
+        }
+        fn serialize_unit_variant(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str,
+        ) -> Result<Self::Ok, SerializationError> {
+            Err($error())
+        }
+        fn serialize_newtype_variant<T: ?Sized + Serialize>(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str,
+            _value: &T,
+        ) -> Result<Self::Ok, SerializationError> {
+            Err($error())
+        }
+        fn serialize_none(self) -> Result<Self::Ok, SerializationError> {
+            Err($error())
+        }
+        fn serialize_seq(
+            self,
+            _len: Option<usize>,
+        ) -> Result<Self::SerializeSeq, SerializationError> {
+            Err($error())
+        }
+        fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, SerializationError> {
+            Err($error())
+        }
+        fn serialize_tuple_struct(
+            self,
+            _name: &'static str,
+            _len: usize,
+        ) -> Result<Self::SerializeTupleStruct, SerializationError> {
+            Err($error())
+        }
+        fn serialize_tuple_variant(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str,
+            _len: usize,
+        ) -> Result<Self::SerializeTupleVariant, SerializationError> {
+            Err($error())
+        }
+        fn serialize_struct_variant(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str,
+            _len: usize,
+        ) -> Result<Self::SerializeStructVariant, SerializationError> {
+            Err($error())
+        }
+    };
+}
+
+impl<'a, 'b> Serializer for &'b mut NamedSerializer<'a> {
+    type Ok = ();
+    type Error = SerializationError;
+    type SerializeSeq = Impossible<(), SerializationError>;
+    type SerializeTuple = Impossible<(), SerializationError>;
+    type SerializeTupleStruct = Impossible<(), SerializationError>;
+    type SerializeTupleVariant = Impossible<(), SerializationError>;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), SerializationError>;
+
+    reject_values!(not_by_name; serialize_bool: bool, serialize_i8: i8, serialize_i16: i16,
+        serialize_i32: i32, serialize_i64: i64, serialize_u8: u8, serialize_u16: u16,
+        serialize_u32: u32, serialize_u64: u64, serialize_f32: f32, serialize_f64: f64,
+        serialize_char: char, serialize_str: &str, serialize_bytes: &[u8]);
+    reject_compounds!(not_by_name);
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), SerializationError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), SerializationError> {
+        value.serialize(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self, SerializationError> {
+        Ok(self)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self, SerializationError> {
+        Ok(self)
+    }
+}
+
+impl<'a, 'b> SerializeStruct for &'b mut NamedSerializer<'a> {
+    type Ok = ();
+    type Error = SerializationError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerializationError> {
+        self.set_value(key, value)
+    }
+
+    fn end(self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> SerializeMap for &'b mut NamedSerializer<'a> {
+    type Ok = ();
+    type Error = SerializationError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), SerializationError> {
+        self.o_key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), SerializationError> {
+        let key = self.o_key.take().ok_or_else(not_a_name)?;
+        self.set_value(&key, value)
+    }
+
+    fn end(self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+// Extracts the name from a map key.
+struct KeySerializer;
+
+impl Serializer for KeySerializer {
+    type Ok = String;
+    type Error = SerializationError;
+    type SerializeSeq = Impossible<String, SerializationError>;
+    type SerializeTuple = Impossible<String, SerializationError>;
+    type SerializeTupleStruct = Impossible<String, SerializationError>;
+    type SerializeTupleVariant = Impossible<String, SerializationError>;
+    type SerializeMap = Impossible<String, SerializationError>;
+    type SerializeStruct = Impossible<String, SerializationError>;
+    type SerializeStructVariant = Impossible<String, SerializationError>;
+
+    reject_values!(not_a_name; serialize_bool: bool, serialize_i8: i8, serialize_i16: i16,
+        serialize_i32: i32, serialize_i64: i64, serialize_u8: u8, serialize_u16: u16,
+        serialize_u32: u32, serialize_u64: u64, serialize_f32: f32, serialize_f64: f64,
+        serialize_bytes: &[u8]);
+    reject_compounds!(not_a_name);
+
+    fn serialize_char(self, value: char) -> Result<String, SerializationError> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_str(self, value: &str) -> Result<String, SerializationError> {
+        Ok(value.to_owned())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(
+        self,
+        value: &T,
+    ) -> Result<String, SerializationError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, SerializationError> {
+        value.serialize(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerializationError> {
+        Err(not_a_name())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, SerializationError> {
+        Err(not_a_name())
+    }
+}
@@ -24,10 +24,20 @@
 #[macro_use]
 extern crate log;
 
+#[cfg(feature = "arrow")]
+pub use arrow_array;
+#[cfg(feature = "arrow")]
+pub use arrow_schema;
 pub use r2d2;
 pub use serde_db;
 
+#[cfg(feature = "arrow")]
+mod arrow_impl;
+#[cfg(feature = "async")]
+mod async_impl;
 mod authentication;
+mod batch_result;
+mod cancel_handle;
 mod conn_core;
 mod connection;
 mod connection_manager;
@@ -35,28 +45,39 @@ mod hdb_error;
 mod hdb_response;
 mod hdb_return_value;
 mod impl_serde_db;
+mod named_parameters;
 mod prepared_statement;
 mod protocol;
+mod savepoint;
 mod xa_impl;
 
 pub mod code_examples;
 
-pub use crate::conn_core::connect_params::{ConnectParams, IntoConnectParams};
+pub use crate::conn_core::connect_params::{ConnectParams, FailoverOrder, IntoConnectParams};
 pub use crate::conn_core::connect_params_builder::ConnectParamsBuilder;
+pub use crate::conn_core::{IsolationLevel, TransactionState};
 
 #[cfg(feature = "tls")]
 pub use crate::conn_core::connect_params::ServerCerts;
 
+#[cfg(feature = "arrow")]
+pub use crate::arrow_impl::ArrowBatches;
+#[cfg(feature = "async")]
+pub use crate::async_impl::{AsyncConnection, AsyncPreparedStatement, AsyncResultSet};
+pub use crate::batch_result::BatchResult;
+pub use crate::cancel_handle::CancelHandle;
 pub use crate::connection::Connection;
 pub use crate::connection_manager::ConnectionManager;
-pub use crate::hdb_error::{HdbError, HdbResult};
+pub use crate::hdb_error::{ErrorContext, HdbError, HdbErrorKind, HdbResult};
 pub use crate::hdb_response::HdbResponse;
 pub use crate::hdb_return_value::HdbReturnValue;
 pub use crate::prepared_statement::PreparedStatement;
+pub use crate::protocol::parts::column_batch::{Column, ColumnBatch};
 pub use crate::protocol::parts::output_parameters::OutputParameters;
-pub use crate::protocol::parts::resultset::ResultSet;
+pub use crate::protocol::parts::resultset::{ResultSet, TypedIter};
 pub use crate::protocol::parts::row::Row;
 pub use crate::protocol::parts::server_error::{ServerError, Severity};
+pub use crate::savepoint::Savepoint;
 
 pub use crate::protocol::parts::execution_result::ExecutionResult;
 pub use crate::protocol::parts::parameter_descriptor::{
@@ -103,3 +124,26 @@ pub const DEFAULT_LOB_READ_LENGTH: u32 = 16_000_000;
 /// The value used at runtime can be changed with
 /// [Connection::set_lob_write_length()](struct.Connection.html#method.set_lob_write_length).
 pub const DEFAULT_LOB_WRITE_LENGTH: usize = 16_000_000;
+
+/// Maximal number of rows that are sent in a single roundtrip when a batch is executed;
+/// the constant's value is 100,000.
+///
+/// The value used at runtime can be changed with
+/// [Connection::set_max_batch_rows()](struct.Connection.html#method.set_max_batch_rows).
+pub const DEFAULT_MAX_BATCH_ROWS: usize = 100_000;
+
+/// Maximal number of bytes that are sent in a single roundtrip when a batch is executed;
+/// the constant's value is 16,000,000.
+///
+/// The value used at runtime can be changed with
+/// [Connection::set_max_batch_bytes()](struct.Connection.html#method.set_max_batch_bytes).
+pub const DEFAULT_MAX_BATCH_BYTES: usize = 16_000_000;
+
+/// Pause before the first repetition of a transaction that failed because of
+/// a deadlock or a serialization failure; the constant's value is 100 milliseconds.
+/// The pause is doubled with each further repetition.
+///
+/// The value used at runtime can be changed with
+/// [Connection::set_transaction_retry_backoff()](struct.Connection.html#method.set_transaction_retry_backoff).
+pub const DEFAULT_TRANSACTION_RETRY_BACKOFF: std::time::Duration =
+    std::time::Duration::from_millis(100);
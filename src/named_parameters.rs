@@ -0,0 +1,150 @@
+use crate::protocol::parts::parameter_descriptor::{ParameterDescriptors, ParameterDirection};
+use crate::{ErrorContext, HdbError, HdbResult};
+
+// Named parameters (`:name`) are a client-side feature:
+// the statement is sent to the server with positional placeholders (`?`),
+// and the names are kept, in the order of the placeholders,
+// for binding the parameter values by name.
+#[derive(Clone, Debug)]
+pub(crate) struct NamedParameters {
+    names: Vec<String>,
+}
+
+impl NamedParameters {
+    // Replaces the named parameters in the given statement with `?`.
+    //
+    // String literals, quoted identifiers and comments are left untouched.
+    // Returns None for the NamedParameters if the statement contains no named parameter.
+    // Mixing named and positional parameters is not supported.
+    pub(crate) fn rewrite(stmt: &str) -> HdbResult<(String, Option<NamedParameters>)> {
+        let mut sql = String::with_capacity(stmt.len());
+        let mut names = Vec::<String>::new();
+        let mut no_of_positionals = 0;
+
+        let mut chars = stmt.chars().peekable();
+        let mut prev: Option<char> = None;
+        while let Some(c) = chars.next() {
+            sql.push(c);
+            match c {
+                '\'' | '"' => {
+                    // a doubled quote inside a literal ends it and starts a new one,
+                    // which has the same effect as skipping it
+                    for c2 in &mut chars {
+                        sql.push(c2);
+                        if c2 == c {
+                            break;
+                        }
+                    }
+                }
+                '-' if chars.peek() == Some(&'-') => {
+                    for c2 in &mut chars {
+                        sql.push(c2);
+                        if c2 == '\n' {
+                            break;
+                        }
+                    }
+                }
+                '/' if chars.peek() == Some(&'*') => {
+                    sql.push(chars.next().unwrap(/* peeked */));
+                    let mut star = false;
+                    for c2 in &mut chars {
+                        sql.push(c2);
+                        if star && c2 == '/' {
+                            break;
+                        }
+                        star = c2 == '*';
+                    }
+                }
+                '?' => no_of_positionals += 1,
+                ':' if !matches!(prev, Some(p) if p == ':' || is_name_char(p))
+                    && matches!(chars.peek(), Some(c2) if c2.is_alphabetic() || *c2 == '_') =>
+                {
+                    let mut name = String::new();
+                    while let Some(c2) = chars.peek() {
+                        if !is_name_char(*c2) {
+                            break;
+                        }
+                        name.push(*c2);
+                        chars.next();
+                    }
+                    sql.pop();
+                    sql.push('?');
+                    names.push(name);
+                }
+                _ => {}
+            }
+            prev = Some(c);
+        }
+
+        if names.is_empty() {
+            Ok((sql, None))
+        } else if no_of_positionals > 0 {
+            Err(HdbError::Usage(
+                "Named and positional parameters cannot be mixed in a statement".to_owned(),
+            ))
+        } else {
+            Ok((sql, Some(NamedParameters { names })))
+        }
+    }
+
+    // The names of the parameters, in the order of the placeholders.
+    pub(crate) fn names(&self) -> &[String] {
+        &self.names
+    }
+}
+
+// Adds the name of the parameter to an error that refers to an input parameter
+// by its index; `names` are in the order of the parameter descriptors.
+pub(crate) fn with_parameter_name(
+    error: HdbError,
+    names: &[String],
+    descriptors: &ParameterDescriptors,
+) -> HdbError {
+    let o_idx = error.context().and_then(ErrorContext::parameter);
+    let o_name = o_idx.and_then(|idx| {
+        names
+            .iter()
+            .zip(descriptors.ref_inner())
+            .filter(|(_, descriptor)| descriptor.direction() != ParameterDirection::OUT)
+            .nth(idx)
+            .map(|(name, _)| name.as_str())
+    });
+    match (o_idx, o_name) {
+        (Some(idx), Some(name)) => error.with_context(ErrorContext::for_named_parameter(idx, name)),
+        _ => error,
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NamedParameters;
+
+    #[test]
+    fn test_rewrite() {
+        let (sql, o_named) =
+            NamedParameters::rewrite("select * from T where ID = :id and NAME = :name or ID = :id")
+                .unwrap();
+        assert_eq!(sql, "select * from T where ID = ? and NAME = ? or ID = ?");
+        assert_eq!(o_named.unwrap().names(), &["id", "name", "id"]);
+
+        let (sql, o_named) = NamedParameters::rewrite(
+            "select ':no', \":no\" /* :no */ from T -- :no\n where A = :a_1",
+        )
+        .unwrap();
+        assert_eq!(
+            sql,
+            "select ':no', \":no\" /* :no */ from T -- :no\n where A = ?"
+        );
+        assert_eq!(o_named.unwrap().names(), &["a_1"]);
+
+        let (sql, o_named) = NamedParameters::rewrite("select * from T where ID = ?").unwrap();
+        assert_eq!(sql, "select * from T where ID = ?");
+        assert!(o_named.is_none());
+
+        assert!(NamedParameters::rewrite("select * from T where ID = :id and A = ?").is_err());
+    }
+}
@@ -1,23 +1,38 @@
-use crate::conn_core::AmConnCore;
+#[cfg(feature = "alpha_routing")]
+use crate::conn_core::Routing;
+use crate::conn_core::{routing_target, AmConnCore, CachedStatement, RoutingTarget};
+use crate::connection::non_zero;
+use crate::hdb_response::InternalReturnValue;
+use crate::named_parameters::{with_parameter_name, NamedParameters};
 use crate::protocol::argument::Argument;
 use crate::protocol::part::Part;
 use crate::protocol::partkind::PartKind;
+use crate::protocol::parts::execution_result::ExecutionResult;
 use crate::protocol::parts::hdb_value::HdbValue;
 use crate::protocol::parts::lob_flags::LobFlags;
 use crate::protocol::parts::parameter_descriptor::{ParameterDescriptor, ParameterDescriptors};
 use crate::protocol::parts::parameter_rows::ParameterRows;
+use crate::protocol::parts::partiton_information::PartitionInformation;
+use crate::protocol::parts::resultset::FetchOverrides;
 use crate::protocol::parts::resultset_metadata::ResultSetMetadata;
 use crate::protocol::parts::type_id::TypeId;
+use crate::protocol::reply::Reply;
 use crate::protocol::request::{Request, HOLD_CURSORS_OVER_COMMIT};
 use crate::protocol::request_type::RequestType;
+#[cfg(feature = "async")]
+use crate::types_impl::lob::write_lob_async;
 use crate::types_impl::lob::LobWriter;
-use crate::{HdbError, HdbResponse, HdbResult};
+use crate::{BatchResult, HdbError, HdbResponse, HdbResult, HdbReturnValue};
 
 use serde;
 use serde_db::ser::SerializationError;
 
+#[cfg(feature = "alpha_routing")]
+use std::collections::HashMap;
 use std::io::Write;
 use std::mem;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Allows injection-safe SQL execution and repeated calls of the same statement
 /// with different parameters with as few roundtrips as possible.
@@ -67,6 +82,17 @@ use std::mem;
 /// If the database e.g. requests an INT, you can also send a String representation of the
 /// number, by using `HdbValue::STRING("1088")`, instead of the binary INT representation
 /// `HdbValue::INT(1088)`.
+///
+/// ## Named parameters
+///
+/// Statements that are prepared with
+/// [`Connection::prepare_named()`](struct.Connection.html#method.prepare_named)
+/// can use named parameters (`:name`) instead of `?`.
+/// The input of [`execute()`](#method.execute) and [`add_batch()`](#method.add_batch)
+/// must then be a struct or a map, and its fields are bound to the parameters by name,
+/// independent of their order.
+/// [`execute_row()`](#method.execute_row) and
+/// [`add_row_to_batch()`](#method.add_row_to_batch) still bind the values by position.
 
 #[derive(Debug)]
 pub struct PreparedStatement {
@@ -75,7 +101,17 @@ pub struct PreparedStatement {
     o_descriptors: Option<ParameterDescriptors>,
     batch: ParameterRows<'static>,
     o_rs_md: Option<ResultSetMetadata>,
-    _o_table_location: Option<Vec<i32>>,
+    #[cfg_attr(not(feature = "alpha_routing"), allow(dead_code))]
+    o_routing: Option<RoutingTarget>, // where the executions can be routed to
+    o_query_timeout: Option<Duration>,
+    fetch_overrides: FetchOverrides,
+    o_lob_write_length: Option<usize>,
+    o_named_parameters: Option<NamedParameters>,
+    o_cached: Option<Arc<CachedStatement>>, // the entry of the statement cache, if any
+    session_id: i64,                        // the session in which the statement id is valid
+    stmt: String,
+    #[cfg(feature = "alpha_routing")]
+    routed: HashMap<i32, PreparedStatement>, // same statement, prepared on routing connections
 }
 
 impl<'a> PreparedStatement {
@@ -129,7 +165,10 @@ impl<'a> PreparedStatement {
         if let Some(ref descriptors) = self.o_descriptors {
             if descriptors.has_in() {
                 let mut par_rows = ParameterRows::new();
-                par_rows.push(input, &descriptors)?;
+                match self.o_named_parameters {
+                    Some(ref named) => par_rows.push_named(input, named.names(), descriptors)?,
+                    None => par_rows.push(input, descriptors)?,
+                }
                 return self.execute_parameter_rows(Some(par_rows));
             }
         }
@@ -181,87 +220,48 @@ impl<'a> PreparedStatement {
     /// After this call, the data from the readers are transferred to the database in additional
     /// roundtrips.
     pub fn execute_row(&'a mut self, hdb_values: Vec<HdbValue<'a>>) -> HdbResult<HdbResponse> {
-        if let Some(ref descriptors) = self.o_descriptors {
-            if descriptors.has_in() {
-                let mut request = Request::new(RequestType::Execute, HOLD_CURSORS_OVER_COMMIT);
-
-                request.push(Part::new(
-                    PartKind::StatementId,
-                    Argument::StatementId(self.statement_id),
-                ));
-
-                // If readers were provided, pick them out and replace them with None
-                let mut readers: Vec<(HdbValue, TypeId)> = vec![];
-                let hdb_values = hdb_values
-                    .into_iter()
-                    .zip(descriptors.iter_in())
-                    .map(|(v, d)| {
-                        if let HdbValue::LOBSTREAM(Some(_)) = v {
-                            readers.push((v, d.type_id()));
-                            HdbValue::LOBSTREAM(None)
-                        } else {
-                            v
-                        }
-                    })
-                    .collect();
-
-                let mut par_rows = ParameterRows::new();
-                par_rows.push_hdb_values(hdb_values, &descriptors)?;
-                request.push(Part::new(
-                    PartKind::Parameters,
-                    Argument::Parameters(par_rows),
-                ));
-
-                if self
-                    .am_conn_core
-                    .lock()?
-                    .connect_options()
-                    .get_implicit_lob_streaming()
-                    .unwrap_or(false)
-                {
-                    request.push(Part::new(
-                        PartKind::LobFlags,
-                        Argument::LobFlags(LobFlags::for_implicit_streaming()),
-                    ));
+        #[cfg(feature = "alpha_routing")]
+        {
+            if let Some(volume_id) = self.routing_volume(std::iter::once(hdb_values.as_slice())) {
+                if self.routed_statement(volume_id)?.is_some() {
+                    if let Some(routed) = self.routed.get_mut(&volume_id) {
+                        return routed.execute_row(hdb_values);
+                    }
                 }
+            }
+        }
+        self.renew_if_stale()?;
+        if !self.has_in_parameters() {
+            return self.execute_parameter_rows(None);
+        }
+        let (request, readers) = self.execute_row_request(hdb_values)?;
+        let mut main_reply = self
+            .am_conn_core
+            .full_send(
+                request,
+                self.o_rs_md.as_ref(),
+                self.o_descriptors.as_ref(),
+                &mut None,
+            )
+            .map_err(|e| self.add_parameter_name(e))?;
 
-                let mut main_reply = self.am_conn_core.full_send(
-                    request,
-                    self.o_rs_md.as_ref(),
-                    self.o_descriptors.as_ref(),
-                    &mut None,
-                )?;
-
-                if let Some(Argument::WriteLobReply(wlr)) =
-                    main_reply.extract_first_arg_of_type(PartKind::WriteLobReply)
-                {
-                    let locator_ids = wlr.into_locator_ids();
-                    if locator_ids.len() != readers.len() {
-                        return Err(HdbError::Usage(format!(
-                            "The number of provided readers ({}) does not match \
-                             the number of required readers ({})",
-                            readers.len(),
-                            locator_ids.len()
-                        )));
-                    }
-                    for (locator_id, (reader, type_id)) in locator_ids.into_iter().zip(readers) {
-                        debug!("writing content to locator with id {:?}", locator_id);
-                        if let HdbValue::LOBSTREAM(Some(reader)) = reader {
-                            let mut reader = reader.lock().unwrap();
-                            let mut writer =
-                                LobWriter::new(locator_id, type_id, self.am_conn_core.clone())?;
-                            std::io::copy(&mut *reader, &mut writer)?;
-                            writer.flush()?;
-                        }
-                    }
+        if let Some(locator_ids) = extract_locator_ids(&mut main_reply, readers.len())? {
+            for (locator_id, (reader, type_id)) in locator_ids.into_iter().zip(readers) {
+                debug!("writing content to locator with id {:?}", locator_id);
+                if let HdbValue::LOBSTREAM(Some(reader)) = reader {
+                    let mut reader = reader.lock().unwrap();
+                    let mut writer = LobWriter::new(
+                        locator_id,
+                        type_id,
+                        self.lob_write_length()?,
+                        self.am_conn_core.clone(),
+                    )?;
+                    std::io::copy(&mut *reader, &mut writer)?;
+                    writer.flush()?;
                 }
-                main_reply.into_hdbresponse(&mut (self.am_conn_core))
-            } else {
-                self.execute_parameter_rows(None)
             }
-        } else {
-            self.execute_parameter_rows(None)
         }
+        main_reply.into_hdbresponse_with(&mut (self.am_conn_core), self.fetch_overrides)
     }
 
     /// Converts the input into a row of parameters and adds it to the batch of this
@@ -270,12 +270,16 @@ impl<'a> PreparedStatement {
         trace!("PreparedStatement::add_batch()");
         if let Some(ref descriptors) = self.o_descriptors {
             if descriptors.has_in() {
-                self.batch.push(input, &descriptors)?;
+                match self.o_named_parameters {
+                    Some(ref named) => self.batch.push_named(input, named.names(), descriptors)?,
+                    None => self.batch.push(input, descriptors)?,
+                }
                 return Ok(());
             }
         }
         Err(HdbError::Serialization(
             SerializationError::StructuralMismatch("no metadata in add_batch()"),
+            None,
         ))
     }
 
@@ -295,6 +299,7 @@ impl<'a> PreparedStatement {
         }
         Err(HdbError::Serialization(
             SerializationError::StructuralMismatch("no metadata in add_row_to_batch()"),
+            None,
         ))
     }
 
@@ -304,18 +309,86 @@ impl<'a> PreparedStatement {
     /// If the statement does not need input and the batch is empty,
     /// a single execution is triggered.
     pub fn execute_batch(&mut self) -> HdbResult<HdbResponse> {
-        if self.batch.is_empty() {
-            if let Some(ref descriptors) = self.o_descriptors {
-                if descriptors.has_in() {
-                    return Err(HdbError::Usage(
-                        "The batch is empty and cannot be executed".to_string(),
-                    ));
-                }
-            }
-        }
+        self.check_batch()?;
         let mut rows2 = ParameterRows::new();
         mem::swap(&mut self.batch, &mut rows2);
-        self.execute_parameter_rows(Some(rows2))
+        self.execute_batch_rows(rows2)
+    }
+
+    /// Executes the statement with the collected batch, clears the batch,
+    /// and reports the outcome for each row.
+    ///
+    /// Unlike [`execute_batch()`](#method.execute_batch), which fails with
+    /// `HdbError::MixedResults` if some rows fail, this method returns a `BatchResult`
+    /// that maps the failures to the rows of the batch, and keeps the parameter values
+    /// of the failed rows.
+    /// Errors that concern the statement as a whole are still returned as `Err`.
+    ///
+    /// Note that the rows of the batch are copied, since they are needed
+    /// in case of failures.
+    pub fn execute_batch_detailed(&mut self) -> HdbResult<BatchResult> {
+        self.check_batch()?;
+        let rows = mem::replace(&mut self.batch, ParameterRows::new());
+        let result = self.execute_batch_rows(rows.try_clone()?);
+        into_batch_result(result, rows)
+    }
+
+    /// Executes the rows of a `BatchResult` again that failed without an error of
+    /// their own, i.e., that were not processed because an earlier row failed,
+    /// and returns the `BatchResult` with their new outcome.
+    ///
+    /// The rows that failed with a server error are not executed again;
+    /// they can be taken with
+    /// [`BatchResult::into_failed_rows()`](struct.BatchResult.html#method.into_failed_rows).
+    /// The batch of the statement is not affected.
+    pub fn execute_failed_rows(&mut self, mut batch_result: BatchResult) -> HdbResult<BatchResult> {
+        let (indices, rows) = self.unprocessed_rows(&mut batch_result)?;
+        if indices.is_empty() {
+            return Ok(batch_result);
+        }
+        let result = self.execute_batch_rows(rows.try_clone()?);
+        batch_result.update(&indices, into_batch_result(result, rows)?);
+        Ok(batch_result)
+    }
+
+    /// Overrides the connection's query timeout for the executions of this statement.
+    ///
+    /// `Duration::from_secs(0)` switches the query timeout off for this statement.
+    /// See [`Connection::set_query_timeout()`](struct.Connection.html#method.set_query_timeout).
+    pub fn set_query_timeout(&mut self, query_timeout: Duration) {
+        self.o_query_timeout = Some(query_timeout);
+    }
+
+    /// Overrides the connection's fetch size for the resultsets of this statement.
+    ///
+    /// The fetch size is captured when the statement is executed, and is used for
+    /// fetching the rows of the resultset.
+    /// See [`Connection::set_fetch_size()`](struct.Connection.html#method.set_fetch_size).
+    pub fn set_fetch_size(&mut self, fetch_size: u32) {
+        self.fetch_overrides.o_fetch_size = Some(fetch_size);
+    }
+
+    /// Overrides the connection's lob read length for the LOBs in the resultsets
+    /// of this statement.
+    ///
+    /// See [`Connection::set_lob_read_length()`](struct.Connection.html#method.set_lob_read_length).
+    pub fn set_lob_read_length(&mut self, lob_read_length: u32) {
+        self.fetch_overrides.o_lob_read_length = Some(lob_read_length);
+    }
+
+    /// Overrides the connection's lob write length for the LOBs that are streamed
+    /// to the database with [`execute_row()`](#method.execute_row).
+    ///
+    /// See [`Connection::set_lob_write_length()`](struct.Connection.html#method.set_lob_write_length).
+    pub fn set_lob_write_length(&mut self, lob_write_length: usize) {
+        self.o_lob_write_length = Some(lob_write_length);
+    }
+
+    fn lob_write_length(&self) -> HdbResult<usize> {
+        match self.o_lob_write_length {
+            Some(lob_write_length) => Ok(lob_write_length),
+            None => Ok(self.am_conn_core.lock()?.get_lob_write_length()),
+        }
     }
 
     /// Descriptors of all parameters of the prepared statement (in, out, inout), if any.
@@ -325,40 +398,433 @@ impl<'a> PreparedStatement {
             .map(|descr| descr.ref_inner().as_slice())
     }
 
+    // Executes the batch, in several roundtrips if it exceeds the configured limits.
+    fn execute_batch_rows(&mut self, rows: ParameterRows<'static>) -> HdbResult<HdbResponse> {
+        let mut chunks = self.split_batch(rows)?;
+        if chunks.len() == 1 {
+            return self.execute_parameter_rows(chunks.pop());
+        }
+        debug!("executing the batch in {} roundtrips", chunks.len());
+        let mut results = Vec::<ExecutionResult>::new();
+        let mut chunks = chunks.into_iter();
+        while let Some(chunk) = chunks.next() {
+            let chunk_len = chunk.count();
+            let chunk_result = self.execute_parameter_rows(Some(chunk));
+            add_chunk_results(&mut results, chunk_result, chunk_len, chunks.by_ref())?;
+        }
+        HdbResponse::rows_affected(vec![InternalReturnValue::AffectedRows(results)])
+    }
+
+    // Splits the batch into chunks that respect the connection's batch limits
+    // and the server's capabilities.
+    fn split_batch(&self, rows: ParameterRows<'static>) -> HdbResult<Vec<ParameterRows<'static>>> {
+        let descriptors = match self.o_descriptors {
+            Some(ref descriptors) => descriptors,
+            None => return Ok(vec![rows]),
+        };
+        let (mut max_rows, max_bytes, large_bulk_ok, large_no_of_pars_ok) = {
+            let conn_core = self.am_conn_core.lock()?;
+            let connect_options = conn_core.connect_options();
+            (
+                conn_core.get_max_batch_rows(),
+                conn_core.get_max_batch_bytes(),
+                connect_options
+                    .get_supports_large_bulk_operations()
+                    .unwrap_or(false),
+                connect_options
+                    .get_large_number_of_parameters_ok()
+                    .unwrap_or(false),
+            )
+        };
+        let small_limit = i16::MAX as usize;
+        if !large_bulk_ok {
+            max_rows = std::cmp::min(max_rows, small_limit);
+        }
+        let no_of_in_pars = descriptors.iter_in().count();
+        if !large_no_of_pars_ok && no_of_in_pars > 0 {
+            max_rows = std::cmp::min(max_rows, std::cmp::max(small_limit / no_of_in_pars, 1));
+        }
+        rows.split(descriptors, max_rows, max_bytes)
+    }
+
     fn execute_parameter_rows(&mut self, o_rows: Option<ParameterRows>) -> HdbResult<HdbResponse> {
         trace!("PreparedStatement::execute_parameter_rows()");
+        #[cfg(feature = "alpha_routing")]
+        {
+            let o_volume_id = match o_rows {
+                Some(ref rows) => self.routing_volume(rows.iter()),
+                None => self.routing_volume(std::iter::empty()),
+            };
+            if let Some(volume_id) = o_volume_id {
+                if let Some(routed) = self.routed_statement(volume_id)? {
+                    return routed.execute_parameter_rows(o_rows);
+                }
+            }
+        }
+        let o_rows = if self.renew_if_stale()? {
+            self.rebind(o_rows)?
+        } else {
+            o_rows
+        };
+        let o_retry_rows = copy_for_retry(&o_rows);
+        let request = self.execute_request(o_rows)?;
+        let reply = match self.am_conn_core.full_send(
+            request,
+            self.o_rs_md.as_ref(),
+            self.o_descriptors.as_ref(),
+            &mut None,
+        ) {
+            Err(ref e) if e.is_invalid_statement_id() && o_retry_rows.is_some() => {
+                debug!("server does not know the statement id, preparing the statement again");
+                self.remove_from_cache()?;
+                self.replace_with(PreparedStatement::try_new(
+                    self.am_conn_core.clone(),
+                    &self.stmt,
+                )?);
+                let o_rows = self.rebind(o_retry_rows.flatten())?;
+                let request = self.execute_request(o_rows)?;
+                self.am_conn_core.full_send(
+                    request,
+                    self.o_rs_md.as_ref(),
+                    self.o_descriptors.as_ref(),
+                    &mut None,
+                )
+            }
+            result => result,
+        }
+        .map_err(|e| self.add_parameter_name(e))?;
+        reply.into_hdbresponse_with(&mut (self.am_conn_core), self.fetch_overrides)
+    }
+
+    // Removes the statement, whose statement id the server does not know anymore,
+    // from the statement cache.
+    fn remove_from_cache(&mut self) -> HdbResult<()> {
+        if self.o_cached.is_some() {
+            let to_drop = self
+                .am_conn_core
+                .lock()?
+                .statement_cache_mut()
+                .remove(&self.stmt);
+            drop_statement_ids(&mut self.am_conn_core, to_drop);
+        }
+        Ok(())
+    }
+
+    // Errors in the conversion of parameter values refer to the parameter by its index.
+    fn add_parameter_name(&self, error: HdbError) -> HdbError {
+        match (&self.o_named_parameters, &self.o_descriptors) {
+            (Some(named), Some(descriptors)) => {
+                with_parameter_name(error, named.names(), descriptors)
+            }
+            _ => error,
+        }
+    }
+
+    // After a reconnect, the server does not know the statement id anymore.
+    fn is_stale(&self) -> HdbResult<bool> {
+        Ok(self.am_conn_core.lock()?.session_id() != self.session_id)
+    }
+
+    // Prepares the statement again if it was prepared in a former session;
+    // returns true in this case.
+    fn renew_if_stale(&mut self) -> HdbResult<bool> {
+        if !self.is_stale()? {
+            return Ok(false);
+        }
+        debug!("statement was prepared in a former session, preparing it again");
+        self.replace_with(PreparedStatement::try_new(
+            self.am_conn_core.clone(),
+            &self.stmt,
+        )?);
+        Ok(true)
+    }
+
+    // Binds rows that were built for the former parameter descriptors to the current ones.
+    fn rebind<'b>(
+        &self,
+        o_rows: Option<ParameterRows<'b>>,
+    ) -> HdbResult<Option<ParameterRows<'b>>> {
+        match (o_rows, &self.o_descriptors) {
+            (None, _) => Ok(None),
+            (Some(rows), Some(descriptors)) => Ok(Some(rows.rebind(descriptors)?)),
+            (Some(_), None) => Err(HdbError::usage_(
+                "the statement, prepared again, has no parameters anymore",
+            )),
+        }
+    }
+
+    // Takes over the server-side statement of a freshly prepared statement;
+    // the stale one is given to the fresh statement, which releases it when it is dropped.
+    fn replace_with(&mut self, mut fresh: PreparedStatement) {
+        mem::swap(&mut self.statement_id, &mut fresh.statement_id);
+        mem::swap(&mut self.session_id, &mut fresh.session_id);
+        mem::swap(&mut self.o_descriptors, &mut fresh.o_descriptors);
+        mem::swap(&mut self.o_rs_md, &mut fresh.o_rs_md);
+        mem::swap(&mut self.o_routing, &mut fresh.o_routing);
+        mem::swap(&mut self.o_cached, &mut fresh.o_cached);
+    }
+
+    // The volume to which an execution with the given rows of input values can be routed.
+    #[cfg(feature = "alpha_routing")]
+    fn routing_volume<'v, I>(&self, rows: I) -> Option<i32>
+    where
+        I: Iterator<Item = &'v [HdbValue<'v>]>,
+    {
+        match (&self.o_routing, &self.o_descriptors) {
+            (Some(RoutingTarget::Volume(volume_id)), _) => Some(*volume_id),
+            (Some(target), Some(descriptors)) => target.volume_of(rows, descriptors),
+            _ => None,
+        }
+    }
+
+    // If the execution affects only data that are located on another index server,
+    // returns the statement prepared on a connection to that index server.
+    // If the routing connection cannot be opened or the statement cannot be prepared there,
+    // the statement is executed on this connection.
+    #[cfg(feature = "alpha_routing")]
+    fn routed_statement(&mut self, volume_id: i32) -> HdbResult<Option<&mut PreparedStatement>> {
+        let routing = self.am_conn_core.lock()?.routing(volume_id);
+        let am_conn_core = match routing {
+            Routing::Local => return Ok(None),
+            Routing::Connected(am_conn_core) => am_conn_core,
+            Routing::Unconnected(setup) => {
+                // the primary connection is not locked while the routing connection is opened
+                let addr = setup.params.addr().to_string();
+                match setup.open() {
+                    Ok(am_conn_core) => {
+                        debug!(
+                            "opened routing connection to {} for volume {}",
+                            addr, volume_id
+                        );
+                        self.am_conn_core
+                            .lock()?
+                            .add_routing_connection(volume_id, am_conn_core)
+                    }
+                    Err(e) => {
+                        warn!(
+                            "cannot open routing connection to {}, statement is not routed: {}",
+                            addr, e
+                        );
+                        return Ok(None);
+                    }
+                }
+            }
+        };
+        if let Some(routed) = self.routed.get(&volume_id) {
+            if !routed.am_conn_core.is_same(&am_conn_core) {
+                // the routing connection was replaced
+                self.routed.remove(&volume_id);
+            }
+        }
+        if !self.routed.contains_key(&volume_id) {
+            debug!("routing statement to volume {}", volume_id);
+            match PreparedStatement::try_new(am_conn_core, &self.stmt) {
+                Ok(mut routed) => {
+                    routed.o_named_parameters = self.o_named_parameters.clone();
+                    self.routed.insert(volume_id, routed);
+                }
+                Err(e) => {
+                    warn!(
+                        "cannot prepare routed statement, statement is not routed: {}",
+                        e
+                    );
+                    return Ok(None);
+                }
+            }
+        }
+        let query_timeout = self.query_timeout()?.unwrap_or_default();
+        let fetch_overrides = self.fetch_overrides;
+        let o_lob_write_length = self.o_lob_write_length;
+        let mut o_routed = self.routed.get_mut(&volume_id);
+        if let Some(ref mut routed) = o_routed {
+            routed.o_query_timeout = Some(query_timeout);
+            routed.fetch_overrides = fetch_overrides;
+            routed.o_lob_write_length = o_lob_write_length;
+        }
+        Ok(o_routed)
+    }
+
+    // Takes the rows of the BatchResult that were not processed.
+    fn unprocessed_rows(
+        &self,
+        batch_result: &mut BatchResult,
+    ) -> HdbResult<(Vec<usize>, ParameterRows<'static>)> {
+        let (indices, values) = batch_result.take_unprocessed_rows();
+        let mut rows = ParameterRows::new();
+        if !values.is_empty() {
+            let descriptors = self.o_descriptors.as_ref().ok_or_else(|| {
+                HdbError::usage_("the statement has no parameters to execute again")
+            })?;
+            for row in values {
+                rows.push_hdb_values(row, descriptors)?;
+            }
+        }
+        Ok((indices, rows))
+    }
+
+    fn check_batch(&self) -> HdbResult<()> {
+        if self.batch.is_empty() && self.has_in_parameters() {
+            Err(HdbError::Usage(
+                "The batch is empty and cannot be executed".to_string(),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn has_in_parameters(&self) -> bool {
+        match self.o_descriptors {
+            Some(ref descriptors) => descriptors.has_in(),
+            None => false,
+        }
+    }
+
+    // The statement's own query timeout takes precedence over that of the connection.
+    fn query_timeout(&self) -> HdbResult<Option<Duration>> {
+        Ok(match self.o_query_timeout {
+            Some(query_timeout) => non_zero(query_timeout),
+            None => self.am_conn_core.lock()?.get_query_timeout(),
+        })
+    }
+
+    fn execute_request<'b>(&'b self, o_rows: Option<ParameterRows<'b>>) -> HdbResult<Request<'b>> {
         let mut request = Request::new(RequestType::Execute, HOLD_CURSORS_OVER_COMMIT);
+        request.set_statement(&self.stmt);
         request.push(Part::new(
             PartKind::StatementId,
             Argument::StatementId(self.statement_id),
         ));
+        if let Some(fetch_size) = self.fetch_overrides.o_fetch_size {
+            request.push(Part::new(
+                PartKind::FetchSize,
+                Argument::FetchSize(fetch_size),
+            ));
+        }
         if let Some(rows) = o_rows {
             request.push(Part::new(PartKind::Parameters, Argument::Parameters(rows)));
         }
+        if let Some(query_timeout) = self.query_timeout()? {
+            request.set_query_timeout(query_timeout);
+        }
+        Ok(request)
+    }
 
-        let reply = self.am_conn_core.full_send(
-            request,
-            self.o_rs_md.as_ref(),
-            self.o_descriptors.as_ref(),
-            &mut None,
-        )?;
-        reply.into_hdbresponse(&mut (self.am_conn_core))
+    // Builds the request for execute_row(), where the LOBSTREAM values with readers are
+    // replaced by placeholders; the readers are returned together with their type-ids.
+    #[allow(clippy::type_complexity)]
+    fn execute_row_request<'b>(
+        &'b self,
+        hdb_values: Vec<HdbValue<'b>>,
+    ) -> HdbResult<(Request<'b>, Vec<(HdbValue<'b>, TypeId)>)> {
+        let descriptors = self
+            .o_descriptors
+            .as_ref()
+            .ok_or_else(|| HdbError::impl_("execute_row_request(): no parameter descriptors"))?;
+        let mut request = Request::new(RequestType::Execute, HOLD_CURSORS_OVER_COMMIT);
+        request.set_statement(&self.stmt);
+        request.push(Part::new(
+            PartKind::StatementId,
+            Argument::StatementId(self.statement_id),
+        ));
+        if let Some(fetch_size) = self.fetch_overrides.o_fetch_size {
+            request.push(Part::new(
+                PartKind::FetchSize,
+                Argument::FetchSize(fetch_size),
+            ));
+        }
+
+        // If readers were provided, pick them out and replace them with None
+        let mut readers: Vec<(HdbValue, TypeId)> = vec![];
+        let hdb_values = hdb_values
+            .into_iter()
+            .zip(descriptors.iter_in())
+            .map(|(v, d)| {
+                if let HdbValue::LOBSTREAM(Some(_)) = v {
+                    readers.push((v, d.type_id()));
+                    HdbValue::LOBSTREAM(None)
+                } else {
+                    v
+                }
+            })
+            .collect();
+
+        let mut par_rows = ParameterRows::new();
+        par_rows.push_hdb_values(hdb_values, descriptors)?;
+        request.push(Part::new(
+            PartKind::Parameters,
+            Argument::Parameters(par_rows),
+        ));
+
+        if self
+            .am_conn_core
+            .lock()?
+            .connect_options()
+            .get_implicit_lob_streaming()
+            .unwrap_or(false)
+        {
+            request.push(Part::new(
+                PartKind::LobFlags,
+                Argument::LobFlags(LobFlags::for_implicit_streaming()),
+            ));
+        }
+        if let Some(query_timeout) = self.query_timeout()? {
+            request.set_query_timeout(query_timeout);
+        }
+        Ok((request, readers))
     }
 
-    // Prepare a statement.
+    // Prepare a statement, or take it from the statement cache.
     pub(crate) fn try_new(
         mut am_conn_core: AmConnCore,
         stmt: &str,
     ) -> HdbResult<PreparedStatement> {
+        let (session_id, o_cached) = {
+            let mut conn_core = am_conn_core.lock()?;
+            (
+                conn_core.session_id(),
+                conn_core.statement_cache_mut().get(stmt),
+            )
+        };
+        if let Some(cached) = o_cached {
+            return Ok(PreparedStatement::from_cached(
+                am_conn_core,
+                session_id,
+                cached,
+            ));
+        }
+        let reply = am_conn_core.send(PreparedStatement::prepare_request(stmt))?;
+        let mut prepared_statement = PreparedStatement::from_reply(am_conn_core, stmt, reply)?;
+        prepared_statement.add_to_cache(stmt)?;
+        Ok(prepared_statement)
+    }
+
+    // Prepare a statement with named parameters.
+    pub(crate) fn try_new_named(
+        am_conn_core: AmConnCore,
+        stmt: &str,
+    ) -> HdbResult<PreparedStatement> {
+        let (stmt, o_named_parameters) = NamedParameters::rewrite(stmt)?;
+        let mut prepared_statement = PreparedStatement::try_new(am_conn_core, &stmt)?;
+        prepared_statement.o_named_parameters = o_named_parameters;
+        Ok(prepared_statement)
+    }
+
+    fn prepare_request(stmt: &str) -> Request<'_> {
         let mut request = Request::new(RequestType::Prepare, HOLD_CURSORS_OVER_COMMIT);
         request.push(Part::new(PartKind::Command, Argument::Command(stmt)));
+        request
+    }
 
-        let mut reply = am_conn_core.send(request)?;
-
+    fn from_reply(
+        am_conn_core: AmConnCore,
+        stmt: &str,
+        mut reply: Reply,
+    ) -> HdbResult<PreparedStatement> {
         // ParameterMetadata, ResultSetMetadata
         // StatementContext, StatementId,
-        // TableLocation, TransactionFlags,
+        // TableLocation, PartitionInformation, TransactionFlags,
         let mut o_table_location: Option<Vec<i32>> = None;
+        let mut o_partition_info: Option<PartitionInformation> = None;
         let mut o_stmt_id: Option<u64> = None;
         let mut o_descriptors: Option<ParameterDescriptors> = None;
         let mut o_rs_md: Option<ResultSetMetadata> = None;
@@ -378,6 +844,10 @@ impl<'a> PreparedStatement {
                 Some(Argument::TableLocation(vec_i)) => {
                     o_table_location = Some(vec_i);
                 }
+                Some(Argument::PartitionInformation(partition_info)) => {
+                    debug!("prepare(): table is partitioned: {:?}", partition_info);
+                    o_partition_info = Some(partition_info);
+                }
                 Some(Argument::ResultSetMetadata(rs_md)) => {
                     o_rs_md = Some(rs_md);
                 }
@@ -395,6 +865,7 @@ impl<'a> PreparedStatement {
             None => {
                 return Err(HdbError::Impl(
                     "PreparedStatement needs a StatementId".to_owned(),
+                    None,
                 ));
             }
         };
@@ -404,27 +875,404 @@ impl<'a> PreparedStatement {
             o_descriptors
         );
 
+        let session_id = am_conn_core.lock()?.session_id();
         Ok(PreparedStatement {
             am_conn_core,
             statement_id,
             batch: ParameterRows::new(),
             o_descriptors,
             o_rs_md,
-            _o_table_location: o_table_location,
+            o_routing: routing_target(o_table_location.as_deref(), o_partition_info),
+            o_query_timeout: None,
+            fetch_overrides: Default::default(),
+            o_lob_write_length: None,
+            o_named_parameters: None,
+            o_cached: None,
+            session_id,
+            stmt: stmt.to_string(),
+            #[cfg(feature = "alpha_routing")]
+            routed: HashMap::new(),
         })
     }
+
+    fn from_cached(
+        am_conn_core: AmConnCore,
+        session_id: i64,
+        cached: Arc<CachedStatement>,
+    ) -> PreparedStatement {
+        trace!("PreparedStatement taken from the statement cache");
+        PreparedStatement {
+            am_conn_core,
+            session_id,
+            statement_id: cached.statement_id,
+            batch: ParameterRows::new(),
+            o_descriptors: cached.o_descriptors.clone(),
+            o_rs_md: cached.o_rs_md.clone(),
+            o_routing: cached.o_routing.clone(),
+            o_query_timeout: None,
+            fetch_overrides: Default::default(),
+            o_lob_write_length: None,
+            o_named_parameters: None,
+            stmt: cached.sql.clone(),
+            o_cached: Some(cached),
+            #[cfg(feature = "alpha_routing")]
+            routed: HashMap::new(),
+        }
+    }
+
+    // Adds the statement to the connection's statement cache, if that is switched on.
+    fn add_to_cache(&mut self, stmt: &str) -> HdbResult<()> {
+        let to_drop = {
+            let mut conn_core = self.am_conn_core.lock()?;
+            if conn_core.statement_cache().capacity() == 0 {
+                return Ok(());
+            }
+            let (o_cached, to_drop) = conn_core.statement_cache_mut().insert(CachedStatement {
+                sql: stmt.to_string(),
+                statement_id: self.statement_id,
+                o_descriptors: self.o_descriptors.clone(),
+                o_rs_md: self.o_rs_md.clone(),
+                o_routing: self.o_routing.clone(),
+            });
+            self.o_cached = o_cached;
+            to_drop
+        };
+        drop_statement_ids(&mut self.am_conn_core, to_drop);
+        Ok(())
+    }
 }
 
-impl Drop for PreparedStatement {
-    /// Frees all server-side ressources that belong to this prepared statement.
-    fn drop(&mut self) {
+#[cfg(feature = "async")]
+impl<'a> PreparedStatement {
+    pub(crate) async fn try_new_async(
+        am_conn_core: AmConnCore,
+        stmt: &str,
+    ) -> HdbResult<PreparedStatement> {
+        let (session_id, o_cached) = {
+            let mut conn_core = am_conn_core.lock()?;
+            (
+                conn_core.session_id(),
+                conn_core.statement_cache_mut().get(stmt),
+            )
+        };
+        if let Some(cached) = o_cached {
+            return Ok(PreparedStatement::from_cached(
+                am_conn_core,
+                session_id,
+                cached,
+            ));
+        }
+        let reply = am_conn_core
+            .send_async(PreparedStatement::prepare_request(stmt))
+            .await?;
+        let mut prepared_statement = PreparedStatement::from_reply(am_conn_core, stmt, reply)?;
+        prepared_statement.add_to_cache(stmt)?;
+        Ok(prepared_statement)
+    }
+
+    pub(crate) async fn try_new_named_async(
+        am_conn_core: AmConnCore,
+        stmt: &str,
+    ) -> HdbResult<PreparedStatement> {
+        let (stmt, o_named_parameters) = NamedParameters::rewrite(stmt)?;
+        let mut prepared_statement = PreparedStatement::try_new_async(am_conn_core, &stmt).await?;
+        prepared_statement.o_named_parameters = o_named_parameters;
+        Ok(prepared_statement)
+    }
+
+    pub(crate) async fn execute_async<T: serde::ser::Serialize>(
+        &mut self,
+        input: &T,
+    ) -> HdbResult<HdbResponse> {
+        trace!("PreparedStatement::execute_async()");
+        if self.has_in_parameters() {
+            let mut par_rows = ParameterRows::new();
+            if let Some(ref descriptors) = self.o_descriptors {
+                match self.o_named_parameters {
+                    Some(ref named) => par_rows.push_named(input, named.names(), descriptors)?,
+                    None => par_rows.push(input, descriptors)?,
+                }
+            }
+            self.execute_parameter_rows_async(Some(par_rows)).await
+        } else {
+            self.execute_parameter_rows_async(None).await
+        }
+    }
+
+    pub(crate) async fn execute_row_async(
+        &'a mut self,
+        hdb_values: Vec<HdbValue<'a>>,
+    ) -> HdbResult<HdbResponse> {
+        self.renew_if_stale_async().await?;
+        if !self.has_in_parameters() {
+            return self.execute_parameter_rows_async(None).await;
+        }
+        let (request, readers) = self.execute_row_request(hdb_values)?;
+        let mut main_reply = self
+            .am_conn_core
+            .full_send_async(
+                request,
+                self.o_rs_md.as_ref(),
+                self.o_descriptors.as_ref(),
+                &mut None,
+            )
+            .await
+            .map_err(|e| self.add_parameter_name(e))?;
+
+        if let Some(locator_ids) = extract_locator_ids(&mut main_reply, readers.len())? {
+            for (locator_id, (reader, type_id)) in locator_ids.into_iter().zip(readers) {
+                debug!("writing content to locator with id {:?}", locator_id);
+                if let HdbValue::LOBSTREAM(Some(reader)) = reader {
+                    write_lob_async(
+                        &self.am_conn_core,
+                        locator_id,
+                        type_id,
+                        self.lob_write_length()?,
+                        &*reader,
+                    )
+                    .await?;
+                }
+            }
+        }
+        main_reply.into_hdbresponse_with(&mut (self.am_conn_core), self.fetch_overrides)
+    }
+
+    pub(crate) async fn execute_batch_async(&mut self) -> HdbResult<HdbResponse> {
+        self.check_batch()?;
+        let mut rows2 = ParameterRows::new();
+        mem::swap(&mut self.batch, &mut rows2);
+        self.execute_batch_rows_async(rows2).await
+    }
+
+    pub(crate) async fn execute_batch_detailed_async(&mut self) -> HdbResult<BatchResult> {
+        self.check_batch()?;
+        let rows = mem::replace(&mut self.batch, ParameterRows::new());
+        let result = self.execute_batch_rows_async(rows.try_clone()?).await;
+        into_batch_result(result, rows)
+    }
+
+    pub(crate) async fn execute_failed_rows_async(
+        &mut self,
+        mut batch_result: BatchResult,
+    ) -> HdbResult<BatchResult> {
+        let (indices, rows) = self.unprocessed_rows(&mut batch_result)?;
+        if indices.is_empty() {
+            return Ok(batch_result);
+        }
+        let result = self.execute_batch_rows_async(rows.try_clone()?).await;
+        batch_result.update(&indices, into_batch_result(result, rows)?);
+        Ok(batch_result)
+    }
+
+    async fn execute_batch_rows_async(
+        &mut self,
+        rows: ParameterRows<'static>,
+    ) -> HdbResult<HdbResponse> {
+        let mut chunks = self.split_batch(rows)?;
+        if chunks.len() == 1 {
+            return self.execute_parameter_rows_async(chunks.pop()).await;
+        }
+        debug!("executing the batch in {} roundtrips", chunks.len());
+        let mut results = Vec::<ExecutionResult>::new();
+        let mut chunks = chunks.into_iter();
+        while let Some(chunk) = chunks.next() {
+            let chunk_len = chunk.count();
+            let chunk_result = self.execute_parameter_rows_async(Some(chunk)).await;
+            add_chunk_results(&mut results, chunk_result, chunk_len, chunks.by_ref())?;
+        }
+        HdbResponse::rows_affected(vec![InternalReturnValue::AffectedRows(results)])
+    }
+
+    async fn execute_parameter_rows_async(
+        &mut self,
+        o_rows: Option<ParameterRows<'_>>,
+    ) -> HdbResult<HdbResponse> {
+        trace!("PreparedStatement::execute_parameter_rows_async()");
+        let o_rows = if self.renew_if_stale_async().await? {
+            self.rebind(o_rows)?
+        } else {
+            o_rows
+        };
+        let o_retry_rows = copy_for_retry(&o_rows);
+        let request = self.execute_request(o_rows)?;
+        let reply = match self
+            .am_conn_core
+            .full_send_async(
+                request,
+                self.o_rs_md.as_ref(),
+                self.o_descriptors.as_ref(),
+                &mut None,
+            )
+            .await
+        {
+            Err(ref e) if e.is_invalid_statement_id() && o_retry_rows.is_some() => {
+                debug!("server does not know the statement id, preparing the statement again");
+                self.remove_from_cache()?;
+                self.replace_with(
+                    PreparedStatement::try_new_async(self.am_conn_core.clone(), &self.stmt).await?,
+                );
+                let o_rows = self.rebind(o_retry_rows.flatten())?;
+                let request = self.execute_request(o_rows)?;
+                self.am_conn_core
+                    .full_send_async(
+                        request,
+                        self.o_rs_md.as_ref(),
+                        self.o_descriptors.as_ref(),
+                        &mut None,
+                    )
+                    .await
+            }
+            result => result,
+        }
+        .map_err(|e| self.add_parameter_name(e))?;
+        reply.into_hdbresponse_with(&mut (self.am_conn_core), self.fetch_overrides)
+    }
+
+    async fn renew_if_stale_async(&mut self) -> HdbResult<bool> {
+        if !self.is_stale()? {
+            return Ok(false);
+        }
+        debug!("statement was prepared in a former session, preparing it again");
+        self.replace_with(
+            PreparedStatement::try_new_async(self.am_conn_core.clone(), &self.stmt).await?,
+        );
+        Ok(true)
+    }
+}
+
+// Copies the rows of an execution, so that it can be repeated with the statement
+// prepared again; returns None if the rows cannot be copied.
+fn copy_for_retry<'a>(o_rows: &Option<ParameterRows<'a>>) -> Option<Option<ParameterRows<'a>>> {
+    match o_rows {
+        Some(rows) => rows.try_clone().ok().map(Some),
+        None => Some(None),
+    }
+}
+
+// Adds the outcome of a chunk of a split batch to the results of the batch.
+//
+// If the chunk failed, the remaining chunks are not sent; their rows are reported
+// as failed without error, and the results are returned as MixedResults.
+// Other errors, e.g. of the connection, are returned as they are, because nothing
+// is known about the rows of the chunk; the rows of earlier chunks may have been
+// processed nevertheless.
+fn add_chunk_results<I: Iterator<Item = ParameterRows<'static>>>(
+    results: &mut Vec<ExecutionResult>,
+    chunk_result: HdbResult<HdbResponse>,
+    chunk_len: usize,
+    remaining_chunks: I,
+) -> HdbResult<()> {
+    match chunk_result {
+        Ok(response) => {
+            results.extend(
+                response
+                    .into_affected_rows()?
+                    .into_iter()
+                    .map(ExecutionResult::RowsAffected),
+            );
+            return Ok(());
+        }
+        Err(HdbError::MixedResults(chunk_results)) => {
+            results.extend(chunk_results);
+        }
+        Err(HdbError::DbError(server_error)) if !results.is_empty() => {
+            results.extend(
+                (0..chunk_len).map(|_| ExecutionResult::Failure(Some(server_error.clone()))),
+            );
+        }
+        Err(e) => return Err(e),
+    }
+    for chunk in remaining_chunks {
+        results.extend((0..chunk.count()).map(|_| ExecutionResult::Failure(None)));
+    }
+    Err(HdbError::MixedResults(std::mem::take(results)))
+}
+
+// Maps the response of a batch execution, or its MixedResults, to the rows of the batch.
+fn into_batch_result(
+    result: HdbResult<HdbResponse>,
+    rows: ParameterRows<'static>,
+) -> HdbResult<BatchResult> {
+    let results = match result {
+        Ok(response) => match response.into_single_retval()? {
+            HdbReturnValue::AffectedRows(counts) => counts
+                .into_iter()
+                .map(ExecutionResult::RowsAffected)
+                .collect(),
+            // the server reports no row counts, e.g. for statements without input parameters
+            HdbReturnValue::Success => (0..std::cmp::max(rows.count(), 1))
+                .map(|_| ExecutionResult::SuccessNoInfo)
+                .collect(),
+            _ => {
+                return Err(HdbError::Evaluation(
+                    "a batch execution returned neither row counts nor success".to_string(),
+                ))
+            }
+        },
+        Err(HdbError::MixedResults(results)) => results,
+        Err(e) => return Err(e),
+    };
+    Ok(BatchResult::new(results, rows.into_rows()))
+}
+
+// Returns the locator ids of a WriteLobReply, if the reply contains one.
+fn extract_locator_ids(reply: &mut Reply, no_of_readers: usize) -> HdbResult<Option<Vec<u64>>> {
+    if let Some(Argument::WriteLobReply(wlr)) =
+        reply.extract_first_arg_of_type(PartKind::WriteLobReply)
+    {
+        let locator_ids = wlr.into_locator_ids();
+        if locator_ids.len() != no_of_readers {
+            return Err(HdbError::Usage(format!(
+                "The number of provided readers ({}) does not match \
+                 the number of required readers ({})",
+                no_of_readers,
+                locator_ids.len()
+            )));
+        }
+        Ok(Some(locator_ids))
+    } else {
+        Ok(None)
+    }
+}
+
+// Frees the server-side resources of the given statement ids.
+pub(crate) fn drop_statement_ids(am_conn_core: &mut AmConnCore, statement_ids: Vec<u64>) {
+    for statement_id in statement_ids {
         let mut request = Request::new(RequestType::DropStatementId, 0);
         request.push(Part::new(
             PartKind::StatementId,
-            Argument::StatementId(self.statement_id),
+            Argument::StatementId(statement_id),
         ));
-        if let Ok(mut reply) = self.am_conn_core.send(request) {
+        #[cfg(feature = "async")]
+        {
+            if am_conn_core.lock().map(|cc| cc.is_async()).unwrap_or(false) {
+                am_conn_core.send_in_background(request);
+                continue;
+            }
+        }
+        if let Ok(mut reply) = am_conn_core.send(request) {
             reply.parts.pop_arg_if_kind(PartKind::StatementContext);
         }
     }
 }
+
+impl Drop for PreparedStatement {
+    /// Frees all server-side ressources that belong to this prepared statement,
+    /// unless they are still used by the statement cache.
+    fn drop(&mut self) {
+        if let Some(cached) = self.o_cached.take() {
+            let is_last_user = match self.am_conn_core.lock() {
+                Ok(conn_core) => conn_core.statement_cache().release(cached),
+                Err(_) => false,
+            };
+            if !is_last_user {
+                return;
+            }
+        }
+        if let Ok(true) = self.is_stale() {
+            // the statement id died with the former session
+            return;
+        }
+        drop_statement_ids(&mut self.am_conn_core, vec![self.statement_id]);
+    }
+}
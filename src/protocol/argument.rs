@@ -7,7 +7,9 @@ use crate::protocol::parts::client_context::ClientContext;
 use crate::protocol::parts::client_info::ClientInfo;
 use crate::protocol::parts::command_info::CommandInfo;
 use crate::protocol::parts::connect_options::ConnectOptions;
+use crate::protocol::parts::db_connect_info::DbConnectInfo;
 use crate::protocol::parts::execution_result::ExecutionResult;
+use crate::protocol::parts::fetch_options::FetchOptions;
 use crate::protocol::parts::lob_flags::LobFlags;
 use crate::protocol::parts::output_parameters::OutputParameters;
 use crate::protocol::parts::parameter_descriptor::ParameterDescriptors;
@@ -40,8 +42,9 @@ pub(crate) enum Argument<'a> {
     CommandInfo(CommandInfo),
     // CommitOptions(super::parts::commit_options::CommitOptions), // not used by any client
     ConnectOptions(ConnectOptions),
+    DbConnectInfo(DbConnectInfo),
     Error(Vec<ServerError>),
-    // FetchOptions(super::parts::fetch_options::FetchOptions),    // not used by any client
+    FetchOptions(FetchOptions),
     FetchSize(u32),
     LobFlags(LobFlags),
     OutputParameters(OutputParameters),
@@ -82,7 +85,8 @@ impl<'a> Argument<'a> {
             Argument::CommandInfo(ref opts) => opts.count(),
             // Argument::CommitOptions(ref opts) => opts.count(),
             Argument::ConnectOptions(ref opts) => opts.count(),
-            // Argument::FetchOptions(ref opts) => opts.count(),
+            Argument::DbConnectInfo(ref opts) => opts.count(),
+            Argument::FetchOptions(ref opts) => opts.count(),
             Argument::LobFlags(ref opts) => opts.count(),
             Argument::Parameters(ref pars) => pars.count(),
             Argument::SessionContext(ref opts) => opts.count(),
@@ -90,7 +94,7 @@ impl<'a> Argument<'a> {
             Argument::TransactionFlags(ref opts) => opts.count(),
             Argument::XatOptions(ref xat) => xat.count(),
             ref a => {
-                return Err(HdbError::Impl(format!("count() called on {:?}", a)));
+                return Err(HdbError::Impl(format!("count() called on {:?}", a), None));
             }
         })
     }
@@ -110,7 +114,8 @@ impl<'a> Argument<'a> {
             Argument::CommandInfo(ref opts) => size += opts.size(),
             // Argument::CommitOptions(ref opts) => size += opts.size(),
             Argument::ConnectOptions(ref conn_opts) => size += conn_opts.size(),
-            // Argument::FetchOptions(ref opts) => size += opts.size(),
+            Argument::DbConnectInfo(ref opts) => size += opts.size(),
+            Argument::FetchOptions(ref opts) => size += opts.size(),
             Argument::FetchSize(_) => size += 4,
             Argument::LobFlags(ref opts) => size += opts.size(),
             Argument::Parameters(ref pars) => {
@@ -119,6 +124,7 @@ impl<'a> Argument<'a> {
                     None => {
                         return Err(HdbError::Impl(
                             "Argument::Parameters::emit(): No metadata".to_string(),
+                            None,
                         ));
                     }
                 }
@@ -134,7 +140,7 @@ impl<'a> Argument<'a> {
             Argument::XatOptions(ref xat) => size += xat.size(),
 
             ref arg => {
-                return Err(HdbError::Impl(format!("size() called on {:?}", arg)));
+                return Err(HdbError::Impl(format!("size() called on {:?}", arg), None));
             }
         }
         if with_padding {
@@ -158,8 +164,8 @@ impl<'a> Argument<'a> {
             Argument::CommandInfo(ref opts) => opts.emit(w)?,
             // Argument::CommitOptions(ref opts) => opts.emit(w)?,
             Argument::ConnectOptions(ref conn_opts) => conn_opts.emit(w)?,
-
-            // Argument::FetchOptions(ref opts) => opts.emit(w)?,
+            Argument::DbConnectInfo(ref opts) => opts.emit(w)?,
+            Argument::FetchOptions(ref opts) => opts.emit(w)?,
             Argument::FetchSize(fs) => {
                 w.write_u32::<LittleEndian>(fs)?;
             }
@@ -169,6 +175,7 @@ impl<'a> Argument<'a> {
                 None => {
                     return Err(HdbError::Impl(
                         "Argument::Parameters::emit(): No metadata".to_string(),
+                        None,
                     ));
                 }
             },
@@ -185,7 +192,7 @@ impl<'a> Argument<'a> {
             Argument::WriteLobRequest(ref r) => r.emit(w)?,
             Argument::XatOptions(ref xatid) => xatid.emit(w)?,
             ref a => {
-                return Err(HdbError::Impl(format!("emit() called on {:?}", a)));
+                return Err(HdbError::Impl(format!("emit() called on {:?}", a), None));
             }
         }
 
@@ -224,6 +231,9 @@ impl<'a> Argument<'a> {
             PartKind::ConnectOptions => {
                 Argument::ConnectOptions(ConnectOptions::parse(no_of_args, rdr)?)
             }
+            PartKind::DbConnectInfo => {
+                Argument::DbConnectInfo(DbConnectInfo::parse(no_of_args, rdr)?)
+            }
             PartKind::Error => Argument::Error(ServerError::parse(no_of_args, rdr)?),
             PartKind::OutputParameters => {
                 if let Some(descriptors) = o_descriptors {
@@ -235,6 +245,7 @@ impl<'a> Argument<'a> {
                 } else {
                     return Err(HdbError::Impl(
                         "Parsing output parameters needs metadata".to_owned(),
+                        None,
                     ));
                 }
             }
@@ -290,10 +301,10 @@ impl<'a> Argument<'a> {
             }
             PartKind::XatOptions => Argument::XatOptions(XatOptions::parse(no_of_args, rdr)?),
             _ => {
-                return Err(HdbError::Impl(format!(
-                    "No handling implemented for received partkind {:?}",
-                    kind
-                )));
+                return Err(HdbError::Impl(
+                    format!("No handling implemented for received partkind {:?}", kind),
+                    None,
+                ));
             }
         };
 
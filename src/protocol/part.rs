@@ -55,6 +55,7 @@ impl<'a> Part<'a> {
             _ => {
                 return Err(HdbError::Impl(
                     "argument count bigger than i32::MAX is not supported".to_owned(),
+                    None,
                 ));
             }
         }
@@ -182,6 +183,20 @@ impl<'a> Parts<'a> {
         Some(self.0.pop().unwrap().arg)
     }
 
+    pub fn arg_of_kind(&self, kind: PartKind) -> Option<&Argument<'a>> {
+        self.0
+            .iter()
+            .find(|part| part.kind.to_i8() == kind.to_i8())
+            .map(|part| &part.arg)
+    }
+
+    pub fn arg_of_kind_mut(&mut self, kind: PartKind) -> Option<&mut Argument<'a>> {
+        self.0
+            .iter_mut()
+            .find(|part| part.kind.to_i8() == kind.to_i8())
+            .map(|part| &mut part.arg)
+    }
+
     pub fn drop_args_of_kind(&mut self, kind: PartKind) {
         self.0.retain(|part| part.kind.to_i8() != kind.to_i8());
     }
@@ -35,6 +35,7 @@ pub enum PartKind {
     FindLobReply,          // 50 // Reply data of FINDLOB message // FIXME is missing
     ClientInfo,            // 57 // Client information values
     TransactionFlags,      // 64 // Transaction handling flags
+    DbConnectInfo,         // 67 // Database connect information
     LobFlags,              // 68 // LOB flags
     ResultsetOptions,      // 69 // Additional context data for result sets
     XatOptions,            // 70 // XA transaction information (XA transaction ID)
@@ -77,6 +78,7 @@ impl PartKind {
             PartKind::FindLobReply => 50,
             PartKind::ClientInfo => 57,
             PartKind::TransactionFlags => 64,
+            PartKind::DbConnectInfo => 67,
             PartKind::LobFlags => 68,
             PartKind::ResultsetOptions => 69,
             PartKind::XatOptions => 70,
@@ -120,6 +122,7 @@ impl PartKind {
             50 => Ok(PartKind::FindLobReply),
             57 => Ok(PartKind::ClientInfo),
             64 => Ok(PartKind::TransactionFlags),
+            67 => Ok(PartKind::DbConnectInfo),
             68 => Ok(PartKind::LobFlags),
             69 => Ok(PartKind::ResultsetOptions),
             70 => Ok(PartKind::XatOptions),
@@ -128,7 +131,10 @@ impl PartKind {
             73 => Ok(PartKind::SQLReplyOptions),
             74 => Ok(PartKind::PrintOptions),
 
-            _ => Err(HdbError::Impl(format!("PartKind {} not implemented", val))),
+            _ => Err(HdbError::Impl(
+                format!("PartKind {} not implemented", val),
+                None,
+            )),
         }
     }
 }
@@ -65,10 +65,10 @@ impl AuthField {
                 w.write_u16::<LittleEndian>(l as u16)?; // U2: length of value
             }
             l => {
-                return Err(HdbError::Impl(format!(
-                    "Value of AuthField is too big: {}",
-                    l
-                )));
+                return Err(HdbError::Impl(
+                    format!("Value of AuthField is too big: {}", l),
+                    None,
+                ));
             }
         }
         w.write_all(&self.0)?; // B (varying) value
@@ -86,10 +86,10 @@ impl AuthField {
                 len = rdr.read_u16::<LittleEndian>()? as usize; // (B1+)I2
             }
             251...254 => {
-                return Err(HdbError::Impl(format!(
-                    "Unknown length indicator for AuthField: {}",
-                    len
-                )));
+                return Err(HdbError::Impl(
+                    format!("Unknown length indicator for AuthField: {}", len),
+                    None,
+                ));
             }
             _ => {}
         }
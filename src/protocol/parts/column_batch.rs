@@ -0,0 +1,345 @@
+use crate::conn_core::AmConnCore;
+use crate::protocol::parts::hdb_value::{
+    parse_alphanum, parse_bigint, parse_binary, parse_bool, parse_double, parse_int, parse_real,
+    parse_smallint, parse_string, parse_tinyint, HdbValue,
+};
+use crate::protocol::parts::resultset::AmRsCore;
+use crate::protocol::parts::resultset_metadata::ResultSetMetadata;
+use crate::protocol::parts::row::Row;
+use crate::protocol::parts::type_id::TypeId;
+use crate::types::{DayDate, LongDate, SecondDate, SecondTime};
+use crate::types_impl::daydate::parse_daydate;
+use crate::types_impl::decimal::parse_decimal;
+use crate::types_impl::longdate::parse_longdate;
+use crate::types_impl::seconddate::parse_seconddate;
+use crate::types_impl::secondtime::parse_secondtime;
+use crate::{HdbError, HdbResult};
+use bigdecimal::BigDecimal;
+use std::sync::Arc;
+
+// The rows are transferred row-wise, and each value is decoded directly into the
+// typed vector of its column; only LOBs and spatial values are decoded as HdbValues.
+// HANA's column-wise transfer (connect option ColumnarResultSet) is not requested,
+// since its wire format is not documented.
+
+/// A set of consecutive rows of a `ResultSet`, stored column by column.
+///
+/// Is returned from [`ResultSet::next_column_batch()`](struct.ResultSet.html#method.next_column_batch).
+///
+/// Each column is a typed vector, so the values can be processed without matching
+/// on `HdbValue` for every single value.
+#[derive(Debug)]
+pub struct ColumnBatch {
+    metadata: Arc<ResultSetMetadata>,
+    columns: Vec<Column>,
+    len: usize,
+}
+
+impl ColumnBatch {
+    pub(crate) fn new(metadata: Arc<ResultSetMetadata>, capacity: usize) -> HdbResult<ColumnBatch> {
+        let mut columns = Vec::with_capacity(metadata.number_of_fields());
+        for i in 0..metadata.number_of_fields() {
+            columns.push(Column::for_type(metadata.type_id(i)?, capacity));
+        }
+        Ok(ColumnBatch {
+            metadata,
+            columns,
+            len: 0,
+        })
+    }
+
+    // Moves the values of the given rows into a new batch.
+    pub(crate) fn from_rows<I: IntoIterator<Item = Row>>(
+        metadata: Arc<ResultSetMetadata>,
+        rows: I,
+    ) -> HdbResult<ColumnBatch> {
+        let rows = rows.into_iter();
+        let mut batch = ColumnBatch::new(metadata, rows.size_hint().0)?;
+        for row in rows {
+            for (column, value) in batch.columns.iter_mut().zip(row) {
+                column.push(value)?;
+            }
+            batch.len += 1;
+        }
+        Ok(batch)
+    }
+
+    // Decodes the next row of the reply into the columns.
+    pub(crate) fn parse_row(
+        &mut self,
+        am_conn_core: &AmConnCore,
+        o_am_rscore: &Option<AmRsCore>,
+        rdr: &mut dyn std::io::BufRead,
+    ) -> HdbResult<()> {
+        for (i, column) in self.columns.iter_mut().enumerate() {
+            column.parse_value(
+                self.metadata.type_id(i)?,
+                self.metadata.scale(i)?,
+                self.metadata.nullable(i)?,
+                am_conn_core,
+                o_am_rscore,
+                rdr,
+            )?;
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Access to metadata.
+    pub fn metadata(&self) -> &ResultSetMetadata {
+        &self.metadata
+    }
+
+    /// Returns the number of rows in the batch.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the batch contains no rows.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the columns, in the order of the metadata.
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Returns the column with the given index, if it exists.
+    pub fn column(&self, idx: usize) -> Option<&Column> {
+        self.columns.get(idx)
+    }
+
+    /// Converts the batch into its columns.
+    pub fn into_columns(self) -> Vec<Column> {
+        self.columns
+    }
+}
+
+/// The values of a single column of a `ColumnBatch`.
+///
+/// The variant is determined by the column's type id;
+/// NULL values are represented as `None`.
+#[derive(Debug)]
+pub enum Column {
+    /// Values of a TINYINT column.
+    TinyInt(Vec<Option<u8>>),
+    /// Values of a SMALLINT column.
+    SmallInt(Vec<Option<i16>>),
+    /// Values of an INT column.
+    Int(Vec<Option<i32>>),
+    /// Values of a BIGINT column.
+    BigInt(Vec<Option<i64>>),
+    /// Values of a REAL column.
+    Real(Vec<Option<f32>>),
+    /// Values of a DOUBLE column.
+    Double(Vec<Option<f64>>),
+    /// Values of a BOOLEAN column.
+    Boolean(Vec<Option<bool>>),
+    /// Values of a DECIMAL column.
+    Decimal(Vec<Option<BigDecimal>>),
+    /// Values of a character column (e.g. VARCHAR, NVARCHAR, ALPHANUM).
+    String(Vec<Option<String>>),
+    /// Values of a binary column (BINARY, VARBINARY).
+    Binary(Vec<Option<Vec<u8>>>),
+    /// Values of a LONGDATE column.
+    LongDate(Vec<Option<LongDate>>),
+    /// Values of a SECONDDATE column.
+    SecondDate(Vec<Option<SecondDate>>),
+    /// Values of a DAYDATE column.
+    DayDate(Vec<Option<DayDate>>),
+    /// Values of a SECONDTIME column.
+    SecondTime(Vec<Option<SecondTime>>),
+    /// Values of all other columns (LOBs, spatial types), as generic values.
+    Other(Vec<HdbValue<'static>>),
+}
+
+impl Column {
+    fn for_type(type_id: TypeId, capacity: usize) -> Column {
+        match type_id {
+            TypeId::TINYINT => Column::TinyInt(Vec::with_capacity(capacity)),
+            TypeId::SMALLINT => Column::SmallInt(Vec::with_capacity(capacity)),
+            TypeId::INT => Column::Int(Vec::with_capacity(capacity)),
+            TypeId::BIGINT => Column::BigInt(Vec::with_capacity(capacity)),
+            TypeId::REAL => Column::Real(Vec::with_capacity(capacity)),
+            TypeId::DOUBLE => Column::Double(Vec::with_capacity(capacity)),
+            TypeId::BOOLEAN => Column::Boolean(Vec::with_capacity(capacity)),
+            TypeId::DECIMAL | TypeId::FIXED8 | TypeId::FIXED12 | TypeId::FIXED16 => {
+                Column::Decimal(Vec::with_capacity(capacity))
+            }
+            TypeId::CHAR
+            | TypeId::VARCHAR
+            | TypeId::NCHAR
+            | TypeId::NVARCHAR
+            | TypeId::STRING
+            | TypeId::NSTRING
+            | TypeId::SHORTTEXT
+            | TypeId::ALPHANUM => Column::String(Vec::with_capacity(capacity)),
+            TypeId::BINARY | TypeId::VARBINARY | TypeId::BSTRING => {
+                Column::Binary(Vec::with_capacity(capacity))
+            }
+            TypeId::LONGDATE => Column::LongDate(Vec::with_capacity(capacity)),
+            TypeId::SECONDDATE => Column::SecondDate(Vec::with_capacity(capacity)),
+            TypeId::DAYDATE => Column::DayDate(Vec::with_capacity(capacity)),
+            TypeId::SECONDTIME => Column::SecondTime(Vec::with_capacity(capacity)),
+            _ => Column::Other(Vec::with_capacity(capacity)),
+        }
+    }
+
+    // Decodes the next value from the reply into the vector.
+    fn parse_value(
+        &mut self,
+        type_id: TypeId,
+        scale: i16,
+        nullable: bool,
+        am_conn_core: &AmConnCore,
+        o_am_rscore: &Option<AmRsCore>,
+        rdr: &mut dyn std::io::BufRead,
+    ) -> HdbResult<()> {
+        match self {
+            Column::TinyInt(v) => v.push(parse_tinyint(nullable, rdr)?),
+            Column::SmallInt(v) => v.push(parse_smallint(nullable, rdr)?),
+            Column::Int(v) => v.push(parse_int(nullable, rdr)?),
+            Column::BigInt(v) => v.push(parse_bigint(nullable, rdr)?),
+            Column::Real(v) => v.push(parse_real(nullable, rdr)?),
+            Column::Double(v) => v.push(parse_double(nullable, rdr)?),
+            Column::Boolean(v) => v.push(parse_bool(nullable, rdr)?),
+            Column::Decimal(v) => v.push(parse_decimal(nullable, type_id, scale, rdr)?),
+            Column::String(v) => v.push(if type_id == TypeId::ALPHANUM {
+                parse_alphanum(nullable, rdr)?
+            } else {
+                parse_string(nullable, rdr)?
+            }),
+            Column::Binary(v) => v.push(parse_binary(nullable, rdr)?),
+            Column::LongDate(v) => v.push(parse_longdate(nullable, rdr)?),
+            Column::SecondDate(v) => v.push(parse_seconddate(nullable, rdr)?),
+            Column::DayDate(v) => v.push(parse_daydate(nullable, rdr)?),
+            Column::SecondTime(v) => v.push(parse_secondtime(nullable, rdr)?),
+            Column::Other(v) => v.push(HdbValue::parse_from_reply(
+                type_id,
+                scale,
+                nullable,
+                am_conn_core,
+                o_am_rscore,
+                rdr,
+            )?),
+        }
+        Ok(())
+    }
+
+    // Moves an already decoded value into the vector.
+    fn push(&mut self, value: HdbValue<'static>) -> HdbResult<()> {
+        match (self, value) {
+            (Column::Other(v), value) => v.push(value),
+            (Column::TinyInt(v), HdbValue::NULL) => v.push(None),
+            (Column::TinyInt(v), HdbValue::TINYINT(i)) => v.push(Some(i)),
+            (Column::SmallInt(v), HdbValue::NULL) => v.push(None),
+            (Column::SmallInt(v), HdbValue::SMALLINT(i)) => v.push(Some(i)),
+            (Column::Int(v), HdbValue::NULL) => v.push(None),
+            (Column::Int(v), HdbValue::INT(i)) => v.push(Some(i)),
+            (Column::BigInt(v), HdbValue::NULL) => v.push(None),
+            (Column::BigInt(v), HdbValue::BIGINT(i)) => v.push(Some(i)),
+            (Column::Real(v), HdbValue::NULL) => v.push(None),
+            (Column::Real(v), HdbValue::REAL(f)) => v.push(Some(f)),
+            (Column::Double(v), HdbValue::NULL) => v.push(None),
+            (Column::Double(v), HdbValue::DOUBLE(f)) => v.push(Some(f)),
+            (Column::Boolean(v), HdbValue::NULL) => v.push(None),
+            (Column::Boolean(v), HdbValue::BOOLEAN(b)) => v.push(Some(b)),
+            (Column::Decimal(v), HdbValue::NULL) => v.push(None),
+            (Column::Decimal(v), HdbValue::DECIMAL(d)) => v.push(Some(d)),
+            (Column::String(v), HdbValue::NULL) => v.push(None),
+            (Column::String(v), HdbValue::STRING(s)) => v.push(Some(s)),
+            (Column::Binary(v), HdbValue::NULL) => v.push(None),
+            (Column::Binary(v), HdbValue::BINARY(b)) => v.push(Some(b)),
+            (Column::LongDate(v), HdbValue::NULL) => v.push(None),
+            (Column::LongDate(v), HdbValue::LONGDATE(ld)) => v.push(Some(ld)),
+            (Column::SecondDate(v), HdbValue::NULL) => v.push(None),
+            (Column::SecondDate(v), HdbValue::SECONDDATE(sd)) => v.push(Some(sd)),
+            (Column::DayDate(v), HdbValue::NULL) => v.push(None),
+            (Column::DayDate(v), HdbValue::DAYDATE(dd)) => v.push(Some(dd)),
+            (Column::SecondTime(v), HdbValue::NULL) => v.push(None),
+            (Column::SecondTime(v), HdbValue::SECONDTIME(st)) => v.push(Some(st)),
+            (column, value) => {
+                return Err(HdbError::Impl(
+                    format!(
+                        "value {:?} does not fit into column of kind {}",
+                        value,
+                        column.kind()
+                    ),
+                    None,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn kind(&self) -> &'static str {
+        match *self {
+            Column::TinyInt(_) => "TinyInt",
+            Column::SmallInt(_) => "SmallInt",
+            Column::Int(_) => "Int",
+            Column::BigInt(_) => "BigInt",
+            Column::Real(_) => "Real",
+            Column::Double(_) => "Double",
+            Column::Boolean(_) => "Boolean",
+            Column::Decimal(_) => "Decimal",
+            Column::String(_) => "String",
+            Column::Binary(_) => "Binary",
+            Column::LongDate(_) => "LongDate",
+            Column::SecondDate(_) => "SecondDate",
+            Column::DayDate(_) => "DayDate",
+            Column::SecondTime(_) => "SecondTime",
+            Column::Other(_) => "Other",
+        }
+    }
+
+    /// Returns the number of values in the column.
+    pub fn len(&self) -> usize {
+        match *self {
+            Column::TinyInt(ref v) => v.len(),
+            Column::SmallInt(ref v) => v.len(),
+            Column::Int(ref v) => v.len(),
+            Column::BigInt(ref v) => v.len(),
+            Column::Real(ref v) => v.len(),
+            Column::Double(ref v) => v.len(),
+            Column::Boolean(ref v) => v.len(),
+            Column::Decimal(ref v) => v.len(),
+            Column::String(ref v) => v.len(),
+            Column::Binary(ref v) => v.len(),
+            Column::LongDate(ref v) => v.len(),
+            Column::SecondDate(ref v) => v.len(),
+            Column::DayDate(ref v) => v.len(),
+            Column::SecondTime(ref v) => v.len(),
+            Column::Other(ref v) => v.len(),
+        }
+    }
+
+    /// Returns true if the column contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Column;
+    use crate::protocol::parts::hdb_value::HdbValue;
+    use crate::protocol::parts::type_id::TypeId;
+
+    #[test]
+    fn test_push_typed_values() {
+        let mut column = Column::for_type(TypeId::INT, 2);
+        column.push(HdbValue::INT(42)).unwrap();
+        column.push(HdbValue::NULL).unwrap();
+        match column {
+            Column::Int(ref v) => assert_eq!(v, &vec![Some(42), None]),
+            ref c => panic!("unexpected column {:?}", c),
+        }
+        assert_eq!(column.len(), 2);
+
+        let mut column = Column::for_type(TypeId::NVARCHAR, 1);
+        column.push(HdbValue::STRING("abc".to_owned())).unwrap();
+        assert!(column.push(HdbValue::INT(1)).is_err());
+        assert_eq!(column.len(), 1);
+    }
+}
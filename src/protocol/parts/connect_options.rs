@@ -8,8 +8,14 @@ pub(crate) type ConnectOptions = OptionPart<ConnOptId>;
 
 // Methods to send information to the server.
 impl ConnectOptions {
-    pub fn for_server(locale: &Option<String>, os_user: String) -> ConnectOptions {
-        let connopts = ConnectOptions::default()
+    pub fn for_server(
+        locale: &Option<String>,
+        os_user: String,
+        compression: bool,
+        scrollable_cursors: bool,
+        o_anchor_connection_id: Option<i32>,
+    ) -> ConnectOptions {
+        let mut connopts = ConnectOptions::default()
             //.set_complete_array_execution(true)
             //.set_row_slot_image_parameter(true)
             //.set_enable_array_type(true)
@@ -17,6 +23,15 @@ impl ConnectOptions {
             .set_dataformat_version2(8)
             .set_client_locale(locale)
             .set_os_user(os_user);
+        if compression {
+            connopts = connopts.set_compression_level_and_flags(1);
+        }
+        if scrollable_cursors {
+            connopts = connopts.set_scrollable_result_set(true);
+        }
+        if let Some(anchor_connection_id) = o_anchor_connection_id {
+            connopts = connopts.set_original_anchor_connection_id(anchor_connection_id);
+        }
         if cfg!(feature = "alpha_routing") {
             warn!("Feature alpha_routing is active!");
             connopts
@@ -84,6 +99,27 @@ impl ConnectOptions {
         self.set_to_server(ConnOptId::OSUser, OptionValue::STRING(s));
         self
     }
+
+    // Proposes to compress the variable part of the messages; the server only
+    // compresses if it echoes the option.
+    fn set_compression_level_and_flags(mut self, v: i32) -> ConnectOptions {
+        self.set_to_server(ConnOptId::CompressionLevelAndFlags, OptionValue::INT(v));
+        self
+    }
+
+    // Asks the server to create scrollable cursors for resultsets, which can then
+    // be positioned with FetchAbsolute, FetchRelative, FetchFirst, and FetchLast.
+    fn set_scrollable_result_set(mut self, b: bool) -> ConnectOptions {
+        self.set_to_server(ConnOptId::ScrollableResultSet, OptionValue::BOOLEAN(b));
+        self
+    }
+
+    // Notifies the server that this is a reconnect of the given connection.
+    fn set_original_anchor_connection_id(mut self, id: i32) -> ConnectOptions {
+        self.set_to_server(ConnOptId::OriginalAnchorConnectionID, OptionValue::INT(id));
+        self
+    }
+
     fn set_to_server(&mut self, id: ConnOptId, value: OptionValue) -> Option<OptionValue> {
         debug!("Sending ConnectionOption to server: {:?} = {:?}", id, value);
         self.set_value(id, value)
@@ -110,6 +146,7 @@ impl ConnectOptions {
                 | ConnOptId::DataFormatVersion2
                 | ConnOptId::NonTransactionalPrepare
                 | ConnOptId::SupportsLargeBulkOperations
+                | ConnOptId::LargeNumberOfParametersOK
                 | ConnOptId::ActiveActiveProtocolVersion
                 | ConnOptId::ImplicitLobStreaming
                 | ConnOptId::CompleteArrayExecution
@@ -121,6 +158,9 @@ impl ConnectOptions {
                 | ConnOptId::ItabParameter
                 | ConnOptId::ClientDistributionMode
                 | ConnOptId::ClientInfoNullValueOK
+                | ConnOptId::CompressionLevelAndFlags
+                | ConnOptId::ClientReconnectWaitTimeout
+                | ConnOptId::OriginalAnchorConnectionID
                 | ConnOptId::FlagSet1 => {
                     let old_value = old_co.get_value(&k);
                     match old_value {
@@ -253,13 +293,23 @@ impl ConnectOptions {
     //     )
     // }
 
-    // // Is set by the server to indicate that it can process array commands.
-    // pub fn get_supports_large_bulk_operations(&self) -> Option<&bool> {
-    //     self.get_bool(
-    //         &ConnOptId::SupportsLargeBulkOperations,
-    //         "SupportsLargeBulkOperations",
-    //     )
-    // }
+    // Is set by the server to indicate that it can process array commands
+    // with more than 32K rows.
+    pub fn get_supports_large_bulk_operations(&self) -> Option<bool> {
+        self.get_bool(
+            &ConnOptId::SupportsLargeBulkOperations,
+            "SupportsLargeBulkOperations",
+        )
+    }
+
+    // Is set by the server to indicate that it can process requests
+    // with more than 32K parameters.
+    pub fn get_large_number_of_parameters_ok(&self) -> Option<bool> {
+        self.get_bool(
+            &ConnOptId::LargeNumberOfParametersOK,
+            "LargeNumberOfParametersOK",
+        )
+    }
 
     // // ActiveActiveProtocolVersion.
     // pub fn get_activeactive_protocolversion(&self) -> Option<i32> {
@@ -275,6 +325,26 @@ impl ConnectOptions {
         self.get_bool(&ConnOptId::ImplicitLobStreaming, "ImplicitLobStreaming")
     }
 
+    // Is set by the server to indicate how long (in seconds) the client
+    // may try to reconnect to the session after the connection broke.
+    pub fn get_client_reconnect_wait_timeout(&self) -> Option<i32> {
+        self.get_integer(
+            &ConnOptId::ClientReconnectWaitTimeout,
+            "ClientReconnectWaitTimeout",
+        )
+    }
+
+    // Is set by the server if it agrees to compress the messages.
+    pub fn is_compression_active(&self) -> bool {
+        match self.get_integer(
+            &ConnOptId::CompressionLevelAndFlags,
+            "CompressionLevelAndFlags",
+        ) {
+            Some(i) => i > 0,
+            None => false,
+        }
+    }
+
     // // Is set to true if array commands continue to process remaining input
     // // when detecting an error in an input row.
     // pub fn get_complete_array_execution(&self) -> Option<&bool> {
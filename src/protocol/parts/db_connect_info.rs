@@ -0,0 +1,73 @@
+use crate::protocol::parts::option_part::OptionId;
+use crate::protocol::parts::option_part::OptionPart;
+use crate::protocol::parts::option_value::OptionValue;
+
+// An Options part that is used to ask the system database for the location
+// of a tenant database.
+pub(crate) type DbConnectInfo = OptionPart<DbConnectInfoId>;
+
+impl DbConnectInfo {
+    pub fn new(database_name: String) -> DbConnectInfo {
+        let mut db_connect_info = DbConnectInfo::default();
+        db_connect_info.set_value(
+            DbConnectInfoId::DatabaseName,
+            OptionValue::STRING(database_name),
+        );
+        db_connect_info
+    }
+
+    // Whether the current connection already leads to the requested database.
+    pub fn is_connected(&self) -> bool {
+        matches!(
+            self.get_value(&DbConnectInfoId::IsConnected),
+            Some(OptionValue::BOOLEAN(true))
+        )
+    }
+
+    // Host and SQL port of the requested database.
+    pub fn location(&self) -> Option<(String, u16)> {
+        match (
+            self.get_value(&DbConnectInfoId::Host),
+            self.get_value(&DbConnectInfoId::Port),
+        ) {
+            (Some(OptionValue::STRING(host)), Some(OptionValue::INT(port))) => {
+                Some((host.clone(), *port as u16))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub enum DbConnectInfoId {
+    DatabaseName, // 1 // STRING  // Name of the tenant database
+    Host,         // 2 // STRING  // Host of the tenant database
+    Port,         // 3 // INT     // SQL port of the tenant database
+    IsConnected,  // 4 // BOOLEAN // Connection already leads to the tenant database
+    __Unexpected__(u8),
+}
+
+impl OptionId<DbConnectInfoId> for DbConnectInfoId {
+    fn to_u8(&self) -> u8 {
+        match *self {
+            DbConnectInfoId::DatabaseName => 1,
+            DbConnectInfoId::Host => 2,
+            DbConnectInfoId::Port => 3,
+            DbConnectInfoId::IsConnected => 4,
+            DbConnectInfoId::__Unexpected__(val) => val,
+        }
+    }
+
+    fn from_u8(val: u8) -> DbConnectInfoId {
+        match val {
+            1 => DbConnectInfoId::DatabaseName,
+            2 => DbConnectInfoId::Host,
+            3 => DbConnectInfoId::Port,
+            4 => DbConnectInfoId::IsConnected,
+            val => {
+                warn!("Unsupported value for DbConnectInfoId received: {}", val);
+                DbConnectInfoId::__Unexpected__(val)
+            }
+        }
+    }
+}
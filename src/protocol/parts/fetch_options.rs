@@ -1,33 +1,43 @@
-// commented out, as the docu says: not used by any client implementation
+use crate::protocol::parts::option_part::OptionId;
+use crate::protocol::parts::option_part::OptionPart;
+use crate::protocol::parts::option_value::OptionValue;
 
-// use crate::protocol::parts::option_part::OptionId;
-// use crate::protocol::parts::option_part::OptionPart;
+// An Options part that is used by the client when fetching resultset lines;
+// the RESULTSETPOS field is used to position scrollable cursors.
+pub(crate) type FetchOptions = OptionPart<FetchOptionsId>;
 
-// // An Options part that is used by the client when fetching resultset lines;
-// // the RESULTSETPOS field can be used to skip over entries.
-// pub type FetchOptions = OptionPart<FetchOptionsId>;
+impl FetchOptions {
+    pub fn for_position(position: i32) -> FetchOptions {
+        let mut fetch_options = FetchOptions::default();
+        fetch_options.set_value(
+            FetchOptionsId::ResultsetPosition,
+            OptionValue::INT(position),
+        );
+        fetch_options
+    }
+}
 
-// #[derive(Debug, Eq, PartialEq, Hash)]
-// pub enum FetchOptionsId {
-//     ResultsetPosition, // 1 // INT // Position for Fetch
-//     __Unexpected__(u8),
-// }
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub enum FetchOptionsId {
+    ResultsetPosition, // 1 // INT // Position for Fetch
+    __Unexpected__(u8),
+}
 
-// impl OptionId<FetchOptionsId> for FetchOptionsId {
-//     fn to_u8(&self) -> u8 {
-//         match *self {
-//             FetchOptionsId::ResultsetPosition => 1,
-//             FetchOptionsId::__Unexpected__(val) => val,
-//         }
-//     }
+impl OptionId<FetchOptionsId> for FetchOptionsId {
+    fn to_u8(&self) -> u8 {
+        match *self {
+            FetchOptionsId::ResultsetPosition => 1,
+            FetchOptionsId::__Unexpected__(val) => val,
+        }
+    }
 
-//     fn from_u8(val: u8) -> FetchOptionsId {
-//         match val {
-//             1 => FetchOptionsId::ResultsetPosition,
-//             val => {
-//                 warn!("Unsupported value for FetchOptionsId received: {}", val);
-//                 FetchOptionsId::__Unexpected__(val)
-//             }
-//         }
-//     }
-// }
+    fn from_u8(val: u8) -> FetchOptionsId {
+        match val {
+            1 => FetchOptionsId::ResultsetPosition,
+            val => {
+                warn!("Unsupported value for FetchOptionsId received: {}", val);
+                FetchOptionsId::__Unexpected__(val)
+            }
+        }
+    }
+}
@@ -101,6 +101,7 @@ impl<'a> HdbValue<'a> {
             HdbValue::NOTHING => {
                 return Err(HdbError::Impl(
                     "Can't send HdbValue::NOTHING to Database".to_string(),
+                    None,
                 ));
             }
             HdbValue::NULL => match requested_type_id {
@@ -118,10 +119,13 @@ impl<'a> HdbValue<'a> {
                     requested_type_id
                 }
                 _ => {
-                    return Err(HdbError::Impl(format!(
-                        "Can't send {} type for requested {:?} type",
-                        "DECIMAL", requested_type_id
-                    )));
+                    return Err(HdbError::Impl(
+                        format!(
+                            "Can't send {} type for requested {:?} type",
+                            "DECIMAL", requested_type_id
+                        ),
+                        None,
+                    ));
                 }
             },
             HdbValue::REAL(_) => TypeId::REAL,
@@ -142,6 +146,38 @@ impl<'a> HdbValue<'a> {
         })
     }
 
+    // Copies the value; LOB handles are not copied, since they share the connection.
+    pub(crate) fn try_clone(&self) -> HdbResult<HdbValue<'a>> {
+        Ok(match *self {
+            HdbValue::NOTHING => HdbValue::NOTHING,
+            HdbValue::NULL => HdbValue::NULL,
+            HdbValue::TINYINT(u) => HdbValue::TINYINT(u),
+            HdbValue::SMALLINT(i) => HdbValue::SMALLINT(i),
+            HdbValue::INT(i) => HdbValue::INT(i),
+            HdbValue::BIGINT(i) => HdbValue::BIGINT(i),
+            HdbValue::DECIMAL(ref bigdec) => HdbValue::DECIMAL(bigdec.clone()),
+            HdbValue::REAL(f) => HdbValue::REAL(f),
+            HdbValue::DOUBLE(f) => HdbValue::DOUBLE(f),
+            HdbValue::BINARY(ref v) => HdbValue::BINARY(v.clone()),
+            HdbValue::CLOB(_) | HdbValue::NCLOB(_) | HdbValue::BLOB(_) => {
+                return Err(HdbError::Usage(format!(
+                    "HdbValue::{} cannot be copied",
+                    self
+                )));
+            }
+            HdbValue::LOBSTREAM(ref o_stream) => HdbValue::LOBSTREAM(o_stream.clone()),
+            HdbValue::BOOLEAN(b) => HdbValue::BOOLEAN(b),
+            HdbValue::STRING(ref s) => HdbValue::STRING(s.clone()),
+            HdbValue::STR(s) => HdbValue::STR(s),
+            HdbValue::LONGDATE(ref ld) => HdbValue::LONGDATE(ld.clone()),
+            HdbValue::SECONDDATE(ref sd) => HdbValue::SECONDDATE(sd.clone()),
+            HdbValue::DAYDATE(ref dd) => HdbValue::DAYDATE(dd.clone()),
+            HdbValue::SECONDTIME(ref st) => HdbValue::SECONDTIME(st.clone()),
+            HdbValue::GEOMETRY(ref v) => HdbValue::GEOMETRY(v.clone()),
+            HdbValue::POINT(ref v) => HdbValue::POINT(v.clone()),
+        })
+    }
+
     /// Returns true if the value is a NULL value.
     pub fn is_null(&self) -> bool {
         match *self {
@@ -211,10 +247,10 @@ impl<'a> HdbValue<'a> {
                 TypeId::FIXED12 => 12,
                 TypeId::FIXED16 => 16,
                 tid => {
-                    return Err(HdbError::Impl(format!(
-                        "invalid TypeId {:?} for DECIMAL",
-                        tid
-                    )));
+                    return Err(HdbError::Impl(
+                        format!("invalid TypeId {:?} for DECIMAL", tid),
+                        None,
+                    ));
                 }
             },
 
@@ -240,10 +276,10 @@ impl<'a> HdbValue<'a> {
             | HdbValue::NCLOB(_)
             | HdbValue::BLOB(_)
             | HdbValue::LOBSTREAM(Some(_)) => {
-                return Err(HdbError::Impl(format!(
-                    "size(): can't send {:?} directly to the database",
-                    self
-                )));
+                return Err(HdbError::Impl(
+                    format!("size(): can't send {:?} directly to the database", self),
+                    None,
+                ));
             }
         })
     }
@@ -259,10 +295,13 @@ impl HdbValue<'static> {
     pub fn try_into_blob(self) -> HdbResult<BLob> {
         match self {
             HdbValue::BLOB(blob) => Ok(blob),
-            tv => Err(HdbError::Conversion(ConversionError::ValueType(format!(
-                "The value {:?} cannot be converted into a BLOB",
-                tv
-            )))),
+            tv => Err(HdbError::Conversion(
+                ConversionError::ValueType(format!(
+                    "The value {:?} cannot be converted into a BLOB",
+                    tv
+                )),
+                None,
+            )),
         }
     }
 
@@ -270,10 +309,13 @@ impl HdbValue<'static> {
     pub fn try_into_clob(self) -> HdbResult<CLob> {
         match self {
             HdbValue::CLOB(clob) => Ok(clob),
-            tv => Err(HdbError::Conversion(ConversionError::ValueType(format!(
-                "The value {:?} cannot be converted into a CLOB",
-                tv
-            )))),
+            tv => Err(HdbError::Conversion(
+                ConversionError::ValueType(format!(
+                    "The value {:?} cannot be converted into a CLOB",
+                    tv
+                )),
+                None,
+            )),
         }
     }
 
@@ -284,7 +326,7 @@ impl HdbValue<'static> {
             tv => Err(HdbError::Conversion(ConversionError::ValueType(format!(
                 "HdbValue::try_into_nclob(): the database value {:?} cannot be converted into a NCLob",
                 tv
-            )))),
+            )), None)),
         }
     }
 
@@ -298,17 +340,28 @@ impl HdbValue<'static> {
     ) -> HdbResult<HdbValue<'static>> {
         let t = type_id;
         match t {
-            TypeId::TINYINT => Ok(parse_tinyint(nullable, rdr)?),
-            TypeId::SMALLINT => Ok(parse_smallint(nullable, rdr)?),
-            TypeId::INT => Ok(parse_int(nullable, rdr)?),
-            TypeId::BIGINT => Ok(parse_bigint(nullable, rdr)?),
-            TypeId::REAL => Ok(parse_real(nullable, rdr)?),
-            TypeId::DOUBLE => Ok(parse_double(nullable, rdr)?),
+            TypeId::TINYINT => {
+                Ok(parse_tinyint(nullable, rdr)?.map_or(HdbValue::NULL, HdbValue::TINYINT))
+            }
+            TypeId::SMALLINT => {
+                Ok(parse_smallint(nullable, rdr)?.map_or(HdbValue::NULL, HdbValue::SMALLINT))
+            }
+            TypeId::INT => Ok(parse_int(nullable, rdr)?.map_or(HdbValue::NULL, HdbValue::INT)),
+            TypeId::BIGINT => {
+                Ok(parse_bigint(nullable, rdr)?.map_or(HdbValue::NULL, HdbValue::BIGINT))
+            }
+            TypeId::REAL => Ok(parse_real(nullable, rdr)?.map_or(HdbValue::NULL, HdbValue::REAL)),
+            TypeId::DOUBLE => {
+                Ok(parse_double(nullable, rdr)?.map_or(HdbValue::NULL, HdbValue::DOUBLE))
+            }
 
-            TypeId::BOOLEAN => Ok(parse_bool(nullable, rdr)?),
+            TypeId::BOOLEAN => {
+                Ok(parse_bool(nullable, rdr)?.map_or(HdbValue::NULL, HdbValue::BOOLEAN))
+            }
 
             TypeId::DECIMAL | TypeId::FIXED8 | TypeId::FIXED12 | TypeId::FIXED16 => {
-                Ok(parse_decimal(nullable, t, scale, rdr)?)
+                Ok(parse_decimal(nullable, t, scale, rdr)?
+                    .map_or(HdbValue::NULL, HdbValue::DECIMAL))
             }
 
             TypeId::CHAR
@@ -317,21 +370,27 @@ impl HdbValue<'static> {
             | TypeId::NVARCHAR
             | TypeId::STRING
             | TypeId::NSTRING
-            | TypeId::SHORTTEXT => Ok(parse_string(nullable, t, rdr)?),
+            | TypeId::SHORTTEXT => {
+                Ok(parse_string(nullable, rdr)?.map_or(HdbValue::NULL, HdbValue::STRING))
+            }
 
             TypeId::ALPHANUM => {
-                let res = parse_alphanum(nullable, rdr)?;
-                Ok(res)
+                Ok(parse_alphanum(nullable, rdr)?.map_or(HdbValue::NULL, HdbValue::STRING))
             }
 
-            TypeId::BINARY
-            | TypeId::VARBINARY
-            | TypeId::BSTRING
-            | TypeId::GEOMETRY
-            | TypeId::POINT => Ok(parse_binary(nullable, t, rdr)?),
+            TypeId::BINARY | TypeId::VARBINARY | TypeId::BSTRING => {
+                Ok(parse_binary(nullable, rdr)?.map_or(HdbValue::NULL, HdbValue::BINARY))
+            }
+            TypeId::GEOMETRY => {
+                Ok(parse_binary(nullable, rdr)?.map_or(HdbValue::NULL, HdbValue::GEOMETRY))
+            }
+            TypeId::POINT => {
+                Ok(parse_binary(nullable, rdr)?.map_or(HdbValue::NULL, HdbValue::POINT))
+            }
 
             TypeId::BLOCATOR => Err(HdbError::Impl(
                 "parsing BLOCATOR not implemented".to_owned(),
+                None,
             )),
             TypeId::BLOB | TypeId::BINTEXT => {
                 Ok(parse_blob(am_conn_core, o_am_rscore, nullable, rdr)?)
@@ -341,10 +400,18 @@ impl HdbValue<'static> {
                 Ok(parse_nclob(am_conn_core, o_am_rscore, nullable, t, rdr)?)
             }
 
-            TypeId::LONGDATE => Ok(parse_longdate(nullable, rdr)?),
-            TypeId::SECONDDATE => Ok(parse_seconddate(nullable, rdr)?),
-            TypeId::DAYDATE => Ok(parse_daydate(nullable, rdr)?),
-            TypeId::SECONDTIME => Ok(parse_secondtime(nullable, rdr)?),
+            TypeId::LONGDATE => {
+                Ok(parse_longdate(nullable, rdr)?.map_or(HdbValue::NULL, HdbValue::LONGDATE))
+            }
+            TypeId::SECONDDATE => {
+                Ok(parse_seconddate(nullable, rdr)?.map_or(HdbValue::NULL, HdbValue::SECONDDATE))
+            }
+            TypeId::DAYDATE => {
+                Ok(parse_daydate(nullable, rdr)?.map_or(HdbValue::NULL, HdbValue::DAYDATE))
+            }
+            TypeId::SECONDTIME => {
+                Ok(parse_secondtime(nullable, rdr)?.map_or(HdbValue::NULL, HdbValue::SECONDTIME))
+            }
         }
     }
 }
@@ -367,109 +434,115 @@ fn parse_null(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<bool> {
     if is_null && !nullable {
         Err(HdbError::Impl(
             "found null value for not-null column".to_owned(),
+            None,
         ))
     } else {
         Ok(is_null)
     }
 }
 
-fn parse_tinyint(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<HdbValue<'static>> {
+// The following functions decode a single value of a given type;
+// they return None for NULL values.
+
+pub(crate) fn parse_tinyint(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<Option<u8>> {
     Ok(if parse_null(nullable, rdr)? {
-        HdbValue::NULL
+        None
     } else {
-        HdbValue::TINYINT(rdr.read_u8()?)
+        Some(rdr.read_u8()?)
     })
 }
 
-fn parse_smallint(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<HdbValue<'static>> {
+pub(crate) fn parse_smallint(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<Option<i16>> {
     Ok(if parse_null(nullable, rdr)? {
-        HdbValue::NULL
+        None
     } else {
-        HdbValue::SMALLINT(rdr.read_i16::<LittleEndian>()?)
+        Some(rdr.read_i16::<LittleEndian>()?)
     })
 }
-fn parse_int(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<HdbValue<'static>> {
+
+pub(crate) fn parse_int(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<Option<i32>> {
     Ok(if parse_null(nullable, rdr)? {
-        HdbValue::NULL
+        None
     } else {
-        HdbValue::INT(rdr.read_i32::<LittleEndian>()?)
+        Some(rdr.read_i32::<LittleEndian>()?)
     })
 }
-fn parse_bigint(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<HdbValue<'static>> {
+
+pub(crate) fn parse_bigint(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<Option<i64>> {
     Ok(if parse_null(nullable, rdr)? {
-        HdbValue::NULL
+        None
     } else {
-        HdbValue::BIGINT(rdr.read_i64::<LittleEndian>()?)
+        Some(rdr.read_i64::<LittleEndian>()?)
     })
 }
 
-fn parse_real(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<HdbValue<'static>> {
-    let mut vec: Vec<u8> = std::iter::repeat(0u8).take(4).collect();
-    rdr.read_exact(&mut vec[..])?;
-    let mut cursor = std::io::Cursor::new(&vec);
-    let tmp = cursor.read_u32::<LittleEndian>()?;
-    let is_null = tmp == std::u32::MAX;
-
-    if is_null {
+pub(crate) fn parse_real(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<Option<f32>> {
+    let bits = rdr.read_u32::<LittleEndian>()?;
+    if bits == std::u32::MAX {
         if nullable {
-            Ok(HdbValue::NULL)
+            Ok(None)
         } else {
             Err(HdbError::Impl(
                 "found NULL value for NOT NULL column".to_owned(),
+                None,
             ))
         }
     } else {
-        cursor.set_position(0);
-        Ok(HdbValue::REAL(cursor.read_f32::<LittleEndian>()?))
+        Ok(Some(f32::from_bits(bits)))
     }
 }
 
-fn parse_double(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<HdbValue<'static>> {
-    let mut vec: Vec<u8> = std::iter::repeat(0u8).take(8).collect();
-    rdr.read_exact(&mut vec[..])?;
-    let mut cursor = std::io::Cursor::new(&vec);
-    let tmp = cursor.read_u64::<LittleEndian>()?;
-    let is_null = tmp == std::u64::MAX;
-
-    if is_null {
+pub(crate) fn parse_double(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<Option<f64>> {
+    let bits = rdr.read_u64::<LittleEndian>()?;
+    if bits == std::u64::MAX {
         if nullable {
-            Ok(HdbValue::NULL)
+            Ok(None)
         } else {
             Err(HdbError::Impl(
                 "found NULL value for NOT NULL column".to_owned(),
+                None,
             ))
         }
     } else {
-        cursor.set_position(0);
-        Ok(HdbValue::DOUBLE(cursor.read_f64::<LittleEndian>()?))
+        Ok(Some(f64::from_bits(bits)))
     }
 }
 
-fn parse_bool(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<HdbValue<'static>> {
+pub(crate) fn parse_bool(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<Option<bool>> {
     //(0x00 = FALSE, 0x01 = NULL, 0x02 = TRUE)
     match rdr.read_u8()? {
-        0 => Ok(HdbValue::BOOLEAN(false)),
-        2 => Ok(HdbValue::BOOLEAN(true)),
+        0 => Ok(Some(false)),
+        2 => Ok(Some(true)),
         1 => {
             if nullable {
-                Ok(HdbValue::NULL)
+                Ok(None)
             } else {
-                Err(HdbError::Impl("parse_bool: got null value".to_string()))
+                Err(HdbError::Impl(
+                    "parse_bool: got null value".to_string(),
+                    None,
+                ))
             }
         }
-        i => Err(HdbError::Impl(format!("parse_bool: got bad value {}", i))),
+        i => Err(HdbError::Impl(
+            format!("parse_bool: got bad value {}", i),
+            None,
+        )),
     }
 }
 
-fn parse_alphanum(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<HdbValue<'static>> {
+pub(crate) fn parse_alphanum(
+    nullable: bool,
+    rdr: &mut std::io::BufRead,
+) -> HdbResult<Option<String>> {
     let indicator1 = rdr.read_u8()?;
     if indicator1 == LENGTH_INDICATOR_NULL {
         // value is null
         if nullable {
-            Ok(HdbValue::NULL)
+            Ok(None)
         } else {
             Err(HdbError::Impl(
                 "found NULL value for NOT NULL ALPHANUM column".to_owned(),
+                None,
             ))
         }
     } else {
@@ -490,65 +563,51 @@ fn parse_alphanum(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<HdbVa
             prefix.append(&mut value);
             prefix
         })?;
-        Ok(HdbValue::STRING(s))
+        Ok(Some(s))
     }
 }
 
-fn parse_string(
+pub(crate) fn parse_string(
     nullable: bool,
-    type_id: TypeId,
     rdr: &mut std::io::BufRead,
-) -> HdbResult<HdbValue<'static>> {
+) -> HdbResult<Option<String>> {
     let l8 = rdr.read_u8()?; // B1
     let is_null = l8 == LENGTH_INDICATOR_NULL;
 
     if is_null {
         if nullable {
-            Ok(HdbValue::NULL)
+            Ok(None)
         } else {
             Err(HdbError::Impl(
                 "found NULL value for NOT NULL string column".to_owned(),
+                None,
             ))
         }
     } else {
-        let s = util::string_from_cesu8(parse_length_and_bytes(l8, rdr)?)?;
-        Ok(match type_id {
-            TypeId::CHAR
-            | TypeId::VARCHAR
-            | TypeId::NCHAR
-            | TypeId::NVARCHAR
-            | TypeId::NSTRING
-            | TypeId::SHORTTEXT
-            | TypeId::STRING => HdbValue::STRING(s),
-            _ => return Err(HdbError::Impl("unexpected type id for string".to_owned())),
-        })
+        Ok(Some(util::string_from_cesu8(parse_length_and_bytes(
+            l8, rdr,
+        )?)?))
     }
 }
 
-fn parse_binary(
+pub(crate) fn parse_binary(
     nullable: bool,
-    type_id: TypeId,
     rdr: &mut std::io::BufRead,
-) -> HdbResult<HdbValue<'static>> {
+) -> HdbResult<Option<Vec<u8>>> {
     let l8 = rdr.read_u8()?; // B1
     let is_null = l8 == LENGTH_INDICATOR_NULL;
 
     if is_null {
         if nullable {
-            Ok(HdbValue::NULL)
+            Ok(None)
         } else {
             Err(HdbError::Impl(
                 "found NULL value for NOT NULL binary column".to_owned(),
+                None,
             ))
         }
     } else {
-        let bytes = parse_length_and_bytes(l8, rdr)?;
-        Ok(match type_id {
-            TypeId::BSTRING | TypeId::VARBINARY | TypeId::BINARY => HdbValue::BINARY(bytes),
-            TypeId::GEOMETRY => HdbValue::GEOMETRY(bytes),
-            TypeId::POINT => HdbValue::POINT(bytes),
-            _ => return Err(HdbError::Impl("unexpected type id for binary".to_owned())),
-        })
+        Ok(Some(parse_length_and_bytes(l8, rdr)?))
     }
 }
 
@@ -558,10 +617,10 @@ fn parse_length_and_bytes(l8: u8, rdr: &mut std::io::BufRead) -> HdbResult<Vec<u
         LENGTH_INDICATOR_2BYTE => rdr.read_i16::<LittleEndian>()? as usize, // I2
         LENGTH_INDICATOR_4BYTE => rdr.read_i32::<LittleEndian>()? as usize, // I4
         l => {
-            return Err(HdbError::Impl(format!(
-                "Unexpected value in length indicator: {}",
-                l
-            )));
+            return Err(HdbError::Impl(
+                format!("Unexpected value in length indicator: {}", l),
+                None,
+            ));
         }
     };
     util::parse_bytes(len, rdr)
@@ -711,7 +770,8 @@ mod test {
             // HdbValue::GEOMETRY(ref vec),
             // HdbValue::POINT(ref vec),
         ] {
-            let _s = value.to_string();
+            let s = value.to_string();
+            assert_eq!(value.try_clone().unwrap().to_string(), s);
         }
     }
 }
@@ -1,9 +1,11 @@
 pub mod authfields;
 pub mod client_context;
 pub mod client_info;
+pub mod column_batch;
 pub mod command_info;
 pub mod commit_options;
 pub mod connect_options;
+pub mod db_connect_info;
 pub mod execution_result;
 pub mod fetch_options;
 pub mod hdb_value;
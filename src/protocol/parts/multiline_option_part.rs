@@ -21,4 +21,9 @@ impl<T: OptionId<T> + Debug + Eq + PartialEq + Hash> MultilineOptionPart<T> {
         }
         Ok(MultilineOptionPart::<T>(option_parts))
     }
+
+    #[cfg(feature = "alpha_routing")]
+    pub fn iter(&self) -> std::slice::Iter<'_, OptionPart<T>> {
+        self.0.iter()
+    }
 }
@@ -33,6 +33,10 @@ impl<T: OptionId<T> + Debug + Eq + PartialEq + Hash> OptionPart<T> {
         self.0.get(id)
     }
 
+    pub fn remove_value(&mut self, id: &T) -> Option<OptionValue> {
+        self.0.remove(id)
+    }
+
     pub fn count(&self) -> usize {
         self.0.len()
     }
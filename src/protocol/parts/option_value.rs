@@ -63,10 +63,13 @@ impl OptionValue {
             28 => Ok(OptionValue::BOOLEAN(rdr.read_u8()? > 0)),         // B1
             29 => Ok(OptionValue::STRING(parse_length_and_string(rdr)?)),
             33 => Ok(OptionValue::BSTRING(parse_length_and_binary(rdr)?)),
-            _ => Err(HdbError::Impl(format!(
-                "OptionValue::parse_value() not implemented for type code {}",
-                typecode
-            ))),
+            _ => Err(HdbError::Impl(
+                format!(
+                    "OptionValue::parse_value() not implemented for type code {}",
+                    typecode
+                ),
+                None,
+            )),
         }
     }
 }
@@ -4,7 +4,7 @@ use crate::{HdbError, HdbResult};
 use byteorder::{LittleEndian, ReadBytesExt};
 use std::u32;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(crate) struct ParameterDescriptors(Vec<ParameterDescriptor>);
 impl ParameterDescriptors {
     pub fn iter_in(&self) -> impl std::iter::Iterator<Item = &ParameterDescriptor> {
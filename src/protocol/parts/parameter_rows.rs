@@ -1,6 +1,8 @@
 use super::hdb_value::HdbValue;
+use crate::impl_serde_db::ser::to_named_params;
+use crate::named_parameters::with_parameter_name;
 use crate::protocol::parts::parameter_descriptor::ParameterDescriptors;
-use crate::{HdbError, HdbResult};
+use crate::{ErrorContext, HdbError, HdbResult};
 use serde_db::ser::to_params;
 use std::io::Write;
 
@@ -18,6 +20,28 @@ impl<'a> ParameterRows<'a> {
         self.0.is_empty()
     }
 
+    // Copies the rows; fails if they contain LOB handles.
+    pub fn try_clone(&self) -> HdbResult<ParameterRows<'a>> {
+        Ok(ParameterRows(
+            self.0
+                .iter()
+                .map(|row| {
+                    row.0
+                        .iter()
+                        .map(HdbValue::try_clone)
+                        .collect::<HdbResult<_>>()
+                        .map(ParameterRow)
+                })
+                .collect::<HdbResult<_>>()?,
+        ))
+    }
+
+    // The input values of the rows.
+    #[cfg_attr(not(feature = "alpha_routing"), allow(dead_code))]
+    pub fn iter(&self) -> impl Iterator<Item = &[HdbValue<'a>]> {
+        self.0.iter().map(|row| row.0.as_slice())
+    }
+
     pub(crate) fn push_hdb_values(
         &mut self,
         hdb_parameters: Vec<HdbValue<'a>>,
@@ -39,6 +63,52 @@ impl<'a> ParameterRows<'a> {
         Ok(())
     }
 
+    // Splits the rows into chunks with at most max_rows rows, and with at most
+    // max_bytes bytes, unless a single row is bigger.
+    pub(crate) fn split(
+        self,
+        descriptors: &ParameterDescriptors,
+        max_rows: usize,
+        max_bytes: usize,
+    ) -> HdbResult<Vec<ParameterRows<'a>>> {
+        let mut chunks = Vec::<ParameterRows>::new();
+        let mut chunk = Vec::<ParameterRow>::new();
+        let mut chunk_size = 0;
+        for row in self.0 {
+            let row_size = row.size(descriptors)?;
+            if !chunk.is_empty() && (chunk.len() >= max_rows || chunk_size + row_size > max_bytes) {
+                chunks.push(ParameterRows(std::mem::take(&mut chunk)));
+                chunk_size = 0;
+            }
+            chunk_size += row_size;
+            chunk.push(row);
+        }
+        if !chunk.is_empty() || chunks.is_empty() {
+            chunks.push(ParameterRows(chunk));
+        }
+        Ok(chunks)
+    }
+
+    // Binds the rows to other descriptors, e.g. of a statement that was prepared again.
+    pub(crate) fn rebind(self, descriptors: &ParameterDescriptors) -> HdbResult<ParameterRows<'a>> {
+        let no_of_in_pars = descriptors.iter_in().count();
+        let mut rows = ParameterRows::new();
+        for row in self.0 {
+            if row.0.len() != no_of_in_pars {
+                return Err(HdbError::Usage(
+                    "the number of parameters changed when the statement was prepared again"
+                        .to_string(),
+                ));
+            }
+            rows.push_hdb_values(row.0, descriptors)?;
+        }
+        Ok(rows)
+    }
+
+    pub(crate) fn into_rows(self) -> Vec<Vec<HdbValue<'a>>> {
+        self.0.into_iter().map(|row| row.0).collect()
+    }
+
     pub(crate) fn count(&self) -> usize {
         self.0.len()
     }
@@ -64,6 +134,20 @@ impl ParameterRows<'static> {
         )?);
         Ok(())
     }
+
+    // Binds the fields of the input to the parameters with the same name.
+    pub(crate) fn push_named<T: serde::ser::Serialize>(
+        &mut self,
+        input: &T,
+        names: &[String],
+        descriptors: &ParameterDescriptors,
+    ) -> HdbResult<()> {
+        self.0.push(
+            ParameterRow::new(to_named_params(input, names, descriptors)?, descriptors)
+                .map_err(|e| with_parameter_name(e, names, descriptors))?,
+        );
+        Ok(())
+    }
 }
 
 // A single row of parameters.
@@ -77,16 +161,18 @@ impl<'a> ParameterRow<'a> {
         descriptors: &ParameterDescriptors,
     ) -> HdbResult<ParameterRow<'a>> {
         let mut in_descriptors = descriptors.iter_in();
-        for hdb_value in &hdb_parameters {
+        for (idx, hdb_value) in hdb_parameters.iter().enumerate() {
             if let Some(descriptor) = in_descriptors.next() {
                 if !hdb_value.is_null() {
-                    descriptor
-                        .type_id()
-                        .matches_value_type(hdb_value.type_id_for_emit(descriptor.type_id())?)?;
+                    hdb_value
+                        .type_id_for_emit(descriptor.type_id())
+                        .and_then(|type_id| descriptor.type_id().matches_value_type(type_id))
+                        .map_err(|e| e.with_context(ErrorContext::for_parameter(idx)))?;
                 }
             } else {
                 return Err(HdbError::Impl(
                     "ParameterRow::new(): Not enough metadata".to_string(),
+                    None,
                 ));
             }
         }
@@ -104,6 +190,7 @@ impl<'a> ParameterRow<'a> {
                 None => {
                     return Err(HdbError::Impl(
                         "ParameterRow::size(): Not enough metadata".to_string(),
+                        None,
                     ));
                 }
             }
@@ -115,15 +202,18 @@ impl<'a> ParameterRow<'a> {
     fn emit<T: Write>(&self, descriptors: &ParameterDescriptors, w: &mut T) -> HdbResult<()> {
         let mut data_pos = 0_i32;
         let mut in_descriptors = descriptors.iter_in();
-        for value in &(self.0) {
+        for (idx, value) in self.0.iter().enumerate() {
             // emit the value
             match in_descriptors.next() {
                 Some(descriptor) => {
-                    value.emit(&mut data_pos, descriptor, w)?;
+                    value
+                        .emit(&mut data_pos, descriptor, w)
+                        .map_err(|e| e.with_context(ErrorContext::for_parameter(idx)))?;
                 }
                 None => {
                     return Err(HdbError::Impl(
                         "ParameterRow::emit(): Not enough metadata".to_string(),
+                        None,
                     ));
                 }
             }
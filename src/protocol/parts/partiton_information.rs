@@ -1,3 +1,5 @@
+use crate::protocol::parts::hdb_value::HdbValue;
+use crate::protocol::parts::parameter_descriptor::{ParameterDescriptors, ParameterDirection};
 use crate::protocol::util;
 use crate::{HdbError, HdbResult};
 
@@ -10,7 +12,7 @@ pub struct PartitionInformation {
     partitions: Vec<Partitions>,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum PartitionMethod {
     Invalid,
     RoundRobin,
@@ -23,10 +25,10 @@ impl PartitionMethod {
             0 => Ok(PartitionMethod::Invalid),
             1 => Ok(PartitionMethod::RoundRobin),
             2 => Ok(PartitionMethod::Hash),
-            _ => Err(HdbError::Impl(format!(
-                "PartitionMethod {} not implemented",
-                val
-            ))),
+            _ => Err(HdbError::Impl(
+                format!("PartitionMethod {} not implemented", val),
+                None,
+            )),
         }
     }
 }
@@ -44,10 +46,10 @@ impl ParameterFunction {
             0 => Ok(ParameterFunction::Invalid),
             1 => Ok(ParameterFunction::Year),
             2 => Ok(ParameterFunction::Month),
-            _ => Err(HdbError::Impl(format!(
-                "ParameterFunction {} not implemented",
-                val
-            ))),
+            _ => Err(HdbError::Impl(
+                format!("ParameterFunction {} not implemented", val),
+                None,
+            )),
         }
     }
 }
@@ -59,6 +61,8 @@ pub struct ParameterDescriptor {
     attribute_type: i8,
 }
 
+// The documentation does not describe the two values; we assume that the first one
+// identifies the partition and the second one the volume that holds it.
 #[derive(Debug, Clone, Copy)]
 pub struct Partitions {
     val1: i32,
@@ -66,6 +70,50 @@ pub struct Partitions {
 }
 
 impl PartitionInformation {
+    // The ids of the volumes that hold the partitions, if the table is partitioned.
+    pub fn partition_volumes(&self) -> Option<Vec<i32>> {
+        if self.partition_method == PartitionMethod::Invalid || self.partitions.is_empty() {
+            None
+        } else {
+            Some(self.partitions.iter().map(|p| p.val2).collect())
+        }
+    }
+
+    // The id of the volume that holds the partition of a row with the given input values,
+    // in the order of the input parameters.
+    //
+    // Only hash partitioning assigns rows to partitions by their values. HANA does not
+    // document its hash function; we hash a canonical representation of the values with
+    // FNV-1a. If this does not match the server's assignment, an execution is routed
+    // to an index server that has to access the partition remotely, which costs
+    // performance, but does not affect the result.
+    pub fn volume_of(
+        &self,
+        in_values: &[HdbValue],
+        descriptors: &ParameterDescriptors,
+    ) -> Option<i32> {
+        if self.partition_method != PartitionMethod::Hash
+            || self.partitions.is_empty()
+            || self.parameter_descriptor.is_empty()
+        {
+            return None;
+        }
+        let mut key = Vec::<u8>::new();
+        for desc in &self.parameter_descriptor {
+            // the parameter index is one-based and counts all parameters
+            let idx = (desc.parameter_index as usize).checked_sub(1)?;
+            let in_idx = descriptors
+                .ref_inner()
+                .iter()
+                .take(idx)
+                .filter(|d| d.direction() != ParameterDirection::OUT)
+                .count();
+            add_hash_key(in_values.get(in_idx)?, desc.parameter_function, &mut key)?;
+        }
+        let idx = (fnv1a(&key) % self.partitions.len() as u64) as usize;
+        Some(self.partitions[idx].val2)
+    }
+
     pub fn parse<T: std::io::BufRead>(rdr: &mut T) -> HdbResult<PartitionInformation> {
         let partition_method = PartitionMethod::from_i8(rdr.read_i8()?)?; // I1
         util::skip_bytes(7, rdr)?;
@@ -102,3 +150,63 @@ impl PartitionInformation {
         })
     }
 }
+
+// Appends the canonical representation of the value, after applying the function,
+// to the key; returns None for values that cannot be assigned to a partition.
+fn add_hash_key(value: &HdbValue, function: ParameterFunction, key: &mut Vec<u8>) -> Option<()> {
+    let number: i64 = match (function, value) {
+        (ParameterFunction::Year, value) => i64::from(year_month(value)?.0),
+        (ParameterFunction::Month, value) => {
+            let (year, month) = year_month(value)?;
+            i64::from(year) * 100 + i64::from(month)
+        }
+        (_, HdbValue::TINYINT(i)) => i64::from(*i),
+        (_, HdbValue::SMALLINT(i)) => i64::from(*i),
+        (_, HdbValue::INT(i)) => i64::from(*i),
+        (_, HdbValue::BIGINT(i)) => *i,
+        (_, HdbValue::BOOLEAN(b)) => i64::from(*b),
+        (_, HdbValue::LONGDATE(ld)) => *ld.ref_raw(),
+        (_, HdbValue::SECONDDATE(sd)) => *sd.ref_raw(),
+        (_, HdbValue::DAYDATE(dd)) => i64::from(*dd.ref_raw()),
+        (_, HdbValue::SECONDTIME(st)) => i64::from(*st.ref_raw()),
+        (_, HdbValue::REAL(f)) => f64::from(*f).to_bits() as i64,
+        (_, HdbValue::DOUBLE(f)) => f.to_bits() as i64,
+        (_, HdbValue::STR(s)) => return add_bytes(s.as_bytes(), key),
+        (_, HdbValue::STRING(s)) => return add_bytes(s.as_bytes(), key),
+        (_, HdbValue::BINARY(b)) => return add_bytes(b, key),
+        (_, HdbValue::DECIMAL(bd)) => return add_bytes(bd.to_string().as_bytes(), key),
+        _ => return None,
+    };
+    key.extend_from_slice(&number.to_le_bytes());
+    Some(())
+}
+
+fn add_bytes(bytes: &[u8], key: &mut Vec<u8>) -> Option<()> {
+    key.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+    key.extend_from_slice(bytes);
+    Some(())
+}
+
+fn year_month(value: &HdbValue) -> Option<(i32, u32)> {
+    match value {
+        HdbValue::LONGDATE(ld) => {
+            let (y, m, ..) = ld.as_ymd_hms_f();
+            Some((y, m))
+        }
+        HdbValue::SECONDDATE(sd) => {
+            let (y, m, ..) = sd.as_ymd_hms();
+            Some((y, m))
+        }
+        HdbValue::DAYDATE(dd) => {
+            let (y, m, _) = dd.as_ymd();
+            Some((y, m))
+        }
+        _ => None,
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
@@ -74,6 +74,17 @@ impl ResultSetMetadata {
             .ok_or_else(|| HdbError::usage_("get_fieldname(): invalid field index"))?)
     }
 
+    // Returns the index of the column with the given name;
+    // display names (which reflect aliases) take precedence over column names.
+    pub(crate) fn index_of(&self, name: &str) -> Option<usize> {
+        let by_displayname = (0..self.number_of_fields())
+            .find(|i| matches!(self.displayname(*i), Ok(dn) if dn == name));
+        by_displayname.or_else(|| {
+            (0..self.number_of_fields())
+                .find(|i| matches!(self.columnname(*i), Ok(cn) if cn == name))
+        })
+    }
+
     /// True if column can contain NULL values.
     pub fn nullable(&self, i: usize) -> HdbResult<bool> {
         Ok(self.get(i)?.is_nullable())
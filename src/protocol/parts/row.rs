@@ -1,11 +1,11 @@
 use crate::conn_core::AmConnCore;
+use crate::impl_serde_db::de::row_into_typed;
 use crate::protocol::parts::hdb_value::HdbValue;
 use crate::protocol::parts::resultset::AmRsCore;
 use crate::protocol::parts::resultset_metadata::ResultSetMetadata;
-use crate::{HdbError, HdbResult};
+use crate::{ErrorContext, HdbError, HdbResult};
 
 use serde;
-use serde_db::de::DeserializableRow;
 use std::fmt;
 use std::sync::Arc;
 
@@ -16,6 +16,9 @@ use std::sync::Arc;
 ///
 /// You also can access individual values with `row[idx]`, or iterate over the values (with
 /// `row.iter()` or `for value in row {...}`).
+///
+/// With [`get()`](#method.get) and [`get_by_index()`](#method.get_by_index),
+/// individual values can be converted into rust values without consuming them.
 #[derive(Debug)]
 pub struct Row {
     metadata: Arc<ResultSetMetadata>,
@@ -37,7 +40,67 @@ impl Row {
         T: serde::de::Deserialize<'de>,
     {
         trace!("Row::into_typed()");
-        Ok(DeserializableRow::into_typed(self)?)
+        row_into_typed(self)
+    }
+
+    /// Converts the value of the column with the given name into a rust value,
+    /// without removing it from the row.
+    ///
+    /// The name is compared with the display names of the columns, which reflect aliases
+    /// that are given in the query, and then with the column names.
+    ///
+    /// ```rust,no_run
+    /// # use hdbconnect::{Connection, HdbResult, IntoConnectParams};
+    /// # fn foo() -> HdbResult<()> {
+    /// # let mut connection = Connection::new("".into_connect_params()?)?;
+    /// for row in connection.query("select NAME, AMOUNT as TOTAL from ORDERS")? {
+    ///     let row = row?;
+    ///     let name: String = row.get("NAME")?;
+    ///     let total: Option<f64> = row.get("TOTAL")?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get<T>(&self, column_name: &str) -> HdbResult<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        trace!("Row::get()");
+        let index = self.metadata.index_of(column_name).ok_or_else(|| {
+            HdbError::Usage(format!("Row has no column with name {:?}", column_name))
+        })?;
+        let consumed = self.number_of_fields() - self.len();
+        if index < consumed {
+            return Err(HdbError::Usage(format!(
+                "The value of column {:?} was already removed from the row",
+                column_name
+            )));
+        }
+        self.get_by_index(index - consumed)
+    }
+
+    /// Converts the value with the given index into a rust value,
+    /// without removing it from the row.
+    ///
+    /// Like with `row[idx]`, the index refers to the values that are still in the row.
+    ///
+    /// LOB values are not supported, since they are bound to the connection;
+    /// use [`next_value()`](#method.next_value) or [`try_into()`](#method.try_into) for them.
+    pub fn get_by_index<T>(&self, idx: usize) -> HdbResult<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        trace!("Row::get_by_index()");
+        match self.value_iter.as_slice().get(idx) {
+            Some(value) => value.try_clone()?.try_into().map_err(|e| {
+                let column = self.number_of_fields() - self.len() + idx;
+                e.with_context(ErrorContext::for_column(column))
+            }),
+            None => Err(HdbError::Usage(format!(
+                "Row has no value with index {}",
+                idx
+            ))),
+        }
     }
 
     /// Removes and returns the next value.
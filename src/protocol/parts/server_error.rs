@@ -1,5 +1,5 @@
 use crate::protocol::util;
-use crate::HdbResult;
+use crate::{ErrorContext, HdbErrorKind, HdbResult};
 use byteorder::{LittleEndian, ReadBytesExt};
 use std::error::Error;
 use std::fmt;
@@ -52,12 +52,14 @@ impl fmt::Display for Severity {
 }
 
 /// Describes an error that is reported from the database.
+#[derive(Clone)]
 pub struct ServerError {
     code: i32,
     position: i32,
     severity: Severity,
     sqlstate: Vec<u8>,
     text: String,
+    o_context: Option<Box<ErrorContext>>,
 }
 const BASE_SIZE: i32 = 4 + 4 + 4 + 1 + 5;
 
@@ -66,6 +68,10 @@ impl ServerError {
     pub fn code(&self) -> i32 {
         self.code
     }
+    /// Returns the classification of the error code.
+    pub fn kind(&self) -> HdbErrorKind {
+        HdbErrorKind::from_code(self.code)
+    }
     /// Returns the position in the line where the error occured.
     pub fn position(&self) -> i32 {
         self.position
@@ -82,6 +88,10 @@ impl ServerError {
     pub fn text(&self) -> String {
         self.text.clone()
     }
+    /// Returns the information about the request in which the error occured, if available.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        self.o_context.as_deref()
+    }
 
     pub(crate) fn new(
         code: i32,
@@ -96,6 +106,14 @@ impl ServerError {
             severity,
             sqlstate,
             text,
+            o_context: None,
+        }
+    }
+
+    pub(crate) fn add_context(&mut self, context: ErrorContext) {
+        match self.o_context {
+            Some(ref mut own_context) => own_context.merge(context),
+            None => self.o_context = Some(Box::new(context)),
         }
     }
 
@@ -146,7 +164,11 @@ impl fmt::Display for ServerError {
             String::from_utf8_lossy(&self.sqlstate),
             self.position(),
             self.text
-        )
+        )?;
+        if let Some(ref context) = self.o_context {
+            write!(fmt, " ({})", context)?;
+        }
+        Ok(())
     }
 }
 
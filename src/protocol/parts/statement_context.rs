@@ -21,6 +21,29 @@ impl StatementContext {
         );
     }
 
+    pub fn remove_statement_sequence_info(&mut self) {
+        self.remove_value(&StatementContextId::StatementSequenceInfo);
+    }
+
+    pub fn get_query_timeout(&self) -> Option<i64> {
+        match self.get_value(&StatementContextId::QueryTimeout) {
+            Some(&OptionValue::BIGINT(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    // The query timeout is sent in seconds.
+    pub fn set_query_timeout(&mut self, value: i64) {
+        self.set_value(StatementContextId::QueryTimeout, OptionValue::BIGINT(value));
+    }
+
+    pub fn get_schema_name(&self) -> Option<&str> {
+        match self.get_value(&StatementContextId::SchemaName) {
+            Some(OptionValue::STRING(value)) => Some(value),
+            _ => None,
+        }
+    }
+
     pub fn get_server_processing_time(&self) -> Option<i32> {
         match self.get_value(&StatementContextId::ServerProcessingTime) {
             Some(&OptionValue::INT(value)) => Some(value),
@@ -1,8 +1,48 @@
 use crate::protocol::parts::multiline_option_part::MultilineOptionPart;
 use crate::protocol::parts::option_part::OptionId;
+#[cfg(feature = "alpha_routing")]
+use crate::protocol::parts::option_part::OptionPart;
+#[cfg(feature = "alpha_routing")]
+use crate::protocol::parts::option_value::OptionValue;
 
 pub type Topology = MultilineOptionPart<TopologyAttrId>;
 
+// Each line of the topology describes one service of the database.
+#[cfg(feature = "alpha_routing")]
+impl Topology {
+    // Host and port of the service that holds the given volume.
+    pub fn location_of_volume(&self, volume_id: i32) -> Option<(String, u16)> {
+        let location = self.line_of_volume(volume_id)?;
+        match (
+            location.get_value(&TopologyAttrId::HostName),
+            location.get_value(&TopologyAttrId::HostPortNumber),
+        ) {
+            (Some(OptionValue::STRING(host)), Some(OptionValue::INT(port))) => {
+                Some((host.clone(), *port as u16))
+            }
+            _ => None,
+        }
+    }
+
+    // Whether the given volume belongs to the service to which the current session
+    // is connected.
+    pub fn is_current_session_volume(&self, volume_id: i32) -> bool {
+        match self.line_of_volume(volume_id) {
+            Some(location) => matches!(
+                location.get_value(&TopologyAttrId::IsCurrentSession),
+                Some(OptionValue::BOOLEAN(true))
+            ),
+            None => false,
+        }
+    }
+
+    fn line_of_volume(&self, volume_id: i32) -> Option<&OptionPart<TopologyAttrId>> {
+        self.iter().find(|line| {
+            line.get_value(&TopologyAttrId::VolumeID) == Some(&OptionValue::INT(volume_id))
+        })
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum TopologyAttrId {
     HostName,         //  1 // host name
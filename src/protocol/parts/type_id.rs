@@ -165,7 +165,7 @@ impl TypeId {
             81 => TypeId::FIXED8,
             82 => TypeId::FIXED12,
             // TypeCode_CIPHERTEXT               = 90,  // FIXME
-            tc => return Err(HdbError::Impl(format!("Illegal type code {}", tc))),
+            tc => return Err(HdbError::Impl(format!("Illegal type code {}", tc), None)),
         })
     }
 
@@ -237,9 +237,12 @@ impl TypeId {
             _ => {}
         }
 
-        Err(HdbError::Impl(format!(
-            "value type id {:?} does not match metadata {:?}",
-            value_type, self
-        )))
+        Err(HdbError::Impl(
+            format!(
+                "value type id {:?} does not match metadata {:?}",
+                value_type, self
+            ),
+            None,
+        ))
     }
 }
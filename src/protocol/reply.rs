@@ -5,9 +5,10 @@ use crate::protocol::part::{Part, Parts};
 use crate::protocol::part_attributes::PartAttributes;
 use crate::protocol::partkind::PartKind;
 use crate::protocol::parts::parameter_descriptor::ParameterDescriptors;
-use crate::protocol::parts::resultset::ResultSet;
+use crate::protocol::parts::resultset::{FetchOverrides, ResultSet};
 use crate::protocol::parts::resultset_metadata::ResultSetMetadata;
 use crate::protocol::reply_type::ReplyType;
+use crate::protocol::request::PACKET_OPTION_COMPRESSED;
 use crate::protocol::util;
 use crate::{HdbError, HdbResponse, HdbResult};
 use byteorder::{LittleEndian, ReadBytesExt};
@@ -48,20 +49,33 @@ impl Reply {
         rdr: &mut T,
     ) -> HdbResult<Reply> {
         trace!("Reply::parse()");
-        let (no_of_parts, mut reply) = parse_message_and_sequence_header(rdr)?;
-
-        for i in 0..no_of_parts {
-            let part = Part::parse(
-                &mut (reply.parts),
-                o_am_conn_core,
+        let (session_id, o_uncompressed_size) = parse_message_header(rdr)?;
+        let reply = match o_uncompressed_size {
+            Some((varpart_size, uncompressed_size)) => {
+                let mut compressed = vec![0_u8; varpart_size as usize];
+                rdr.read_exact(&mut compressed)?;
+                let varpart = lz4_flex::block::decompress(&compressed, uncompressed_size as usize)
+                    .map_err(|e| {
+                        HdbError::Impl(format!("decompression of reply failed: {}", e), None)
+                    })?;
+                parse_segment(
+                    session_id,
+                    o_rs_md,
+                    o_descriptors,
+                    o_rs,
+                    o_am_conn_core,
+                    &mut std::io::Cursor::new(varpart),
+                )?
+            }
+            None => parse_segment(
+                session_id,
                 o_rs_md,
                 o_descriptors,
                 o_rs,
-                i == no_of_parts - 1,
+                o_am_conn_core,
                 rdr,
-            )?;
-            reply.push(part);
-        }
+            )?,
+        };
 
         // Make sure that here (after parsing) the buffer is empty.
         // The following only works with nightly, because `.buffer()`
@@ -93,10 +107,10 @@ impl Reply {
         if self.replytype == *reply_type {
             Ok(()) // we got what we expected
         } else {
-            Err(HdbError::Impl(format!(
-                "unexpected reply_type {:?}",
-                self.replytype
-            )))
+            Err(HdbError::Impl(
+                format!("unexpected reply_type {:?}", self.replytype),
+                None,
+            ))
         }
     }
 
@@ -110,9 +124,26 @@ impl Reply {
             .map(|p| p.into_arg())
     }
 
-    pub fn into_hdbresponse(mut self, am_conn_core: &mut AmConnCore) -> HdbResult<HdbResponse> {
+    pub fn into_hdbresponse(self, am_conn_core: &mut AmConnCore) -> HdbResult<HdbResponse> {
+        self.into_hdbresponse_with(am_conn_core, FetchOverrides::default())
+    }
+
+    // The resultsets of the response capture the fetch size and lob read length
+    // of the statement, or else those of the connection.
+    pub fn into_hdbresponse_with(
+        mut self,
+        am_conn_core: &mut AmConnCore,
+        overrides: FetchOverrides,
+    ) -> HdbResult<HdbResponse> {
         // digest parts, collect InternalReturnValues
         let mut conn_core = am_conn_core.lock()?;
+        let fetch_size = overrides
+            .o_fetch_size
+            .unwrap_or_else(|| conn_core.get_fetch_size());
+        let lob_read_length = overrides
+            .o_lob_read_length
+            .unwrap_or_else(|| conn_core.get_lob_read_length());
+        let read_ahead = conn_core.is_read_ahead();
         let mut int_return_values = Vec::<InternalReturnValue>::new();
         self.parts.reverse(); // digest the last part first
         while let Some(part) = self.parts.pop() {
@@ -152,6 +183,7 @@ impl Reply {
                     _ => {
                         return Err(HdbError::Impl(
                             "Missing required part ResultSetID".to_owned(),
+                            None,
                         ));
                     }
                 },
@@ -169,6 +201,13 @@ impl Reply {
             }
         }
 
+        std::mem::drop(conn_core);
+        for int_return_value in &mut int_return_values {
+            if let InternalReturnValue::ResultSet(ref mut rs) = int_return_value {
+                rs.capture_fetch_settings(fetch_size, lob_read_length, read_ahead)?;
+            }
+        }
+
         // re-pack InternalReturnValues into appropriate HdbResponse
         trace!(
             "Reply::into_hdbresponse(): building HdbResponse for a reply of type {:?}",
@@ -232,25 +271,79 @@ impl Drop for Reply {
     }
 }
 
-fn parse_message_and_sequence_header<T: std::io::BufRead>(rdr: &mut T) -> HdbResult<(i16, Reply)> {
+// Returns the session id and, if the variable part of the message is compressed,
+// its size on the wire and its uncompressed size.
+fn parse_message_header<T: std::io::BufRead>(rdr: &mut T) -> HdbResult<(i64, Option<(u32, u32)>)> {
     // MESSAGE HEADER: 32 bytes
     let session_id: i64 = rdr.read_i64::<LittleEndian>()?; // I8
     let packet_seq_number: i32 = rdr.read_i32::<LittleEndian>()?; // I4
-    let varpart_size: u32 = rdr.read_u32::<LittleEndian>()?; // UI4  not needed?
+    let varpart_size: u32 = rdr.read_u32::<LittleEndian>()?; // UI4
     let remaining_bufsize: u32 = rdr.read_u32::<LittleEndian>()?; // UI4  not needed?
     let no_of_segs = rdr.read_i16::<LittleEndian>()?; // I2
     if no_of_segs == 0 {
         return Err(HdbError::Impl(
             "empty response (is ok for drop connection)".to_owned(),
+            None,
         ));
     }
 
     if no_of_segs > 1 {
-        return Err(HdbError::Impl(format!("no_of_segs = {} > 1", no_of_segs)));
+        return Err(HdbError::Impl(
+            format!("no_of_segs = {} > 1", no_of_segs),
+            None,
+        ));
+    }
+
+    let packet_options = rdr.read_u8()?; // I1
+    util::skip_bytes(1, rdr)?; // B[1]
+    let uncompressed_varpart_size = rdr.read_u32::<LittleEndian>()?; // UI4
+    util::skip_bytes(4, rdr)?; // B[4]
+
+    trace!(
+        "message header: {{ packet_seq_number = {}, varpart_size = {}, \
+         remaining_bufsize = {}, packet_options = {} }}",
+        packet_seq_number,
+        varpart_size,
+        remaining_bufsize,
+        packet_options
+    );
+
+    if packet_options & PACKET_OPTION_COMPRESSED == 0 {
+        Ok((session_id, None))
+    } else {
+        Ok((session_id, Some((varpart_size, uncompressed_varpart_size))))
     }
+}
+
+fn parse_segment<T: std::io::BufRead>(
+    session_id: i64,
+    o_rs_md: Option<&ResultSetMetadata>,
+    o_descriptors: Option<&ParameterDescriptors>,
+    o_rs: &mut Option<&mut ResultSet>,
+    o_am_conn_core: Option<&AmConnCore>,
+    rdr: &mut T,
+) -> HdbResult<Reply> {
+    let (no_of_parts, mut reply) = parse_segment_header(session_id, rdr)?;
 
-    util::skip_bytes(10, rdr)?; // (I1 + B[9])
+    for i in 0..no_of_parts {
+        let part = Part::parse(
+            &mut (reply.parts),
+            o_am_conn_core,
+            o_rs_md,
+            o_descriptors,
+            o_rs,
+            i == no_of_parts - 1,
+            rdr,
+        )?;
+        reply.push(part);
+    }
+    Ok(reply)
+}
 
+fn parse_segment_header<T: std::io::BufRead>(
+    session_id: i64,
+    rdr: &mut T,
+) -> HdbResult<(i16, Reply)> {
     // SEGMENT HEADER: 24 bytes
     rdr.read_i32::<LittleEndian>()?; // I4 seg_size
     rdr.read_i32::<LittleEndian>()?; // I4 seg_offset
@@ -258,14 +351,7 @@ fn parse_message_and_sequence_header<T: std::io::BufRead>(rdr: &mut T) -> HdbRes
     rdr.read_i16::<LittleEndian>()?; // I2 seg_number
     let seg_kind = Kind::from_i8(rdr.read_i8()?)?; // I1
 
-    trace!(
-        "message and segment header: {{ packet_seq_number = {}, varpart_size = {}, \
-         remaining_bufsize = {}, no_of_parts = {} }}",
-        packet_seq_number,
-        varpart_size,
-        remaining_bufsize,
-        no_of_parts
-    );
+    trace!("segment header: {{ no_of_parts = {} }}", no_of_parts);
 
     match seg_kind {
         Kind::Request => Err(HdbError::Usage("Cannot _parse_ a request".to_string())),
@@ -295,10 +381,10 @@ impl Kind {
             1 => Ok(Kind::Request),
             2 => Ok(Kind::Reply),
             5 => Ok(Kind::Error),
-            _ => Err(HdbError::Impl(format!(
-                "reply::Kind {} not implemented",
-                val
-            ))),
+            _ => Err(HdbError::Impl(
+                format!("reply::Kind {} not implemented", val),
+                None,
+            )),
         }
     }
 }
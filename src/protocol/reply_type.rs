@@ -60,10 +60,10 @@ impl ReplyType {
             25 => Ok(ReplyType::XAControl),
             26 => Ok(ReplyType::XAPrepare),
             27 => Ok(ReplyType::XARecover),
-            _ => Err(HdbError::Impl(format!(
-                "found unexpected value {} for ReplyType",
-                val
-            ))),
+            _ => Err(HdbError::Impl(
+                format!("found unexpected value {} for ReplyType", val),
+                None,
+            )),
         }
     }
 }
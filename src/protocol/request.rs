@@ -5,14 +5,20 @@ use super::argument::Argument;
 use super::part::{Part, Parts};
 use super::partkind::PartKind;
 use super::request_type::RequestType;
+use crate::protocol::parts::db_connect_info::DbConnectInfo;
 use crate::protocol::parts::parameter_descriptor::ParameterDescriptors;
 use crate::protocol::parts::statement_context::StatementContext;
-use crate::HdbResult;
+use crate::{ErrorContext, HdbResult};
 use byteorder::{LittleEndian, WriteBytesExt};
+use std::time::Duration;
 
 const MESSAGE_HEADER_SIZE: u32 = 32;
 const SEGMENT_HEADER_SIZE: usize = 24; // same for in and out
 pub const HOLD_CURSORS_OVER_COMMIT: u8 = 8;
+pub const PACKET_OPTION_COMPRESSED: u8 = 2;
+
+// Smaller requests are not compressed.
+const MIN_COMPRESSION_SIZE: u32 = 10 * 1024;
 
 // Packets having the same sequence number belong to one request/response pair.
 #[derive(Debug)]
@@ -20,6 +26,7 @@ pub(crate) struct Request<'a> {
     pub request_type: RequestType,
     command_options: u8,
     parts: Parts<'a>,
+    o_statement: Option<&'a str>, // the SQL text of a prepared statement that is executed
 }
 // Methods for defining a request
 impl<'a> Request<'a> {
@@ -28,6 +35,7 @@ impl<'a> Request<'a> {
             request_type,
             command_options,
             parts: Parts::default(),
+            o_statement: None,
         }
     }
 
@@ -35,49 +43,155 @@ impl<'a> Request<'a> {
         Request::new(RequestType::Disconnect, 0)
     }
 
+    pub fn new_for_db_connect_info(database_name: &str) -> Request<'a> {
+        let mut request = Request::new(RequestType::DbConnectInfo, 0);
+        request.push(Part::new(
+            PartKind::DbConnectInfo,
+            Argument::DbConnectInfo(DbConnectInfo::new(database_name.to_string())),
+        ));
+        request
+    }
+
     pub fn push(&mut self, part: Part<'a>) {
         self.parts.push(part);
     }
 
+    pub fn set_statement(&mut self, stmt: &'a str) {
+        self.o_statement = Some(stmt);
+    }
+
+    // Describes the request for the errors that occur in its roundtrip.
+    pub fn error_context(&self) -> ErrorContext {
+        let o_statement = match self.parts.arg_of_kind(PartKind::Command) {
+            Some(Argument::Command(stmt)) => Some(*stmt),
+            _ => self.o_statement,
+        };
+        ErrorContext::for_request(format!("{:?}", self.request_type), o_statement)
+    }
+
+    pub fn drop_parts_of_kind(&mut self, kind: PartKind) {
+        self.parts.drop_args_of_kind(kind);
+    }
+
     pub fn add_statement_context(&mut self, ssi_value: i64) {
-        let mut stmt_ctx: StatementContext = Default::default();
-        stmt_ctx.set_statement_sequence_info(ssi_value);
         trace!(
             "Sending StatementContext with sequence_info = {:?}",
             ssi_value
         );
-        self.push(Part::new(
-            PartKind::StatementContext,
-            Argument::StatementContext(stmt_ctx),
-        ));
+        self.statement_context_mut()
+            .set_statement_sequence_info(ssi_value);
+    }
+
+    // The statement sequence info belongs to the session, so it must not be sent
+    // again after a reconnect.
+    pub fn drop_statement_sequence_info(&mut self) {
+        let is_empty = match self.parts.arg_of_kind_mut(PartKind::StatementContext) {
+            Some(Argument::StatementContext(stmt_ctx)) => {
+                stmt_ctx.remove_statement_sequence_info();
+                stmt_ctx.count() == 0
+            }
+            _ => false,
+        };
+        if is_empty {
+            self.drop_parts_of_kind(PartKind::StatementContext);
+        }
+    }
+
+    // The server works with full seconds, so we round up.
+    pub fn set_query_timeout(&mut self, query_timeout: Duration) {
+        let mut secs = query_timeout.as_secs();
+        if query_timeout.subsec_nanos() > 0 {
+            secs += 1;
+        }
+        trace!("Sending StatementContext with query timeout = {}s", secs);
+        self.statement_context_mut().set_query_timeout(secs as i64);
+    }
+
+    pub fn has_query_timeout(&self) -> bool {
+        match self.parts.arg_of_kind(PartKind::StatementContext) {
+            Some(Argument::StatementContext(stmt_ctx)) => stmt_ctx.get_query_timeout().is_some(),
+            _ => false,
+        }
+    }
+
+    // All statement context information goes into a single part.
+    fn statement_context_mut(&mut self) -> &mut StatementContext {
+        if self
+            .parts
+            .arg_of_kind_mut(PartKind::StatementContext)
+            .is_none()
+        {
+            self.push(Part::new(
+                PartKind::StatementContext,
+                Argument::StatementContext(StatementContext::default()),
+            ));
+        }
+        match self.parts.arg_of_kind_mut(PartKind::StatementContext) {
+            Some(Argument::StatementContext(stmt_ctx)) => stmt_ctx,
+            _ => unreachable!("StatementContext part was just added"),
+        }
     }
 
     pub fn emit<T: std::io::Write>(
-        self,
+        &self,
         session_id: i64,
         seq_number: i32,
         auto_commit_flag: i8,
+        compress: bool,
         o_descriptors: Option<&ParameterDescriptors>,
         w: &mut T,
     ) -> HdbResult<()> {
         let varpart_size = self.varpart_size(o_descriptors)?;
         let total_size = MESSAGE_HEADER_SIZE + varpart_size;
         trace!("Writing request with total size {}", total_size);
-        let mut remaining_bufsize = total_size - MESSAGE_HEADER_SIZE;
 
         debug!(
             "Request::emit() of type {:?} for session_id = {}, seq_number = {}",
             self.request_type, session_id, seq_number
         );
-        // MESSAGE HEADER
-        w.write_i64::<LittleEndian>(session_id)?; // I8
-        w.write_i32::<LittleEndian>(seq_number)?; // I4
-        w.write_u32::<LittleEndian>(varpart_size)?; // UI4
-        w.write_u32::<LittleEndian>(remaining_bufsize)?; // UI4
-        w.write_i16::<LittleEndian>(1)?; // I2    Number of segments
-        for _ in 0..10 {
-            w.write_u8(0)?;
-        } // I1+ B[9]  unused
+
+        if compress && varpart_size >= MIN_COMPRESSION_SIZE {
+            // the variable part is compressed as a whole, and only if it pays off
+            let mut varpart = Vec::<u8>::with_capacity(varpart_size as usize);
+            self.emit_varpart(varpart_size, auto_commit_flag, o_descriptors, &mut varpart)?;
+            let compressed = lz4_flex::block::compress(&varpart);
+            if compressed.len() < varpart.len() {
+                trace!(
+                    "Compressed the request from {} to {} bytes",
+                    varpart.len(),
+                    compressed.len()
+                );
+                emit_message_header(
+                    session_id,
+                    seq_number,
+                    compressed.len() as u32,
+                    PACKET_OPTION_COMPRESSED,
+                    varpart_size,
+                    w,
+                )?;
+                w.write_all(&compressed)?;
+            } else {
+                emit_message_header(session_id, seq_number, varpart_size, 0, 0, w)?;
+                w.write_all(&varpart)?;
+            }
+        } else {
+            emit_message_header(session_id, seq_number, varpart_size, 0, 0, w)?;
+            self.emit_varpart(varpart_size, auto_commit_flag, o_descriptors, w)?;
+        }
+        w.flush()?;
+        trace!("Parts are written");
+        Ok(())
+    }
+
+    // Writes segment header and parts.
+    fn emit_varpart<T: std::io::Write>(
+        &self,
+        varpart_size: u32,
+        auto_commit_flag: i8,
+        o_descriptors: Option<&ParameterDescriptors>,
+        w: &mut T,
+    ) -> HdbResult<()> {
+        let mut remaining_bufsize = varpart_size;
 
         // SEGMENT HEADER
         let parts_len = self.parts.len() as i16;
@@ -100,8 +214,6 @@ impl<'a> Request<'a> {
         for part in self.parts.ref_inner() {
             remaining_bufsize = part.emit(remaining_bufsize, o_descriptors, w)?;
         }
-        w.flush()?;
-        trace!("Parts are written");
         Ok(())
     }
 
@@ -122,3 +234,27 @@ impl<'a> Request<'a> {
         Ok(len)
     }
 }
+
+// The varpart size is the size of what follows the message header, i.e., if the
+// message is compressed, the size of the compressed data.
+fn emit_message_header<T: std::io::Write>(
+    session_id: i64,
+    seq_number: i32,
+    varpart_size: u32,
+    packet_options: u8,
+    uncompressed_varpart_size: u32,
+    w: &mut T,
+) -> HdbResult<()> {
+    w.write_i64::<LittleEndian>(session_id)?; // I8
+    w.write_i32::<LittleEndian>(seq_number)?; // I4
+    w.write_u32::<LittleEndian>(varpart_size)?; // UI4
+    w.write_u32::<LittleEndian>(varpart_size)?; // UI4  remaining bufsize
+    w.write_i16::<LittleEndian>(1)?; // I2    Number of segments
+    w.write_u8(packet_options)?; // I1    Packet options
+    w.write_u8(0)?; // I1    unused
+    w.write_u32::<LittleEndian>(uncompressed_varpart_size)?; // UI4  0 if not compressed
+    for _ in 0..4 {
+        w.write_u8(0)?;
+    } // B[4]  unused
+    Ok(())
+}
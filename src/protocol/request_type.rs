@@ -19,19 +19,19 @@ pub enum RequestType {
     CloseResultSet,  // Closes resultset
     DropStatementId, // Drops prepared statement identifier
     FetchNext,       // Fetches next data from resultset
-    // FetchAbsolute,   // Moves the cursor to the given row number and fetches the data
-    // FetchRelative,   // Like above, but moves the cursor relative to the current position
-    // FetchFirst,      // Moves the cursor to the first row and fetches the data
-    // FetchLast,       // Moves the cursor to the last row and fetches the data
-    Disconnect, // Disconnects session
-    // DbConnectInfo,   // Request/receive database connect information
-    XAStart,    // = 83,
-    XAEnd,      // = 84,
-    XAPrepare,  // = 85,
-    XACommit,   // = 86,
-    XARollback, // = 87,
-    XARecover,  // = 88,
-    XAForget,   // = 89,
+    FetchAbsolute,   // Moves the cursor to the given row number and fetches the data
+    FetchRelative,   // Like above, but moves the cursor relative to the current position
+    FetchFirst,      // Moves the cursor to the first row and fetches the data
+    FetchLast,       // Moves the cursor to the last row and fetches the data
+    Disconnect,      // Disconnects session
+    DbConnectInfo,   // Request/receive database connect information
+    XAStart,         // = 83,
+    XAEnd,           // = 84,
+    XAPrepare,       // = 85,
+    XACommit,        // = 86,
+    XARollback,      // = 87,
+    XARecover,       // = 88,
+    XAForget,        // = 89,
 }
 
 impl RequestType {
@@ -53,12 +53,12 @@ impl RequestType {
             RequestType::CloseResultSet => 69,
             RequestType::DropStatementId => 70,
             RequestType::FetchNext => 71,
-            // RequestType::FetchAbsolute => 72,
-            // RequestType::FetchRelative => 73,
-            // RequestType::FetchFirst => 74,
-            // RequestType::FetchLast => 75,
+            RequestType::FetchAbsolute => 72,
+            RequestType::FetchRelative => 73,
+            RequestType::FetchFirst => 74,
+            RequestType::FetchLast => 75,
             RequestType::Disconnect => 77,
-            // RequestType::DbConnectInfo => 82,
+            RequestType::DbConnectInfo => 82,
             RequestType::XAStart => 83,
             RequestType::XAEnd => 84,
             RequestType::XAPrepare => 85,
@@ -68,10 +68,10 @@ pub fn to_string_and_surrogate(cesu8: Vec<u8>) -> HdbResult<(String, Option<Vec<
                 Some(vec![buffer_cesu8[0], buffer_cesu8[1], buffer_cesu8[2]]),
             ))
         }
-        _ => Err(HdbError::Impl(format!(
-            "Unexpected buffer_cesu8 = {:?}",
-            buffer_cesu8
-        ))),
+        _ => Err(HdbError::Impl(
+            format!("Unexpected buffer_cesu8 = {:?}", buffer_cesu8),
+            None,
+        )),
     }
 }
 
@@ -132,10 +132,10 @@ fn get_cesu8_tail_len(bytes: &[u8]) -> HdbResult<usize> {
                     }
                 }
             }
-            Err(HdbError::Impl(format!(
-                "no valid cesu8 cutoff point found for {:?}!",
-                bytes,
-            )))
+            Err(HdbError::Impl(
+                format!("no valid cesu8 cutoff point found for {:?}!", bytes,),
+                None,
+            ))
         }
     }
 }
@@ -165,10 +165,10 @@ fn get_utf8_tail_len(bytes: &[u8]) -> HdbResult<usize> {
                     }
                 }
             }
-            Err(HdbError::Impl(format!(
-                "no valid utf8 cutoff point found for {:?}!",
-                bytes
-            )))
+            Err(HdbError::Impl(
+                format!("no valid utf8 cutoff point found for {:?}!", bytes),
+                None,
+            ))
         }
     }
 }
@@ -223,7 +223,10 @@ pub fn split_off_orphaned_surrogates(cesu8: Vec<u8>) -> HdbResult<CharLobSlice>
             cesu8[3..].to_vec(),
         ),
         Cesu8CharType::NotAStart => {
-            return Err(HdbError::Impl("Unexpected value for NCLob".to_string()));
+            return Err(HdbError::Impl(
+                "Unexpected value for NCLob".to_string(),
+                None,
+            ));
         }
         Cesu8CharType::Empty => (None, cesu8),
         Cesu8CharType::TooShort => (None, cesu8),
@@ -0,0 +1,137 @@
+use crate::conn_core::AmConnCore;
+use crate::connection::execute;
+use crate::protocol::parts::resultset::FetchOverrides;
+use crate::{HdbError, HdbResult};
+
+/// A savepoint within the current transaction.
+///
+/// A `Savepoint` is created with
+/// [`Connection::savepoint()`](struct.Connection.html#method.savepoint).
+/// With [`rollback_to()`](#method.rollback_to), the changes that were done in the transaction
+/// after the savepoint was set are undone, while the earlier changes are kept.
+///
+/// A savepoint that is dropped without having been released with
+/// [`release()`](#method.release) rolls back to itself.
+/// A savepoint ends with its transaction; afterwards, dropping it has no effect,
+/// and `rollback_to()` and `release()` fail.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use hdbconnect::{Connection, HdbResult, IntoConnectParams};
+/// # fn main() -> HdbResult<()> {
+/// # let mut connection = Connection::new("".into_connect_params()?)?;
+/// # let sub_batches: Vec<Vec<(i32, String)>> = vec![];
+/// connection.transaction(|tx| {
+///     let mut stmt = tx.prepare("insert into TAB (ID, TEXT) values(?, ?)")?;
+///     for sub_batch in &sub_batches {
+///         let savepoint = tx.savepoint("SUB_BATCH")?;
+///         for row in sub_batch {
+///             stmt.add_batch(row)?;
+///         }
+///         match stmt.execute_batch() {
+///             Ok(_) => savepoint.release()?,
+///             // the bad sub-batch is skipped, the others are kept
+///             Err(e) => println!("sub-batch skipped: {}", e),
+///         }
+///     }
+///     Ok(())
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Savepoint {
+    am_conn_core: AmConnCore,
+    name: String,
+    transaction_number: u64, // the transaction in which the savepoint was set
+    released: bool,
+}
+
+impl Savepoint {
+    pub(crate) fn try_new(mut am_conn_core: AmConnCore, name: &str) -> HdbResult<Savepoint> {
+        let transaction_number = {
+            let conn_core = am_conn_core.lock()?;
+            if conn_core.is_auto_commit() {
+                return Err(HdbError::Usage(
+                    "Savepoints can only be used with auto-commit switched off".to_owned(),
+                ));
+            }
+            conn_core.get_transaction_number()
+        };
+        let name = format!("\"{}\"", name.replace('"', "\"\""));
+        execute(
+            &mut am_conn_core,
+            format!("SAVEPOINT {}", name),
+            None,
+            FetchOverrides::default(),
+        )?
+        .into_success()?;
+        Ok(Savepoint {
+            am_conn_core,
+            name,
+            transaction_number,
+            released: false,
+        })
+    }
+
+    /// Returns the name of the savepoint, as it is used in SQL (i.e., quoted).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Undoes the changes that were done in the transaction since the savepoint was set.
+    ///
+    /// The savepoint stays valid and can be rolled back to again.
+    pub fn rollback_to(&mut self) -> HdbResult<()> {
+        self.check_transaction()?;
+        execute(
+            &mut self.am_conn_core,
+            format!("ROLLBACK TO SAVEPOINT {}", self.name),
+            None,
+            FetchOverrides::default(),
+        )?
+        .into_success()
+    }
+
+    /// Releases the savepoint; the changes that were done since it was set are kept.
+    pub fn release(mut self) -> HdbResult<()> {
+        self.released = true;
+        self.check_transaction()?;
+        execute(
+            &mut self.am_conn_core,
+            format!("RELEASE SAVEPOINT {}", self.name),
+            None,
+            FetchOverrides::default(),
+        )?
+        .into_success()
+    }
+
+    fn is_in_transaction(&self) -> HdbResult<bool> {
+        Ok(self.am_conn_core.lock()?.get_transaction_number() == self.transaction_number)
+    }
+
+    fn check_transaction(&self) -> HdbResult<()> {
+        if self.is_in_transaction()? {
+            Ok(())
+        } else {
+            Err(HdbError::Usage(format!(
+                "Savepoint {} ended with its transaction",
+                self.name
+            )))
+        }
+    }
+}
+
+impl Drop for Savepoint {
+    // roll back to the savepoint if it was not released and its transaction is still open,
+    // ignore all errors
+    fn drop(&mut self) {
+        if !self.released && self.is_in_transaction().unwrap_or(false) {
+            debug!("rolling back to unreleased savepoint {}", self.name);
+            if let Err(e) = self.rollback_to() {
+                warn!("rollback to savepoint {} failed with {}", self.name, e);
+            }
+        }
+    }
+}
@@ -1,4 +1,3 @@
-use crate::protocol::parts::hdb_value::HdbValue;
 use crate::{HdbError, HdbResult};
 use byteorder::{LittleEndian, ReadBytesExt};
 use serde_derive::Serialize;
@@ -81,17 +80,18 @@ impl DayDate {
 pub(crate) fn parse_daydate(
     nullable: bool,
     rdr: &mut std::io::BufRead,
-) -> HdbResult<HdbValue<'static>> {
+) -> HdbResult<Option<DayDate>> {
     let i = rdr.read_i32::<LittleEndian>()?;
     if i == NULL_REPRESENTATION {
         if nullable {
-            Ok(HdbValue::NULL)
+            Ok(None)
         } else {
             Err(HdbError::Impl(
                 "found NULL value for NOT NULL longdate column".to_owned(),
+                None,
             ))
         }
     } else {
-        Ok(HdbValue::DAYDATE(DayDate::new(i)))
+        Ok(Some(DayDate::new(i)))
     }
 }
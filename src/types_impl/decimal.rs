@@ -1,4 +1,3 @@
-use crate::protocol::parts::hdb_value::HdbValue;
 use crate::protocol::parts::type_id::TypeId;
 use crate::types_impl::hdb_decimal::HdbDecimal;
 use crate::{HdbError, HdbResult};
@@ -12,42 +11,42 @@ pub fn parse_decimal(
     type_id: TypeId,
     scale: i16,
     rdr: &mut std::io::BufRead,
-) -> HdbResult<HdbValue<'static>> {
+) -> HdbResult<Option<BigDecimal>> {
     match type_id {
         TypeId::DECIMAL => HdbDecimal::parse_hdb_decimal(nullable, scale, rdr),
 
         TypeId::FIXED8 => Ok(if parse_null(nullable, rdr)? {
-            HdbValue::NULL
+            None
         } else {
             trace!("parse FIXED8");
             let i = rdr.read_i64::<LittleEndian>()?;
             let bigint = BigInt::from_i64(i)
-                .ok_or_else(|| HdbError::Impl("invalid value of type FIXED8".to_owned()))?;
-            let bd = BigDecimal::new(bigint, i64::from(scale));
-            HdbValue::DECIMAL(bd)
+                .ok_or_else(|| HdbError::Impl("invalid value of type FIXED8".to_owned(), None))?;
+            Some(BigDecimal::new(bigint, i64::from(scale)))
         }),
 
         TypeId::FIXED12 => Ok(if parse_null(nullable, rdr)? {
-            HdbValue::NULL
+            None
         } else {
             trace!("parse FIXED12");
             let bytes = crate::protocol::util::parse_bytes(12, rdr)?;
             let bigint = BigInt::from_signed_bytes_le(&bytes);
-            let bd = BigDecimal::new(bigint, i64::from(scale));
-            HdbValue::DECIMAL(bd)
+            Some(BigDecimal::new(bigint, i64::from(scale)))
         }),
 
         TypeId::FIXED16 => Ok(if parse_null(nullable, rdr)? {
-            HdbValue::NULL
+            None
         } else {
             trace!("parse FIXED16");
             let i = rdr.read_i128::<LittleEndian>()?;
             let bi = BigInt::from_i128(i)
-                .ok_or_else(|| HdbError::Impl("invalid value of type FIXED16".to_owned()))?;
-            let bd = BigDecimal::new(bi, i64::from(scale));
-            HdbValue::DECIMAL(bd)
+                .ok_or_else(|| HdbError::Impl("invalid value of type FIXED16".to_owned(), None))?;
+            Some(BigDecimal::new(bi, i64::from(scale)))
         }),
-        _ => Err(HdbError::Impl("unexpected type id for decimal".to_owned())),
+        _ => Err(HdbError::Impl(
+            "unexpected type id for decimal".to_owned(),
+            None,
+        )),
     }
 }
 
@@ -56,6 +55,7 @@ fn parse_null(nullable: bool, rdr: &mut std::io::BufRead) -> HdbResult<bool> {
     if is_null && !nullable {
         Err(HdbError::Impl(
             "found null value for not-null column".to_owned(),
+            None,
         ))
     } else {
         Ok(is_null)
@@ -107,7 +107,12 @@ pub(crate) fn emit_decimal(
             let (bigint, _exponent) = bd.as_bigint_and_exponent();
             w.write_i128::<LittleEndian>(bigint.to_i128().unwrap())?;
         }
-        _ => return Err(HdbError::Impl("unexpected type id for decimal".to_owned())),
+        _ => {
+            return Err(HdbError::Impl(
+                "unexpected type id for decimal".to_owned(),
+                None,
+            ))
+        }
     }
     Ok(())
 }
@@ -1,5 +1,4 @@
 use crate::hdb_error::{HdbError, HdbResult};
-use crate::protocol::parts::hdb_value::HdbValue;
 use bigdecimal::{BigDecimal, Zero};
 use byteorder::{ByteOrder, LittleEndian};
 use num::bigint::{BigInt, Sign};
@@ -29,23 +28,23 @@ impl HdbDecimal {
         nullable: bool,
         scale: i16,
         rdr: &mut std::io::BufRead,
-    ) -> HdbResult<HdbValue<'static>> {
+    ) -> HdbResult<Option<BigDecimal>> {
         let mut raw = [0_u8; 16];
         rdr.read_exact(&mut raw[..])?;
 
         if raw[15] == 112 && raw[0..=14].iter().all(|el| *el == 0) {
             // it's a NULL!
             if nullable {
-                Ok(HdbValue::NULL)
+                Ok(None)
             } else {
                 Err(HdbError::Impl(
                     "received null value for not-null column".to_owned(),
+                    None,
                 ))
             }
         } else {
             trace!("parse DECIMAL");
-            let bd = HdbDecimal::new(raw).into_bigdecimal_with_scale(scale);
-            Ok(HdbValue::DECIMAL(bd))
+            Ok(Some(HdbDecimal::new(raw).into_bigdecimal_with_scale(scale)))
         }
     }
 
@@ -7,6 +7,8 @@ mod wire;
 
 pub use self::blob::BLob;
 pub use self::clob::CLob;
+#[cfg(feature = "async")]
+pub(crate) use self::lob_writer::write_lob_async;
 pub(crate) use self::lob_writer::LobWriter;
 pub use self::nclob::NCLob;
 pub(crate) use self::wire::{emit_lob_header, parse_blob, parse_clob, parse_nclob};
@@ -1,4 +1,6 @@
-use super::fetch::fetch_a_lob_chunk;
+#[cfg(feature = "async")]
+use super::fetch::fetch_a_lob_chunk_async;
+use super::fetch::{fetch_a_lob_chunk, lob_read_length};
 use crate::conn_core::AmConnCore;
 use crate::protocol::parts::resultset::AmRsCore;
 use crate::protocol::server_resource_consumption_info::ServerResourceConsumptionInfo;
@@ -80,6 +82,16 @@ impl BLob {
         self.0.into_bytes()
     }
 
+    /// Does the same as `into_bytes()`, but fetches the outstanding data asynchronously.
+    ///
+    /// Can only be used with LOBs that were retrieved via an
+    /// [`AsyncConnection`](../struct.AsyncConnection.html).
+    #[cfg(feature = "async")]
+    pub async fn into_bytes_async(self) -> HdbResult<Vec<u8>> {
+        trace!("BLob::into_bytes_async()");
+        self.0.into_bytes_async().await
+    }
+
     /// Reads from given offset and the given length, in bytes.
     pub fn read_slice(&mut self, offset: u64, length: u32) -> HdbResult<Vec<u8>> {
         self.0.read_slice(offset, length)
@@ -184,23 +196,45 @@ impl BLobHandle {
     }
 
     fn fetch_next_chunk(&mut self) -> HdbResult<()> {
+        let (offset, read_length) = self.next_chunk_range()?;
+        let (reply_data, reply_is_last_data) = fetch_a_lob_chunk(
+            &mut self.am_conn_core,
+            self.locator_id,
+            offset,
+            read_length,
+            &mut self.server_resource_consumption_info,
+        )?;
+        self.append_chunk(reply_data, reply_is_last_data)
+    }
+
+    #[cfg(feature = "async")]
+    async fn fetch_next_chunk_async(&mut self) -> HdbResult<()> {
+        let (offset, read_length) = self.next_chunk_range()?;
+        let (reply_data, reply_is_last_data) = fetch_a_lob_chunk_async(
+            &self.am_conn_core,
+            self.locator_id,
+            offset,
+            read_length,
+            &mut self.server_resource_consumption_info,
+        )
+        .await?;
+        self.append_chunk(reply_data, reply_is_last_data)
+    }
+
+    // Returns offset and length of the next chunk that needs to be fetched.
+    fn next_chunk_range(&self) -> HdbResult<(u64, u32)> {
         if self.is_data_complete {
             return Err(HdbError::impl_("fetch_next_chunk(): already complete"));
         }
 
         let read_length = std::cmp::min(
-            self.am_conn_core.lock()?.get_lob_read_length() as u32,
+            lob_read_length(&self.am_conn_core, &self.o_am_rscore)?,
             (self.total_byte_length - self.acc_byte_length as u64) as u32,
         );
+        Ok((self.acc_byte_length as u64, read_length))
+    }
 
-        let (mut reply_data, reply_is_last_data) = fetch_a_lob_chunk(
-            &mut self.am_conn_core,
-            self.locator_id,
-            self.acc_byte_length as u64,
-            read_length,
-            &mut self.server_resource_consumption_info,
-        )?;
-
+    fn append_chunk(&mut self, mut reply_data: Vec<u8>, reply_is_last_data: bool) -> HdbResult<()> {
         self.acc_byte_length += reply_data.len();
         self.data.append(&mut reply_data);
         self.is_data_complete = reply_is_last_data;
@@ -226,6 +260,15 @@ impl BLobHandle {
         Ok(())
     }
 
+    #[cfg(feature = "async")]
+    async fn load_complete_async(&mut self) -> HdbResult<()> {
+        trace!("load_complete_async()");
+        while !self.is_data_complete {
+            self.fetch_next_chunk_async().await?;
+        }
+        Ok(())
+    }
+
     fn max_buf_len(&self) -> usize {
         self.max_buf_len
     }
@@ -236,6 +279,13 @@ impl BLobHandle {
         self.load_complete()?;
         Ok(self.data)
     }
+
+    #[cfg(feature = "async")]
+    async fn into_bytes_async(mut self) -> HdbResult<Vec<u8>> {
+        trace!("into_bytes_async()");
+        self.load_complete_async().await?;
+        Ok(self.data)
+    }
 }
 
 // Support for streaming
@@ -3,12 +3,25 @@ use crate::protocol::argument::Argument;
 use crate::protocol::part::Part;
 use crate::protocol::partkind::PartKind;
 use crate::protocol::parts::read_lob_request::ReadLobRequest;
+use crate::protocol::parts::resultset::AmRsCore;
+use crate::protocol::reply::Reply;
 use crate::protocol::reply_type::ReplyType;
 use crate::protocol::request::Request;
 use crate::protocol::request_type::RequestType;
 use crate::protocol::server_resource_consumption_info::ServerResourceConsumptionInfo;
 use crate::{HdbError, HdbResult};
 
+// LOBs of a resultset use the lob read length that was captured with the resultset.
+pub(crate) fn lob_read_length(
+    am_conn_core: &AmConnCore,
+    o_am_rscore: &Option<AmRsCore>,
+) -> HdbResult<u32> {
+    Ok(match o_am_rscore {
+        Some(ref am_rscore) => am_rscore.lock()?.lob_read_length(),
+        None => am_conn_core.lock()?.get_lob_read_length(),
+    })
+}
+
 // Note that requested_length and offset count either bytes (BLOB, CLOB), or 1-2-3-chars (NCLOB)
 pub(crate) fn fetch_a_lob_chunk(
     am_conn_core: &mut AmConnCore,
@@ -17,14 +30,39 @@ pub(crate) fn fetch_a_lob_chunk(
     length: u32,
     server_resource_consumption_info: &mut ServerResourceConsumptionInfo,
 ) -> HdbResult<(Vec<u8>, bool)> {
+    let request = read_lob_request(locator_id, offset, length);
+    let reply = am_conn_core.send(request)?;
+    evaluate_read_lob_reply(reply, locator_id, server_resource_consumption_info)
+}
+
+#[cfg(feature = "async")]
+pub(crate) async fn fetch_a_lob_chunk_async(
+    am_conn_core: &AmConnCore,
+    locator_id: u64,
+    offset: u64,
+    length: u32,
+    server_resource_consumption_info: &mut ServerResourceConsumptionInfo,
+) -> HdbResult<(Vec<u8>, bool)> {
+    let request = read_lob_request(locator_id, offset, length);
+    let reply = am_conn_core.send_async(request).await?;
+    evaluate_read_lob_reply(reply, locator_id, server_resource_consumption_info)
+}
+
+fn read_lob_request(locator_id: u64, offset: u64, length: u32) -> Request<'static> {
     let mut request = Request::new(RequestType::ReadLob, 0);
     let offset = offset + 1;
     request.push(Part::new(
         PartKind::ReadLobRequest,
         Argument::ReadLobRequest(ReadLobRequest::new(locator_id, offset, length)),
     ));
+    request
+}
 
-    let mut reply = am_conn_core.send(request)?;
+fn evaluate_read_lob_reply(
+    mut reply: Reply,
+    locator_id: u64,
+    server_resource_consumption_info: &mut ServerResourceConsumptionInfo,
+) -> HdbResult<(Vec<u8>, bool)> {
     reply.assert_expected_reply_type(&ReplyType::ReadLob)?;
 
     let (reply_data, reply_is_last_data) = match reply.parts.pop_arg_if_kind(PartKind::ReadLobReply)
@@ -33,11 +71,17 @@ pub(crate) fn fetch_a_lob_chunk(
             if *read_lob_reply.locator_id() != locator_id {
                 return Err(HdbError::Impl(
                     "lob::fetch_a_lob_chunk(): locator ids do not match".to_owned(),
+                    None,
                 ));
             }
             read_lob_reply.into_data_and_last()
         }
-        _ => return Err(HdbError::Impl("No ReadLobReply part found".to_owned())),
+        _ => {
+            return Err(HdbError::Impl(
+                "No ReadLobReply part found".to_owned(),
+                None,
+            ))
+        }
     };
 
     let (server_proc_time, server_cpu_time, server_memory_usage) =
@@ -51,6 +95,7 @@ pub(crate) fn fetch_a_lob_chunk(
             _ => {
                 return Err(HdbError::Impl(
                     "Inconsistent StatementContext part found for ResultSet".to_owned(),
+                    None,
                 ));
             }
         };
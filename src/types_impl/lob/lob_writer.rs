@@ -4,6 +4,7 @@ use crate::protocol::part::Part;
 use crate::protocol::partkind::PartKind;
 use crate::protocol::parts::type_id::TypeId;
 use crate::protocol::parts::write_lob_request::WriteLobRequest;
+use crate::protocol::reply::Reply;
 use crate::protocol::reply_type::ReplyType;
 use crate::protocol::request::Request;
 use crate::protocol::request_type::RequestType;
@@ -21,13 +22,20 @@ pub(crate) struct LobWriter {
     lob_write_length: usize,
 }
 impl LobWriter {
-    pub fn new(locator_id: u64, type_id: TypeId, am_conn_core: AmConnCore) -> HdbResult<LobWriter> {
+    pub fn new(
+        locator_id: u64,
+        type_id: TypeId,
+        lob_write_length: usize,
+        am_conn_core: AmConnCore,
+    ) -> HdbResult<LobWriter> {
         if let TypeId::BLOB | TypeId::CLOB | TypeId::NCLOB = type_id {
             // ok
         } else {
-            return Err(HdbError::Impl(format!("Unsupported type-id {:?}", type_id)));
+            return Err(HdbError::Impl(
+                format!("Unsupported type-id {:?}", type_id),
+                None,
+            ));
         }
-        let lob_write_length = am_conn_core.lock()?.get_lob_write_length();
         Ok(LobWriter {
             locator_id,
             type_id,
@@ -118,6 +126,63 @@ fn write_a_lob_chunk(
     lob_write_mode: LobWriteMode,
     server_resource_consumption_info: &mut ServerResourceConsumptionInfo,
 ) -> HdbResult<Vec<u64>> {
+    let request = write_lob_request(locator_id, lob_write_mode);
+    let reply = am_conn_core.send(request)?;
+    evaluate_write_lob_reply(reply, server_resource_consumption_info)
+}
+
+// Writes the complete content of the reader to the LOB with the given locator,
+// using the asynchronous transport.
+#[cfg(feature = "async")]
+pub(crate) async fn write_lob_async(
+    am_conn_core: &AmConnCore,
+    locator_id: u64,
+    type_id: TypeId,
+    lob_write_length: usize,
+    reader: &std::sync::Mutex<dyn std::io::Read + Send>,
+) -> HdbResult<()> {
+    use std::io::Read;
+    let mut server_resource_consumption_info = ServerResourceConsumptionInfo::default();
+    let mut utf8_tail = Vec::<u8>::new();
+    loop {
+        // the reader must not be locked across an await point
+        let (payload_raw, is_last) = {
+            let mut reader = reader.lock().map_err(|e| HdbError::Poison(e.to_string()))?;
+            let mut payload_raw = Vec::<u8>::with_capacity(lob_write_length + 8);
+            std::mem::swap(&mut payload_raw, &mut utf8_tail);
+            let count = (&mut *reader)
+                .take(lob_write_length as u64)
+                .read_to_end(&mut payload_raw)?;
+            (payload_raw, count < lob_write_length)
+        };
+
+        let payload = if let TypeId::CLOB | TypeId::NCLOB = type_id {
+            let (cesu8, tail) = utf8_to_cesu8_and_utf8_tail(payload_raw)?;
+            if is_last && !tail.is_empty() {
+                return Err(HdbError::usage_("stream is ending with invalid utf-8"));
+            }
+            utf8_tail = tail;
+            cesu8
+        } else {
+            payload_raw
+        };
+
+        let lob_write_mode = if is_last {
+            LobWriteMode::Last(&payload)
+        } else {
+            LobWriteMode::Append(&payload)
+        };
+        let reply = am_conn_core
+            .send_async(write_lob_request(locator_id, lob_write_mode))
+            .await?;
+        evaluate_write_lob_reply(reply, &mut server_resource_consumption_info)?;
+        if is_last {
+            return Ok(());
+        }
+    }
+}
+
+fn write_lob_request<'a>(locator_id: u64, lob_write_mode: LobWriteMode<'a>) -> Request<'a> {
     let mut request = Request::new(RequestType::WriteLob, 0);
     let write_lob_request = match lob_write_mode {
         // LobWriteMode::Offset(offset, buf) =>
@@ -129,8 +194,13 @@ fn write_a_lob_chunk(
         PartKind::WriteLobRequest,
         Argument::WriteLobRequest(write_lob_request),
     ));
+    request
+}
 
-    let mut reply = am_conn_core.send(request)?;
+fn evaluate_write_lob_reply(
+    mut reply: Reply,
+    server_resource_consumption_info: &mut ServerResourceConsumptionInfo,
+) -> HdbResult<Vec<u64>> {
     reply.assert_expected_reply_type(&ReplyType::WriteLob)?;
 
     let (server_proc_time, server_cpu_time, server_memory_usage) =
@@ -144,6 +214,7 @@ fn write_a_lob_chunk(
             _ => {
                 return Err(HdbError::Impl(
                     "Inconsistent StatementContext part found for ResultSet".to_owned(),
+                    None,
                 ));
             }
         };
@@ -161,10 +232,10 @@ fn write_a_lob_chunk(
 
     match reply.parts.pop_arg_if_kind(PartKind::WriteLobReply) {
         Some(Argument::WriteLobReply(write_lob_reply)) => Ok(write_lob_reply.into_locator_ids()),
-        _ => Err(HdbError::Impl(format!(
-            "No WriteLobReply part found; parts = {:?}",
-            reply.parts
-        ))),
+        _ => Err(HdbError::Impl(
+            format!("No WriteLobReply part found; parts = {:?}", reply.parts),
+            None,
+        )),
     }
 }
 
@@ -1,4 +1,6 @@
-use super::fetch::fetch_a_lob_chunk;
+#[cfg(feature = "async")]
+use super::fetch::fetch_a_lob_chunk_async;
+use super::fetch::{fetch_a_lob_chunk, lob_read_length};
 use super::CharLobSlice;
 use crate::conn_core::AmConnCore;
 use crate::protocol::parts::resultset::AmRsCore;
@@ -86,6 +88,16 @@ impl NCLob {
         self.0.into_string()
     }
 
+    /// Does the same as `into_string()`, but fetches the outstanding data asynchronously.
+    ///
+    /// Can only be used with LOBs that were retrieved via an
+    /// [`AsyncConnection`](../struct.AsyncConnection.html).
+    #[cfg(feature = "async")]
+    pub async fn into_string_async(self) -> HdbResult<String> {
+        trace!("NCLob::into_string_async()");
+        self.0.into_string_async().await
+    }
+
     /// Reads from given offset and the given length, in number of 123-byte sequences.
     pub fn read_slice(&mut self, offset: u64, length: u32) -> HdbResult<CharLobSlice> {
         self.0.read_slice(offset, length)
@@ -210,23 +222,45 @@ impl NCLobHandle {
     }
 
     fn fetch_next_chunk(&mut self) -> HdbResult<()> {
+        let (offset, read_length) = self.next_chunk_range()?;
+        let (reply_data, reply_is_last_data) = fetch_a_lob_chunk(
+            &mut self.am_conn_core,
+            self.locator_id,
+            offset,
+            read_length,
+            &mut self.server_resource_consumption_info,
+        )?;
+        self.append_chunk(reply_data, reply_is_last_data)
+    }
+
+    #[cfg(feature = "async")]
+    async fn fetch_next_chunk_async(&mut self) -> HdbResult<()> {
+        let (offset, read_length) = self.next_chunk_range()?;
+        let (reply_data, reply_is_last_data) = fetch_a_lob_chunk_async(
+            &self.am_conn_core,
+            self.locator_id,
+            offset,
+            read_length,
+            &mut self.server_resource_consumption_info,
+        )
+        .await?;
+        self.append_chunk(reply_data, reply_is_last_data)
+    }
+
+    // Returns offset and length of the next chunk that needs to be fetched.
+    fn next_chunk_range(&self) -> HdbResult<(u64, u32)> {
         if self.is_data_complete {
             return Err(HdbError::impl_("fetch_next_chunk(): already complete"));
         }
 
         let read_length = std::cmp::min(
-            self.am_conn_core.lock()?.get_lob_read_length() as u32,
+            lob_read_length(&self.am_conn_core, &self.o_am_rscore)?,
             (self.total_char_length - self.acc_char_length as u64) as u32,
         );
+        Ok((self.acc_char_length as u64, read_length))
+    }
 
-        let (mut reply_data, reply_is_last_data) = fetch_a_lob_chunk(
-            &mut self.am_conn_core,
-            self.locator_id,
-            self.acc_char_length as u64,
-            read_length,
-            &mut self.server_resource_consumption_info,
-        )?;
-
+    fn append_chunk(&mut self, mut reply_data: Vec<u8>, reply_is_last_data: bool) -> HdbResult<()> {
         self.acc_byte_length += reply_data.len();
         self.acc_char_length += util::count_1_2_3_sequence_starts(&reply_data);
 
@@ -267,6 +301,15 @@ impl NCLobHandle {
         Ok(())
     }
 
+    #[cfg(feature = "async")]
+    async fn load_complete_async(&mut self) -> HdbResult<()> {
+        trace!("load_complete_async()");
+        while !self.is_data_complete {
+            self.fetch_next_chunk_async().await?;
+        }
+        Ok(())
+    }
+
     fn max_buf_len(&self) -> usize {
         self.max_buf_len
     }
@@ -277,6 +320,13 @@ impl NCLobHandle {
         self.load_complete()?;
         Ok(self.utf8)
     }
+
+    #[cfg(feature = "async")]
+    async fn into_string_async(mut self) -> HdbResult<String> {
+        trace!("into_string_async()");
+        self.load_complete_async().await?;
+        Ok(self.utf8)
+    }
 }
 
 // Support for NCLOB streaming
@@ -20,6 +20,7 @@ pub(crate) fn parse_blob(
         } else {
             Err(HdbError::Impl(
                 "found null value for not-null BLOB column".to_owned(),
+                None,
             ))
         }
     } else {
@@ -48,6 +49,7 @@ pub(crate) fn parse_clob(
         } else {
             Err(HdbError::Impl(
                 "found null value for not-null CLOB column".to_owned(),
+                None,
             ))
         }
     } else {
@@ -78,6 +80,7 @@ pub(crate) fn parse_nclob(
         } else {
             Err(HdbError::Impl(
                 "found null value for not-null NCLOB column".to_owned(),
+                None,
             ))
         }
     } else {
@@ -92,7 +95,12 @@ pub(crate) fn parse_nclob(
                 locator_id,
                 data,
             )),
-            _ => return Err(HdbError::Impl("unexpected type id for nclob".to_owned())),
+            _ => {
+                return Err(HdbError::Impl(
+                    "unexpected type id for nclob".to_owned(),
+                    None,
+                ))
+            }
         })
     }
 }
@@ -1,4 +1,3 @@
-use crate::protocol::parts::hdb_value::HdbValue;
 use crate::{HdbError, HdbResult};
 use byteorder::{LittleEndian, ReadBytesExt};
 use serde_derive::Serialize;
@@ -99,17 +98,18 @@ impl LongDate {
 pub(crate) fn parse_longdate(
     nullable: bool,
     rdr: &mut std::io::BufRead,
-) -> HdbResult<HdbValue<'static>> {
+) -> HdbResult<Option<LongDate>> {
     let i = rdr.read_i64::<LittleEndian>()?;
     if i == NULL_REPRESENTATION {
         if nullable {
-            Ok(HdbValue::NULL)
+            Ok(None)
         } else {
             Err(HdbError::Impl(
                 "found NULL value for NOT NULL longdate column".to_owned(),
+                None,
             ))
         }
     } else {
-        Ok(HdbValue::LONGDATE(LongDate::new(i)))
+        Ok(Some(LongDate::new(i)))
     }
 }
@@ -1,4 +1,3 @@
-use crate::protocol::parts::hdb_value::HdbValue;
 use crate::{HdbError, HdbResult};
 use byteorder::{LittleEndian, ReadBytesExt};
 use serde_derive::Serialize;
@@ -98,17 +97,18 @@ impl SecondDate {
 pub(crate) fn parse_seconddate(
     nullable: bool,
     rdr: &mut std::io::BufRead,
-) -> HdbResult<HdbValue<'static>> {
+) -> HdbResult<Option<SecondDate>> {
     let i = rdr.read_i64::<LittleEndian>()?;
     if i == NULL_REPRESENTATION {
         if nullable {
-            Ok(HdbValue::NULL)
+            Ok(None)
         } else {
             Err(HdbError::Impl(
                 "found NULL value for NOT NULL longdate column".to_owned(),
+                None,
             ))
         }
     } else {
-        Ok(HdbValue::SECONDDATE(SecondDate::new(i)))
+        Ok(Some(SecondDate::new(i)))
     }
 }
@@ -1,4 +1,3 @@
-use crate::protocol::parts::hdb_value::HdbValue;
 use crate::{HdbError, HdbResult};
 use byteorder::{LittleEndian, ReadBytesExt};
 use serde_derive::Serialize;
@@ -60,17 +59,18 @@ impl SecondTime {
 pub(crate) fn parse_secondtime(
     nullable: bool,
     rdr: &mut std::io::BufRead,
-) -> HdbResult<HdbValue<'static>> {
+) -> HdbResult<Option<SecondTime>> {
     let i = rdr.read_i32::<LittleEndian>()?;
     if i == NULL_REPRESENTATION {
         if nullable {
-            Ok(HdbValue::NULL)
+            Ok(None)
         } else {
             Err(HdbError::Impl(
                 "found NULL value for NOT NULL scondtime column".to_owned(),
+                None,
             ))
         }
     } else {
-        Ok(HdbValue::SECONDTIME(SecondTime::new(i)))
+        Ok(Some(SecondTime::new(i)))
     }
 }
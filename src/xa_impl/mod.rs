@@ -11,22 +11,29 @@ use std::error::Error;
 impl From<HdbError> for RmError {
     fn from(error: HdbError) -> RmError {
         match error {
-            HdbError::Cesu8(e) => RmError::new(ErrorCode::RmError, e.description().to_string()),
-            HdbError::DbError(se) => RmError::new(ErrorCode::RmError, se.to_string()),
+            HdbError::Cesu8(e, _) => RmError::new(ErrorCode::RmError, e.description().to_string()),
+            HdbError::DbError(se) | HdbError::QueryTimeout(se) => {
+                RmError::new(ErrorCode::RmError, se.to_string())
+            }
+            HdbError::Cancelled => {
+                RmError::new(ErrorCode::RmError, "Statement was cancelled".to_string())
+            }
             HdbError::MixedResults(se) => RmError::new(ErrorCode::RmError, se[0].to_string()),
-            HdbError::Conversion(e) => {
+            HdbError::Conversion(e, _) => {
                 RmError::new(ErrorCode::RmError, e.description().to_string())
             }
-            HdbError::Deserialization(e) => {
+            HdbError::Deserialization(e, _) => {
                 RmError::new(ErrorCode::RmError, e.description().to_string())
             }
             HdbError::Usage(s)
             | HdbError::Evaluation(s)
             | HdbError::Poison(s)
             | HdbError::DbIssue(s) => RmError::new(ErrorCode::RmError, s),
-            HdbError::Impl(s) => RmError::new(ErrorCode::RmError, s.to_string()),
-            HdbError::Io(e) => RmError::new(ErrorCode::RmError, e.description().to_string()),
-            HdbError::Serialization(e) => {
+            HdbError::Impl(s, _) => RmError::new(ErrorCode::RmError, s.to_string()),
+            HdbError::Io(e) | HdbError::Timeout(e) => {
+                RmError::new(ErrorCode::RmError, e.description().to_string())
+            }
+            HdbError::Serialization(e, _) => {
                 RmError::new(ErrorCode::RmError, e.description().to_string())
             }
         }
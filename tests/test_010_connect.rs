@@ -14,6 +14,8 @@ pub fn test_010_connect() -> HdbResult<()> {
     connect_successfully(&mut log_handle);
     connect_options(&mut log_handle)?;
     connect_wrong_password(&mut log_handle);
+    connect_with_failover(&mut log_handle)?;
+    connect_with_database_name(&mut log_handle)?;
     connect_and_select_with_explicit_clientlocale(&mut log_handle)?;
     connect_and_select_with_clientlocale_from_env(&mut log_handle)?;
     client_info(&mut log_handle)?;
@@ -26,6 +28,36 @@ fn connect_successfully(_log_handle: &mut ReconfigurationHandle) {
     test_utils::get_authenticated_connection().unwrap();
 }
 
+fn connect_with_failover(_log_handle: &mut ReconfigurationHandle) -> HdbResult<()> {
+    info!("test a successful connection after failing over from an unreachable host");
+    let mut url = test_utils::get_std_connect_url()?.trim().to_string();
+    let hosts_start = url.find('@').unwrap() + 1;
+    url.insert_str(hosts_start, "localhost:1,");
+    let params = url.into_connect_params()?;
+    assert_eq!(params.addr(), "localhost:1");
+    assert_eq!(params.failover_hosts().len(), 1);
+
+    let mut connection = Connection::new(params)?;
+    let one: i32 = connection.query("select 1 from dummy")?.try_into()?;
+    assert_eq!(one, 1);
+    Ok(())
+}
+
+fn connect_with_database_name(_log_handle: &mut ReconfigurationHandle) -> HdbResult<()> {
+    info!("test a connection that names the database explicitly");
+    let db_name = test_utils::get_authenticated_connection()?
+        .get_database_name()?
+        .unwrap();
+    let url = format!(
+        "{}?databasename={}",
+        test_utils::get_std_connect_url()?.trim(),
+        db_name
+    );
+    let connection = Connection::new(url.into_connect_params()?)?;
+    assert_eq!(connection.get_database_name()?.unwrap(), db_name);
+    Ok(())
+}
+
 fn connect_options(_log_handle: &mut ReconfigurationHandle) -> HdbResult<()> {
     info!("test a successful connection");
     _log_handle.parse_and_push_temp_spec("info, test = debug");
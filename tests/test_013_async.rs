@@ -0,0 +1,116 @@
+#![cfg(feature = "async")]
+
+mod test_utils;
+
+use hdbconnect::{AsyncConnection, HdbResult, HdbValue};
+use log::info;
+use serde_derive::Deserialize;
+use std::sync::{Arc, Mutex};
+
+// cargo test --features async --test test_013_async -- --nocapture
+#[test]
+fn test_013_async() -> HdbResult<()> {
+    let _log_handle = test_utils::init_logger();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    runtime.block_on(async {
+        let mut connection = AsyncConnection::new(test_utils::get_std_connect_params()?).await?;
+        query_and_fetch(&mut connection).await?;
+        prepare_and_lobs(&mut connection).await?;
+        Ok(())
+    })
+}
+
+async fn query_and_fetch(connection: &mut AsyncConnection) -> HdbResult<()> {
+    info!("query with small fetch size, so that rows are fetched asynchronously");
+    connection
+        .exec("drop table TEST_ASYNC")
+        .await
+        .unwrap_or_default();
+    connection
+        .exec("create table TEST_ASYNC (ID INT, TXT NVARCHAR(20))")
+        .await?;
+    let mut stmt = connection
+        .prepare("insert into TEST_ASYNC (ID, TXT) values(?, ?)")
+        .await?;
+    for i in 0..50 {
+        stmt.add_batch(&(i, format!("row {}", i)))?;
+    }
+    stmt.execute_batch().await?;
+
+    connection.set_fetch_size(7)?;
+    let mut resultset = connection
+        .query("select ID, TXT from TEST_ASYNC order by ID")
+        .await?;
+    let mut count = 0;
+    while let Some(row) = resultset.next_row().await? {
+        let (id, _txt): (i32, String) = row.try_into()?;
+        assert_eq!(id, count);
+        count += 1;
+    }
+    assert_eq!(count, 50);
+
+    #[derive(Deserialize)]
+    struct Data {
+        #[serde(rename = "ID")]
+        _id: i32,
+        #[serde(rename = "TXT")]
+        _txt: String,
+    }
+    let data: Vec<Data> = connection
+        .query("select ID, TXT from TEST_ASYNC")
+        .await?
+        .try_into()
+        .await?;
+    assert_eq!(data.len(), 50);
+    Ok(())
+}
+
+async fn prepare_and_lobs(connection: &mut AsyncConnection) -> HdbResult<()> {
+    info!("write and read a BLOB asynchronously");
+    connection
+        .exec("drop table TEST_ASYNC_LOBS")
+        .await
+        .unwrap_or_default();
+    connection
+        .exec("create table TEST_ASYNC_LOBS (ID INT, BINDATA BLOB)")
+        .await?;
+    connection.set_lob_read_length(10_000)?;
+    connection.set_lob_write_length(10_000)?;
+    connection.set_auto_commit(false)?;
+
+    let bytes: Vec<u8> = (0..100_000).map(|i| (i % 251) as u8).collect();
+    let mut stmt = connection
+        .prepare("insert into TEST_ASYNC_LOBS values(?, ?)")
+        .await?;
+    stmt.execute_row(vec![
+        HdbValue::INT(1),
+        HdbValue::LOBSTREAM(Some(Arc::new(Mutex::new(std::io::Cursor::new(
+            bytes.clone(),
+        ))))),
+    ])
+    .await?;
+    connection.commit().await?;
+
+    let blob = connection
+        .query("select BINDATA from TEST_ASYNC_LOBS where ID = 1")
+        .await?
+        .into_single_row()
+        .await?
+        .into_single_value()?
+        .try_into_blob()?;
+    assert_eq!(blob.into_bytes_async().await?, bytes);
+    Ok(())
+}
+
+// The futures must be usable with multi-threaded runtimes.
+#[allow(dead_code)]
+fn futures_are_send() {
+    fn is_send<T: Send>(_: T) {}
+    is_send(async {
+        let mut connection = AsyncConnection::new(test_utils::get_std_connect_params()?).await?;
+        query_and_fetch(&mut connection).await?;
+        prepare_and_lobs(&mut connection).await
+    });
+}
@@ -0,0 +1,116 @@
+mod test_utils;
+
+use hdbconnect::{Connection, HdbError, HdbResult, IsolationLevel};
+use log::{debug, info};
+
+// cargo test --test test_014_reconnect -- --nocapture
+#[test]
+pub fn test_014_reconnect() -> HdbResult<()> {
+    let mut _log_handle = test_utils::init_logger();
+    let mut sys_conn = test_utils::get_system_connection()?;
+    let mut connection = test_utils::get_authenticated_connection()?;
+
+    repeat_after_reconnect(&mut sys_conn, &mut connection)?;
+    lose_write_transaction(&mut sys_conn, &mut connection)?;
+    prepare_again_after_reconnect(&mut sys_conn, &mut connection)?;
+    Ok(())
+}
+
+fn disconnect(sys_conn: &mut Connection, connection: &Connection) -> HdbResult<()> {
+    let conn_id = connection.id()?;
+    debug!("disconnecting session {}", conn_id);
+    sys_conn.exec(&format!("ALTER SYSTEM DISCONNECT SESSION '{}'", conn_id))
+}
+
+fn repeat_after_reconnect(sys_conn: &mut Connection, connection: &mut Connection) -> HdbResult<()> {
+    info!("a request that fails with a broken connection is repeated after the reconnect");
+    connection.set_auto_commit(false)?;
+    connection.set_fetch_size(7)?;
+    connection.set_application_user("RECONNECT_TEST")?;
+    connection.set_isolation_level(IsolationLevel::RepeatableRead)?;
+    connection.set_read_only(true)?;
+    connection.set_session_variable("RECONNECT_VAR", "kept")?;
+    let _: i32 = connection.query("select 1 from dummy")?.try_into()?;
+
+    disconnect(sys_conn, connection)?;
+    let one: i32 = connection.query("select 1 from dummy")?.try_into()?;
+    assert_eq!(one, 1);
+    debug!("session continues with connection id {}", connection.id()?);
+
+    info!("the settings of the connection are restored");
+    assert!(!connection.is_auto_commit()?);
+    let appl_user: String = connection
+        .query("select session_context('APPLICATIONUSER') from dummy")?
+        .try_into()?;
+    assert_eq!(appl_user, "RECONNECT_TEST");
+
+    info!("the session settings are restored");
+    let (isolation_level, var): (String, String) = connection
+        .query(
+            "select ISOLATION_LEVEL, session_context('RECONNECT_VAR') \
+             from M_TRANSACTIONS where CONNECTION_ID = current_connection",
+        )?
+        .try_into()?;
+    assert_eq!(isolation_level, "REPEATABLE READ");
+    assert_eq!(var, "kept");
+    assert!(connection.is_read_only()?);
+    match connection.exec("create table TEST_RECONNECT_READ_ONLY (F1_I INT)") {
+        Err(HdbError::DbError(_)) => debug!("read-only mode was restored"),
+        r => panic!("unexpected result: {:?}", r),
+    }
+    connection.set_read_only(false)?;
+    connection.set_isolation_level(IsolationLevel::ReadCommitted)?;
+    Ok(())
+}
+
+fn lose_write_transaction(sys_conn: &mut Connection, connection: &mut Connection) -> HdbResult<()> {
+    info!("a request within an open write transaction is not repeated");
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_RECONNECT"]);
+    connection.multiple_statements(vec!["create table TEST_RECONNECT (F1_I INT)"])?;
+    connection.set_auto_commit(false)?;
+    connection.dml("insert into TEST_RECONNECT (F1_I) values(1)")?;
+
+    disconnect(sys_conn, connection)?;
+    match connection.dml("insert into TEST_RECONNECT (F1_I) values(2)") {
+        Err(HdbError::DbIssue(s)) => debug!("got expected error: {}", s),
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    info!("the connection is usable again, but the transaction was lost");
+    let count: i32 = connection
+        .query("select count(*) from TEST_RECONNECT")?
+        .try_into()?;
+    assert_eq!(count, 0);
+    Ok(())
+}
+
+fn prepare_again_after_reconnect(
+    sys_conn: &mut Connection,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("prepared statements of the former session are prepared again");
+    connection.set_auto_commit(true)?;
+    connection.set_statement_cache_size(2)?;
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_RECONNECT_PREPARED"]);
+    connection.multiple_statements(vec!["create table TEST_RECONNECT_PREPARED (F1_I INT)"])?;
+    let insert = "insert into TEST_RECONNECT_PREPARED (F1_I) values(?)";
+    let mut cached = connection.prepare(insert)?;
+    cached.add_batch(&1_i32)?;
+    cached.add_batch(&2_i32)?;
+
+    disconnect(sys_conn, connection)?;
+    let _: i32 = connection.query("select 1 from dummy")?.try_into()?;
+
+    debug!("the batch is bound to the statement that was prepared again");
+    cached.execute_batch()?;
+    cached.execute(&3_i32)?;
+    drop(cached);
+    connection.prepare(insert)?.execute(&4_i32)?;
+
+    let count: i32 = connection
+        .query("select count(*) from TEST_RECONNECT_PREPARED")?
+        .try_into()?;
+    assert_eq!(count, 4);
+    connection.set_statement_cache_size(0)?;
+    Ok(())
+}
@@ -2,7 +2,7 @@ mod test_utils;
 
 use chrono::NaiveDateTime;
 use flexi_logger::{Duplicate, Logger, ReconfigurationHandle};
-use hdbconnect::{Connection, HdbResult};
+use hdbconnect::{Column, Connection, HdbError, HdbResult, IntoConnectParams};
 use log::{debug, info};
 use serde_derive::Deserialize;
 
@@ -18,6 +18,14 @@ pub fn test_015_resultset() -> HdbResult<()> {
 
     evaluate_resultset(&mut log_handle, &mut connection)?;
     verify_row_ordering(&mut log_handle, &mut connection)?;
+    scroll_resultset(&mut log_handle, &mut connection)?;
+    read_column_batches(&mut log_handle, &mut connection)?;
+    iterate_typed_rows(&mut log_handle, &mut connection)?;
+    read_ahead(&mut log_handle, &mut connection)?;
+    access_values_by_name(&mut log_handle, &mut connection)?;
+    override_fetch_size(&mut log_handle, &mut connection)?;
+    #[cfg(feature = "arrow")]
+    export_to_arrow(&mut log_handle, &mut connection)?;
 
     info!("{} calls to DB were executed", connection.get_call_count()?);
 
@@ -173,3 +181,241 @@ fn verify_row_ordering(
 
     Ok(())
 }
+
+fn scroll_resultset(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("scroll in a resultset");
+    let stmt = "select * from TEST_ROW_ORDERING order by f1 asc";
+
+    // without scrollable cursors, the resultset can only be read forward
+    match connection.query(stmt)?.fetch_first() {
+        Err(HdbError::Usage(s)) => debug!("got expected error: {}", s),
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    let url = test_utils::get_std_connect_url()?;
+    let url = format!("{}?scrollable_cursors=true", url.trim());
+    let mut scroll_conn = Connection::new(url.into_connect_params()?)?;
+    scroll_conn.set_fetch_size(10)?;
+    let mut resultset = scroll_conn.query(stmt)?;
+
+    let (f1, _): (usize, usize) = resultset.fetch_absolute(1001)?.unwrap().try_into()?;
+    assert_eq!(f1, 1000);
+
+    debug!("the rows following the cursor position are read forward");
+    for i in 1001..1025 {
+        let (f1, _): (usize, usize) = resultset.next_row()?.unwrap().try_into()?;
+        assert_eq!(f1, i);
+    }
+
+    debug!("relative moves start at the row that was returned last");
+    let (f1, _): (usize, usize) = resultset.fetch_relative(-24)?.unwrap().try_into()?;
+    assert_eq!(f1, 1000);
+    let (f1, _): (usize, usize) = resultset.fetch_relative(500)?.unwrap().try_into()?;
+    assert_eq!(f1, 1500);
+
+    let (f1, _): (usize, usize) = resultset.fetch_last()?.unwrap().try_into()?;
+    assert_eq!(f1, 2999);
+    assert!(resultset.next_row()?.is_none());
+
+    let (f1, _): (usize, usize) = resultset.fetch_first()?.unwrap().try_into()?;
+    assert_eq!(f1, 0);
+    let (f1, _): (usize, usize) = resultset.next_row()?.unwrap().try_into()?;
+    assert_eq!(f1, 1);
+
+    assert!(resultset.fetch_absolute(5000)?.is_none());
+    Ok(())
+}
+
+fn read_column_batches(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("read a resultset column by column");
+    connection.set_fetch_size(1000)?;
+    let mut resultset = connection.query("select * from TEST_ROW_ORDERING order by f1 asc")?;
+
+    debug!("rows that were already read are not contained in the batches");
+    let (f1, _): (usize, usize) = resultset.next_row()?.unwrap().try_into()?;
+    assert_eq!(f1, 0);
+
+    let mut expected = 1;
+    let mut no_of_batches = 0;
+    while let Some(batch) = resultset.next_column_batch()? {
+        no_of_batches += 1;
+        assert_eq!(batch.columns().len(), 2);
+        match batch.column(0) {
+            Some(Column::Int(values)) => {
+                assert_eq!(values.len(), batch.len());
+                for value in values {
+                    assert_eq!(*value, Some(expected));
+                    expected += 1;
+                }
+            }
+            c => panic!("unexpected column: {:?}", c),
+        }
+    }
+    assert_eq!(expected, 3000);
+    assert_eq!(no_of_batches, 3);
+    Ok(())
+}
+
+#[cfg(feature = "arrow")]
+fn export_to_arrow(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    use hdbconnect::arrow_array::cast::AsArray;
+    use hdbconnect::arrow_array::types::Int32Type;
+    use hdbconnect::arrow_schema::DataType;
+
+    info!("export a resultset to arrow");
+    connection.set_fetch_size(100)?;
+    let batches = connection
+        .query("select * from TEST_ROW_ORDERING order by f1 asc")?
+        .into_arrow_batches(1000)?;
+    assert_eq!(batches.schema().field(0).data_type(), &DataType::Int32);
+
+    let mut expected = 0;
+    for batch in batches {
+        let batch = batch?;
+        assert_eq!(batch.num_rows(), 1000);
+        for value in batch.column(0).as_primitive::<Int32Type>().values().iter() {
+            assert_eq!(*value, expected);
+            expected += 1;
+        }
+    }
+    assert_eq!(expected, 3000);
+    Ok(())
+}
+
+fn iterate_typed_rows(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("iterate over the rows, converted into a rust type while fetching");
+    #[derive(Deserialize)]
+    struct TestData {
+        #[serde(rename = "F1")]
+        f1: usize,
+        #[serde(rename = "F2")]
+        f2: usize,
+    }
+
+    connection.set_fetch_size(100)?;
+    let typed_iter = connection
+        .query("select * from TEST_ROW_ORDERING order by f1 asc")?
+        .into_typed_iter::<TestData>();
+    assert_eq!(typed_iter.metadata().number_of_fields(), 2);
+    let mut count = 0;
+    for (index, td) in typed_iter.enumerate() {
+        let td = td?;
+        assert_eq!(index, td.f1);
+        assert_eq!(index, td.f2);
+        count += 1;
+    }
+    assert_eq!(count, 3000);
+
+    debug!("conversion errors are reported per row");
+    let mut typed_iter = connection
+        .query("select 'a' from dummy")?
+        .into_typed_iter::<i32>();
+    assert!(typed_iter.next().unwrap().is_err());
+    assert!(typed_iter.next().is_none());
+    Ok(())
+}
+
+fn read_ahead(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("fetch the next rows in the background while the current ones are consumed");
+    connection.set_fetch_size(100)?;
+    connection.set_read_ahead(true)?;
+    assert!(connection.is_read_ahead()?);
+
+    let mut resultset = connection.query("select * from TEST_ROW_ORDERING order by f1 asc")?;
+    let mut expected = 0;
+    while let Some(row) = resultset.next_row()? {
+        let (f1, f2): (usize, usize) = row.try_into()?;
+        assert_eq!(f1, expected);
+        assert_eq!(f2, expected);
+        expected += 1;
+    }
+    assert_eq!(expected, 3000);
+
+    debug!("the connection can be used while a prefetch is pending");
+    let mut resultset = connection.query("select * from TEST_ROW_ORDERING order by f1 asc")?;
+    let (f1, _): (usize, usize) = resultset.next_row()?.unwrap().try_into()?;
+    assert_eq!(f1, 0);
+    let count: usize = connection
+        .query("select count(*) from TEST_ROW_ORDERING")?
+        .try_into()?;
+    assert_eq!(count, 3000);
+    resultset.fetch_all()?;
+    assert_eq!(resultset.total_number_of_rows()?, 2999);
+    drop(resultset);
+
+    connection.set_read_ahead(false)?;
+    Ok(())
+}
+
+fn override_fetch_size(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("statements can use their own fetch size");
+    connection.set_fetch_size(100)?;
+    let count = connection.get_call_count()?;
+    let mut resultset = connection
+        .query_with_fetch_size("select * from TEST_ROW_ORDERING order by f1 asc", 1000)?;
+    debug!("the fetch size is captured when the statement is executed");
+    connection.set_fetch_size(10)?;
+    resultset.fetch_all()?;
+    assert_eq!(resultset.total_number_of_rows()?, 3000);
+    // one execute and two or three fetches
+    assert!(connection.get_call_count()? - count <= 4);
+
+    let mut stmt = connection.prepare("select * from TEST_ROW_ORDERING where f1 < ?")?;
+    stmt.set_fetch_size(20);
+    let count = connection.get_call_count()?;
+    let mut resultset = stmt.execute(&100_usize)?.into_resultset()?;
+    resultset.fetch_all()?;
+    assert_eq!(resultset.total_number_of_rows()?, 100);
+    assert!(connection.get_call_count()? - count <= 6);
+
+    connection.set_fetch_size(hdbconnect::DEFAULT_FETCH_SIZE)?;
+    Ok(())
+}
+
+fn access_values_by_name(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("convert single values of a row, addressed by column name or index");
+    let mut row = connection
+        .query("select F1, F2 as SECOND, 'x' as F3 from TEST_ROW_ORDERING where F1 = 7")?
+        .into_single_row()?;
+    let f1: usize = row.get("F1")?;
+    assert_eq!(f1, 7);
+    debug!("the alias takes precedence, the column name also works");
+    let f2: i64 = row.get("SECOND")?;
+    assert_eq!(f2, 7);
+    let f2: String = row.get("F2")?;
+    assert_eq!(f2, "7");
+    let f3: Option<String> = row.get_by_index(2)?;
+    assert_eq!(f3.as_deref(), Some("x"));
+    assert_eq!(row.len(), 3);
+
+    debug!("errors name the column");
+    let err = row.get::<usize>("NO_SUCH_COLUMN").unwrap_err();
+    assert!(err.to_string().contains("NO_SUCH_COLUMN"));
+    assert!(row.get_by_index::<usize>(3).is_err());
+    row.next_value();
+    assert!(row.get::<usize>("F1").is_err());
+    let f3: String = row.get("F3")?;
+    assert_eq!(f3, "x");
+    Ok(())
+}
@@ -0,0 +1,46 @@
+mod test_utils;
+
+use hdbconnect::{HdbError, HdbResult};
+use log::{debug, info};
+use std::time::Duration;
+
+const SLEEP_3: &str = "DO BEGIN USING SQLSCRIPT_SYNC AS SYNCLIB; \
+                       CALL SYNCLIB:SLEEP_SECONDS(3); END;";
+
+// cargo test --test test_017_query_timeout -- --nocapture
+#[test]
+pub fn test_017_query_timeout() -> HdbResult<()> {
+    let mut _log_handle = test_utils::init_logger();
+    let mut connection = test_utils::get_authenticated_connection()?;
+    assert_eq!(connection.get_query_timeout()?, None);
+
+    info!("a statement that takes too long is cancelled");
+    connection.set_query_timeout(Duration::from_secs(1))?;
+    match connection.exec(SLEEP_3) {
+        Err(HdbError::QueryTimeout(server_error)) => {
+            debug!("got expected error: {:?}", server_error)
+        }
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    info!("the connection is still usable");
+    let one: i32 = connection.query("select 1 from dummy")?.try_into()?;
+    assert_eq!(one, 1);
+
+    info!("a prepared statement inherits the query timeout of the connection");
+    let mut stmt = connection.prepare(SLEEP_3)?;
+    match stmt.execute(&()) {
+        Err(HdbError::QueryTimeout(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    info!("a prepared statement can switch the query timeout off");
+    stmt.set_query_timeout(Duration::from_secs(0));
+    stmt.execute(&())?;
+
+    info!("the query timeout of the connection can be switched off");
+    connection.set_query_timeout(Duration::from_secs(0))?;
+    assert_eq!(connection.get_query_timeout()?, None);
+    connection.exec(SLEEP_3)?;
+    Ok(())
+}
@@ -0,0 +1,42 @@
+mod test_utils;
+
+use hdbconnect::{HdbError, HdbResult};
+use log::info;
+use std::thread;
+use std::time::Duration;
+
+const SLEEP_5: &str = "DO BEGIN USING SQLSCRIPT_SYNC AS SYNCLIB; \
+                       CALL SYNCLIB:SLEEP_SECONDS(5); END;";
+
+// cargo test --test test_018_cancel -- --nocapture
+#[test]
+pub fn test_018_cancel() -> HdbResult<()> {
+    let mut _log_handle = test_utils::init_logger();
+    let mut connection = test_utils::get_authenticated_connection()?;
+    let cancel_handle = connection.cancel_handle()?;
+    assert_eq!(cancel_handle.connection_id(), connection.id()?);
+
+    info!("a running statement is cancelled from another thread");
+    let handle = cancel_handle.clone();
+    let canceller = thread::spawn(move || {
+        thread::sleep(Duration::from_secs(1));
+        handle.cancel()
+    });
+    match connection.exec(SLEEP_5) {
+        Err(HdbError::Cancelled) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+    canceller.join().unwrap()?;
+
+    info!("the connection is still usable");
+    let one: i32 = connection.query("select 1 from dummy")?.try_into()?;
+    assert_eq!(one, 1);
+
+    info!("a cancellation without running statement does not affect later statements");
+    cancel_handle.cancel()?;
+    connection.exec(
+        "DO BEGIN USING SQLSCRIPT_SYNC AS SYNCLIB; \
+                     CALL SYNCLIB:SLEEP_SECONDS(1); END;",
+    )?;
+    Ok(())
+}
@@ -0,0 +1,34 @@
+mod test_utils;
+
+use hdbconnect::{Connection, HdbError, HdbResult, IntoConnectParams};
+use log::{debug, info};
+
+const SLEEP_3: &str = "DO BEGIN USING SQLSCRIPT_SYNC AS SYNCLIB; \
+                       CALL SYNCLIB:SLEEP_SECONDS(3); END;";
+
+// cargo test --test test_019_socket_timeouts -- --nocapture
+#[test]
+pub fn test_019_socket_timeouts() -> HdbResult<()> {
+    let mut _log_handle = test_utils::init_logger();
+
+    info!("the timeouts are taken from the url");
+    let url = test_utils::get_std_connect_url()?;
+    let url = format!(
+        "{}?connect_timeout=5&read_timeout=1&write_timeout=5&tcp_keepalive=30",
+        url.trim()
+    );
+    let mut connection = Connection::new(url.into_connect_params()?)?;
+    let one: i32 = connection.query("select 1 from dummy")?.try_into()?;
+    assert_eq!(one, 1);
+
+    info!("a read that takes too long is reported as timeout");
+    match connection.exec(SLEEP_3) {
+        Err(HdbError::Timeout(io_error)) => debug!("got expected error: {:?}", io_error),
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    info!("the connection is re-established with the next request");
+    let one: i32 = connection.query("select 1 from dummy")?.try_into()?;
+    assert_eq!(one, 1);
+    Ok(())
+}
@@ -1,9 +1,10 @@
 mod test_utils;
 
 use flexi_logger::ReconfigurationHandle;
-use hdbconnect::{Connection, HdbResult, HdbValue};
+use hdbconnect::{Connection, HdbError, HdbErrorKind, HdbResult, HdbValue};
 use log::{debug, info};
-use serde_derive::Deserialize;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // Test prepared statements, transactional correctness,
 // incl. parameter serialization (and resultset deserialization)
@@ -18,6 +19,11 @@ pub fn test_030_prepare() -> HdbResult<()> {
     prepare_multiple_errors(&mut log_handle, &mut connection)?;
     prepare_select_with_pars(&mut log_handle, &mut connection)?;
     prepare_select_without_pars(&mut log_handle, &mut connection)?;
+    prepare_named_parameters(&mut log_handle, &mut connection)?;
+    prepare_batch_with_failures(&mut log_handle, &mut connection)?;
+    prepare_split_batch(&mut log_handle, &mut connection)?;
+    prepare_with_statement_cache(&mut log_handle, &mut connection)?;
+    prepare_error_context(&mut log_handle, &mut connection)?;
     info!("{} calls to DB were executed", connection.get_call_count()?);
     Ok(())
 }
@@ -225,3 +231,241 @@ fn prepare_select_without_pars(
 
     Ok(())
 }
+
+fn prepare_named_parameters(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("prepared statement with named parameters");
+    let sum_positional: i64 = connection
+        .prepare("select sum(F2_I) from TEST_PREPARE where F2_I > ? and F2_I < ?")?
+        .execute(&(44_i32, 100_i32))?
+        .into_resultset()?
+        .try_into()?;
+
+    debug!("bind a struct, whose fields are in a different order than the parameters");
+    #[derive(Serialize)]
+    struct Range {
+        upper: i32,
+        comment: &'static str,
+        lower: i32,
+    }
+    let stmt_str = "select sum(F2_I) from TEST_PREPARE where F2_I > :lower and F2_I < :upper";
+    let mut stmt = connection.prepare_named(stmt_str)?;
+    let range = Range {
+        upper: 100,
+        comment: "not a parameter",
+        lower: 44,
+    };
+    let sum: i64 = stmt.execute(&range)?.into_resultset()?.try_into()?;
+    assert_eq!(sum, sum_positional);
+
+    debug!("bind a map");
+    let mut map = HashMap::new();
+    map.insert("upper", 100_i32);
+    map.insert("lower", 44_i32);
+    let sum: i64 = stmt.execute(&map)?.into_resultset()?.try_into()?;
+    assert_eq!(sum, sum_positional);
+
+    debug!("missing values and positional input are rejected");
+    map.remove("upper");
+    assert!(stmt.execute(&map).is_err());
+    assert!(stmt.execute(&(44_i32, 100_i32)).is_err());
+
+    debug!("conversion errors report the parameter name");
+    let mut bad_map = HashMap::new();
+    bad_map.insert("upper", "100");
+    bad_map.insert("lower", "not a number");
+    let error = stmt.execute(&bad_map).unwrap_err();
+    debug!("expected error: {}", error);
+    let context = error.context().unwrap();
+    assert_eq!(context.parameter(), Some(0));
+    assert_eq!(context.parameter_name(), Some("lower"));
+    Ok(())
+}
+
+fn prepare_batch_with_failures(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("batch execution with failing rows, reported per row");
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_PREPARE_BATCH"]);
+    connection.exec("create table TEST_PREPARE_BATCH (ID INT primary key, TEXT NVARCHAR(20))")?;
+
+    let mut stmt = connection.prepare("insert into TEST_PREPARE_BATCH (ID, TEXT) values(?, ?)")?;
+    stmt.add_batch(&(1_i32, "one"))?;
+    stmt.add_batch(&(2_i32, "two"))?;
+    stmt.add_batch(&(1_i32, "duplicate"))?;
+    stmt.add_batch(&(3_i32, "three"))?;
+    let batch_result = stmt.execute_batch_detailed()?;
+    debug!("batch result: {}", batch_result);
+    assert_eq!(batch_result.len(), 4);
+    assert!(batch_result.has_failures());
+    assert!(batch_result.failed_indices().contains(&2));
+    assert!(batch_result.server_error(2).is_some());
+    assert_eq!(batch_result.affected_rows(0), Some(1));
+
+    debug!("execute the failed rows again that have no error of their own");
+    let batch_result = stmt.execute_failed_rows(batch_result)?;
+    assert_eq!(batch_result.len(), 4);
+    assert_eq!(batch_result.failed_indices(), vec![2]);
+    assert!(batch_result.server_error(2).is_some());
+
+    let count: usize = connection
+        .query("select count(*) from TEST_PREPARE_BATCH")?
+        .try_into()?;
+    assert_eq!(count, 3);
+
+    debug!("a successful batch reports the affected rows");
+    stmt.add_batch(&(4_i32, "four"))?;
+    stmt.add_batch(&(5_i32, "five"))?;
+    let batch_result = stmt.execute_batch_detailed()?;
+    assert!(!batch_result.has_failures());
+    assert_eq!(batch_result.affected_rows(1), Some(1));
+    Ok(())
+}
+
+fn prepare_split_batch(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("big batches are split into several roundtrips");
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_PREPARE_SPLIT"]);
+    connection.exec("create table TEST_PREPARE_SPLIT (ID INT primary key, TEXT NVARCHAR(20))")?;
+    connection.set_max_batch_rows(3)?;
+    assert_eq!(connection.get_max_batch_rows()?, 3);
+
+    let mut stmt = connection.prepare("insert into TEST_PREPARE_SPLIT (ID, TEXT) values(?, ?)")?;
+    for i in 0..10_i32 {
+        stmt.add_batch(&(i, "split"))?;
+    }
+    let affected_rows = stmt.execute_batch()?.into_affected_rows()?;
+    assert_eq!(affected_rows.len(), 10);
+    assert!(affected_rows.iter().all(|count| *count == 1));
+
+    debug!("also the byte budget leads to splitting");
+    connection.set_max_batch_rows(hdbconnect::DEFAULT_MAX_BATCH_ROWS)?;
+    connection.set_max_batch_bytes(20)?;
+    for i in 10..15_i32 {
+        stmt.add_batch(&(i, "split by size"))?;
+    }
+    assert_eq!(stmt.execute_batch()?.into_affected_rows()?.len(), 5);
+
+    debug!("a failure in one roundtrip stops the remaining ones");
+    connection.set_max_batch_bytes(hdbconnect::DEFAULT_MAX_BATCH_BYTES)?;
+    connection.set_max_batch_rows(2)?;
+    for i in &[20_i32, 21, 0, 22, 23, 24] {
+        stmt.add_batch(&(*i, "split"))?;
+    }
+    let batch_result = stmt.execute_batch_detailed()?;
+    assert_eq!(batch_result.len(), 6);
+    assert_eq!(batch_result.affected_rows(0), Some(1));
+    assert!(batch_result.server_error(2).is_some());
+    assert!(batch_result.failed_indices().contains(&4));
+    assert!(batch_result.failed_indices().contains(&5));
+
+    connection.set_max_batch_rows(hdbconnect::DEFAULT_MAX_BATCH_ROWS)?;
+    Ok(())
+}
+
+fn prepare_with_statement_cache(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("repeated prepare calls reuse the statements of the statement cache");
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_PREPARE_CACHE"]);
+    connection.exec("create table TEST_PREPARE_CACHE (ID INT, TEXT NVARCHAR(20))")?;
+    connection.set_statement_cache_size(2)?;
+    assert_eq!(connection.get_statement_cache_size()?, 2);
+
+    let insert = "insert into TEST_PREPARE_CACHE (ID, TEXT) values(?, ?)";
+    let count = connection.get_call_count()?;
+    for i in 0..5_i32 {
+        let mut stmt = connection.prepare(insert)?;
+        stmt.execute(&(i, "cached"))?;
+    }
+    // one prepare and five executions; the statement is not dropped
+    assert_eq!(connection.get_call_count()? - count, 6);
+    assert_eq!(connection.get_statement_cache_misses()?, 1);
+    assert_eq!(connection.get_statement_cache_hits()?, 4);
+
+    debug!("two statements can share a cached statement");
+    let mut stmt1 = connection.prepare(insert)?;
+    let mut stmt2 = connection.prepare(insert)?;
+    stmt1.execute(&(5_i32, "shared"))?;
+    stmt2.execute(&(6_i32, "shared"))?;
+
+    debug!("the least recently used statement is evicted");
+    connection.prepare("select * from TEST_PREPARE_CACHE where ID = ?")?;
+    connection.prepare("select count(*) from TEST_PREPARE_CACHE")?;
+    connection.prepare(insert)?;
+    assert_eq!(connection.get_statement_cache_misses()?, 4);
+    stmt1.execute(&(7_i32, "evicted"))?;
+    drop(stmt1);
+    stmt2.execute(&(8_i32, "evicted"))?;
+    drop(stmt2);
+
+    let count: usize = connection
+        .query("select count(*) from TEST_PREPARE_CACHE")?
+        .try_into()?;
+    assert_eq!(count, 9);
+
+    connection.set_statement_cache_size(0)?;
+    Ok(())
+}
+
+fn prepare_error_context(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("errors carry the context in which they occured");
+    connection.multiple_statements_ignore_err(vec!["drop table TEST_PREPARE_CONTEXT"]);
+    connection.exec("create table TEST_PREPARE_CONTEXT (ID INT PRIMARY KEY, TEXT NVARCHAR(20))")?;
+
+    let insert = "insert into TEST_PREPARE_CONTEXT (ID, TEXT) values(?, ?)";
+    let mut stmt = connection.prepare(insert)?;
+    stmt.execute(&(1_i32, "first"))?;
+    let error = stmt.execute(&(1_i32, "duplicate")).unwrap_err();
+    debug!("expected error: {}", error);
+    assert_eq!(error.kind(), HdbErrorKind::UniqueConstraintViolation);
+    let context = error.context().unwrap();
+    assert_eq!(context.statement(), Some(insert));
+    assert_eq!(context.request_type(), Some("Execute"));
+    assert!(std::error::Error::source(&error).is_some());
+
+    let error = connection
+        .query("select * from TEST_PREPARE_NO_SUCH_TABLE")
+        .unwrap_err();
+    assert_eq!(error.kind(), HdbErrorKind::InvalidTableName);
+    assert_eq!(
+        error.context().unwrap().request_type(),
+        Some("ExecuteDirect")
+    );
+
+    debug!("conversion errors report the column");
+    let row = connection
+        .query("select ID, TEXT from TEST_PREPARE_CONTEXT")?
+        .into_single_row()?;
+    let error = row.get_by_index::<i32>(1).unwrap_err();
+    debug!("expected error: {}", error);
+    assert_eq!(error.context().unwrap().column(), Some(1));
+
+    let error = connection
+        .query("select ID, TEXT from TEST_PREPARE_CONTEXT")?
+        .into_single_row()?
+        .try_into::<(i32, i32)>()
+        .unwrap_err();
+    debug!("expected error: {}", error);
+    assert_eq!(error.context().unwrap().column(), Some(1));
+    match error {
+        HdbError::Deserialization(_, _) => {}
+        e => panic!("unexpected error {:?}", e),
+    }
+
+    let error = connection
+        .query("select ID, TEXT from TEST_PREPARE_CONTEXT")?
+        .try_into::<Vec<(i32, i32)>>()
+        .unwrap_err();
+    assert_eq!(error.context().unwrap().column(), Some(1));
+    Ok(())
+}
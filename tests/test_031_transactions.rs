@@ -2,7 +2,7 @@ mod test_utils;
 
 use chrono::NaiveDate;
 use flexi_logger::ReconfigurationHandle;
-use hdbconnect::{Connection, HdbError, HdbResult};
+use hdbconnect::{Connection, HdbError, HdbResult, IsolationLevel, TransactionState};
 use log::{debug, info};
 
 // From wikipedia:
@@ -41,7 +41,9 @@ pub fn test_031_transactions() -> HdbResult<()> {
     write1_read2(&mut log_handle, &mut connection, "REPEATABLE READ")?;
     write1_read2(&mut log_handle, &mut connection, "SERIALIZABLE")?;
 
-    // SET TRANSACTION { READ ONLY | READ WRITE }
+    isolation_level_and_read_only(&mut log_handle, &mut connection)?;
+    transaction_closure(&mut log_handle, &mut connection)?;
+    savepoints(&mut log_handle, &mut connection)?;
 
     // SET TRANSACTION LOCK WAIT TIMEOUT <unsigned_integer> // (milliseconds)
     // let result = conn.exec("SET TRANSACTION LOCK WAIT TIMEOUT 3000")?; // (milliseconds)
@@ -121,3 +123,157 @@ fn write1_read2(
 
     Ok(())
 }
+
+fn isolation_level_and_read_only(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("set and verify the isolation level, the transaction state and the read-only mode");
+    connection.set_isolation_level(IsolationLevel::RepeatableRead)?;
+    assert_eq!(
+        connection.isolation_level()?,
+        IsolationLevel::RepeatableRead
+    );
+
+    debug!("the isolation level that the server reports is tracked");
+    connection.exec("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE")?;
+    assert_eq!(connection.isolation_level()?, IsolationLevel::Serializable);
+    connection.set_isolation_level(IsolationLevel::ReadCommitted)?;
+
+    connection.dml("insert into TEST_TRANSACTIONS (strng,nmbr) values('state',7)")?;
+    assert_eq!(
+        connection.transaction_state()?,
+        TransactionState::WriteTransaction
+    );
+    connection.rollback()?;
+    assert_eq!(
+        connection.transaction_state()?,
+        TransactionState::RolledBack
+    );
+
+    debug!("in read-only mode, writing fails");
+    connection.set_read_only(true)?;
+    assert!(connection.is_read_only()?);
+    assert!(connection
+        .dml("insert into TEST_TRANSACTIONS (strng,nmbr) values('read-only',8)")
+        .is_err());
+    connection.rollback()?;
+    connection.set_read_only(false)?;
+    assert!(!connection.is_read_only()?);
+    Ok(())
+}
+
+fn transaction_closure(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("run closures in transactions that are committed or rolled back");
+    connection.set_auto_commit(true)?;
+    connection.set_transaction_retries(2)?;
+    connection.set_transaction_retry_backoff(std::time::Duration::from_millis(10))?;
+    let get_count = |conn: &mut Connection| -> HdbResult<usize> {
+        conn.query("select count(*) from TEST_TRANSACTIONS where nmbr = 9")?
+            .try_into()
+    };
+
+    debug!("Ok commits");
+    let affected = connection
+        .transaction(|tx| tx.dml("insert into TEST_TRANSACTIONS (strng,nmbr) values('tx1',9)"))?;
+    assert_eq!(affected, 1);
+    assert_eq!(get_count(connection)?, 1);
+    assert!(connection.is_auto_commit()?);
+
+    debug!("Err rolls back");
+    let result: HdbResult<()> = connection.transaction(|tx| {
+        tx.dml("insert into TEST_TRANSACTIONS (strng,nmbr) values('tx2',9)")?;
+        Err(HdbError::Usage("abort".to_owned()))
+    });
+    assert!(result.is_err());
+    assert_eq!(get_count(connection)?, 1);
+
+    debug!("only deadlocks and serialization failures are repeated");
+    let mut attempts = 0;
+    let result: HdbResult<()> = connection.transaction(|_| {
+        attempts += 1;
+        Err(HdbError::from(std::io::Error::new(
+            std::io::ErrorKind::TimedOut,
+            "timeout",
+        )))
+    });
+    assert!(result.is_err());
+    assert_eq!(attempts, 1);
+
+    debug!("a panic rolls back");
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        connection
+            .transaction(|tx| -> HdbResult<()> {
+                tx.dml("insert into TEST_TRANSACTIONS (strng,nmbr) values('tx3',9)")?;
+                panic!("panic in transaction");
+            })
+            .ok();
+    }));
+    assert!(result.is_err());
+    assert_eq!(get_count(connection)?, 1);
+    assert!(connection.is_auto_commit()?);
+
+    debug!("transactions cannot be nested");
+    let result = connection.transaction(|tx| {
+        tx.dml("insert into TEST_TRANSACTIONS (strng,nmbr) values('tx4',9)")?;
+        tx.transaction(|inner| inner.exec("select 1 from dummy"))
+    });
+    assert!(result.is_err());
+    assert_eq!(get_count(connection)?, 1);
+    let result =
+        connection.transaction(|tx| tx.transaction(|inner| inner.exec("select 1 from dummy")));
+    assert!(result.is_err());
+    connection.transaction(|tx| tx.exec("select 1 from dummy"))?;
+
+    connection.set_transaction_retries(0)?;
+    Ok(())
+}
+
+fn savepoints(
+    _log_handle: &mut ReconfigurationHandle,
+    connection: &mut Connection,
+) -> HdbResult<()> {
+    info!("roll back parts of a transaction with savepoints");
+    let get_count = |conn: &mut Connection| -> HdbResult<usize> {
+        conn.query("select count(*) from TEST_TRANSACTIONS where nmbr = 10")?
+            .try_into()
+    };
+    debug!("savepoints need auto-commit to be switched off");
+    connection.set_auto_commit(true)?;
+    assert!(connection.savepoint("SP").is_err());
+
+    connection.transaction(|tx| {
+        tx.dml("insert into TEST_TRANSACTIONS (strng,nmbr) values('sp1',10)")?;
+
+        let mut savepoint = tx.savepoint("SP1")?;
+        tx.dml("insert into TEST_TRANSACTIONS (strng,nmbr) values('sp2',10)")?;
+        savepoint.rollback_to()?;
+        assert_eq!(get_count(tx)?, 1);
+        tx.dml("insert into TEST_TRANSACTIONS (strng,nmbr) values('sp3',10)")?;
+        savepoint.release()?;
+
+        debug!("an unreleased savepoint rolls back when it is dropped");
+        {
+            let _savepoint = tx.savepoint("SP2")?;
+            tx.dml("insert into TEST_TRANSACTIONS (strng,nmbr) values('sp4',10)")?;
+            assert_eq!(get_count(tx)?, 3);
+        }
+        assert_eq!(get_count(tx)?, 2);
+        Ok(())
+    })?;
+    assert_eq!(get_count(connection)?, 2);
+
+    debug!("a savepoint that outlives its transaction has no effect");
+    let mut escaped = connection.transaction(|tx| tx.savepoint("SP3"))?;
+    connection.set_auto_commit(false)?;
+    connection.dml("insert into TEST_TRANSACTIONS (strng,nmbr) values('sp5',10)")?;
+    assert!(escaped.rollback_to().is_err());
+    drop(escaped);
+    connection.commit()?;
+    connection.set_auto_commit(true)?;
+    assert_eq!(get_count(connection)?, 3);
+    Ok(())
+}
@@ -214,6 +214,22 @@ fn test_streaming(
         blob.max_buf_len()
     );
 
+    debug!("write big blob with a statement-level lob write length");
+    stmt.set_lob_write_length(50_000);
+    let reader = std::sync::Arc::new(std::sync::Mutex::new(std::io::Cursor::new(
+        random_bytes.clone(),
+    )));
+    let count = connection.get_call_count()?;
+    stmt.execute_row(vec![
+        HdbValue::STRING("streaming3".to_string()),
+        HdbValue::LOBSTREAM(Some(reader)),
+    ])?;
+    assert!((connection.get_call_count()? - count) as usize > random_bytes.len() / 50_000);
+    let length: usize = connection
+        .query("select length(bindata_NN) from TEST_BLOBS where desc = 'streaming3'")?
+        .try_into()?;
+    assert_eq!(length, random_bytes.len());
+
     connection.set_auto_commit(true)?;
     Ok(())
 }